@@ -10,6 +10,8 @@ use ndarray::{
     ArrayViewMut,
     OwnedArray,
     Ix,
+    Zip,
+    aview2,
 };
 
 type Ix2 = (Ix, Ix);
@@ -29,21 +31,13 @@ fn conv_3x3<F>(a: &ArrayView<F, Ix2>, out: &mut ArrayViewMut<F, Ix2>, kernel: &K
         return;
     }
     assert!(np >= n && mp >= m);
-    // i, j offset by -1 so that we can use unsigned indices
-    unsafe {
-        for i in 0..n - 2 {
-            for j in 0..m - 2 {
-                let mut conv = F::zero();
-                for k in 0..3 {
-                    for l in 0..3 {
-                        conv = conv + *a.uget((i + k, j + l)) * kernel[k][l];
-                        //conv += a[[i + k, j + l]] * x_kernel[k][l];
-                    }
-                }
-                *out.uget_mut((i + 1, j + 1)) = conv;
-            }
-        }
-    }
+    let kernel = aview2(kernel);
+    // each 3 × 3 window of a sets the element of out at its center
+    Zip::from(out.slice_mut(s![1..n as isize - 1, 1..m as isize - 1]))
+        .and(a.windows((3, 3)))
+        .apply(|elt, window| {
+            *elt = window.iter().zip(&kernel).fold(F::zero(), |acc, (&x, &k)| acc + x * k);
+        });
 }
 
 fn main() {
@@ -3,9 +3,11 @@
 extern crate ndarray;
 extern crate ndarray_rand;
 extern crate rand;
+extern crate num;
 
 use ndarray_rand::{RandomExt, F32};
 use rand::Rng;
+use num::{Complex, Zero, One};
 
 use ndarray::{
     ArrayBase,
@@ -17,6 +19,7 @@ use ndarray::{
     LinalgScalar,
     Ix,
 };
+use ndarray::linalg::ComplexField;
 
 use rand::distributions::Normal;
 
@@ -62,6 +65,108 @@ fn gen_f64<D>(d: D) -> OwnedArray<f64, D>
     OwnedArray::random(d, Normal::new(0., 1.))
 }
 
+fn gen_c64<D>(d: D) -> OwnedArray<Complex<f64>, D>
+    where D: Dimension,
+{
+    let re = gen_f64(d.clone());
+    let im = gen_f64(d);
+    let mut c = re.mapv(|x| Complex::new(x, 0.));
+    c.zip_mut_with(&im, |x, &y| x.im = y);
+    c
+}
+
+fn conj_t<A: ComplexField>(a: &OwnedArray<A, (Ix, Ix)>) -> OwnedArray<A, (Ix, Ix)> {
+    a.t().map(|x| x.conj())
+}
+
+/// Scale the columns of `u` by `s`
+fn scale_columns<A: ComplexField>(u: &OwnedArray<A, (Ix, Ix)>, s: &OwnedArray<A::Real, Ix>)
+    -> OwnedArray<A, (Ix, Ix)>
+{
+    let mut res = u.clone();
+    for (mut column, &x) in res.axis_iter_mut(Axis(1)).zip(s) {
+        column.mapv_inplace(|y| y * A::from_real(x));
+    }
+    res
+}
+
+/// Panic unless `a` and `b` are equal within `tol` relative to the largest
+/// element of `b`
+fn assert_rel_close<A: ComplexField>(a: &OwnedArray<A, (Ix, Ix)>,
+                                     b: &OwnedArray<A, (Ix, Ix)>,
+                                     tol: A::Real)
+{
+    assert_eq!(a.shape(), b.shape());
+    let scale = b.iter().fold(A::Real::one(), |acc, x| if x.abs() > acc { x.abs() } else { acc });
+    let maxdiff = a.iter().zip(b).fold(A::Real::zero(), |acc, (&x, &y)| {
+        let d = (x - y).abs();
+        if d > acc { d } else { acc }
+    });
+    println!("max difference relative to scale = {:.2e}", maxdiff / scale);
+    if maxdiff > tol * scale {
+        panic!("results differ");
+    }
+}
+
+fn check_eigh<A: ComplexField>(a: OwnedArray<A, (Ix, Ix)>, tol: A::Real) {
+    // make it hermitian
+    let a = &a + &conj_t(&a);
+    let n = a.rows();
+    let (w, v) = a.eigh().unwrap();
+    for i in 1..n {
+        assert!(w[i - 1] <= w[i]);
+    }
+    assert_rel_close(&reference_mat_mul(&conj_t(&v), &v), &OwnedArray::eye(n), tol);
+    let reconstructed = reference_mat_mul(&scale_columns(&v, &w), &conj_t(&v));
+    assert_rel_close(&reconstructed, &a, tol);
+}
+
+fn check_svd<A: ComplexField>(a: OwnedArray<A, (Ix, Ix)>, tol: A::Real) {
+    let (m, n) = a.dim();
+    let k = if m < n { m } else { n };
+    let (u, s, vt) = a.svd();
+    for i in 1..k {
+        assert!(s[i - 1] >= s[i]);
+    }
+    assert_rel_close(&reference_mat_mul(&conj_t(&u), &u), &OwnedArray::eye(k), tol);
+    assert_rel_close(&reference_mat_mul(&vt, &conj_t(&vt)), &OwnedArray::eye(k), tol);
+    assert_rel_close(&reference_mat_mul(&scale_columns(&u, &s), &vt), &a, tol);
+
+    let (u, s_full, vt) = a.svd_full();
+    assert_eq!(s_full.len(), k);
+    assert_rel_close(&reference_mat_mul(&conj_t(&u), &u), &OwnedArray::eye(m), tol);
+    assert_rel_close(&reference_mat_mul(&conj_t(&vt), &vt), &OwnedArray::eye(n), tol);
+}
+
+#[test]
+fn accurate_eigh() {
+    let mut rng = rand::weak_rng();
+    for _ in 0..10 {
+        let n = rng.gen_range(1, 40);
+        println!("Testing size {} by {}", n, n);
+        check_eigh(gen((n, n)), 1e-4);
+        check_eigh(gen_f64((n, n)), 1e-12);
+        check_eigh(gen_c64((n, n)), 1e-12);
+    }
+}
+
+#[test]
+fn accurate_svd() {
+    let mut rng = rand::weak_rng();
+    for _ in 0..10 {
+        let m = rng.gen_range(1, 40);
+        let n = rng.gen_range(1, 40);
+        println!("Testing size {} by {}", m, n);
+        check_svd(gen((m, n)), 1e-4);
+        check_svd(gen_f64((m, n)), 1e-12);
+        check_svd(gen_c64((m, n)), 1e-12);
+    }
+    // rank deficient
+    let a = gen_f64((30, 3));
+    let b = gen_f64((3, 20));
+    check_svd(reference_mat_mul(&a, &b), 1e-12);
+}
+
 #[test]
 fn accurate_eye_f32() {
     for i in 0..20 {
@@ -159,7 +264,7 @@ fn accurate_mul_f32() {
         let crtol = c.mapv(|x| x.abs() * rtol);
         let tol = crtol + atol;
         let tol_m_diff = &diff - &tol;
-        let maxdiff = *tol_m_diff.max();
+        let maxdiff = tol_m_diff.scalar_max().unwrap();
         println!("diff offset from tolerance level= {:.2e}", maxdiff);
         if maxdiff > 0. {
             panic!("results differ");
@@ -193,7 +298,7 @@ fn accurate_mul_f64() {
         let crtol = c.mapv(|x| x.abs() * rtol);
         let tol = crtol + atol;
         let tol_m_diff = &diff - &tol;
-        let maxdiff = *tol_m_diff.max();
+        let maxdiff = tol_m_diff.scalar_max().unwrap();
         println!("diff offset from tolerance level= {:.2e}", maxdiff);
         if maxdiff > 0. {
             panic!("results differ");
@@ -207,8 +312,6 @@ trait Utils {
     type Elem;
     type Dim;
     type Data;
-    fn max(&self) -> &Self::Elem
-        where Self::Elem: PartialOrd;
     fn lift<F>(&mut self, F)
         where F: FnMut(Self::Elem) -> Self::Elem, Self::Elem: Copy,
               Self::Data: DataMut;
@@ -226,21 +329,6 @@ impl<A, S, D> Utils for ArrayBase<S, D>
     type Dim = D;
     type Data = S;
 
-    fn max(&self) -> &A
-        where A: PartialOrd
-    {
-        let mut iter = self.iter();
-        if let Some(mut max) = iter.next() {
-            for elt in iter {
-                if elt > max {
-                    max = elt;
-                }
-            }
-            max
-        } else {
-            panic!("empty");
-        }
-    }
     fn lift<F>(&mut self, mut f: F)
         where F: FnMut(A) -> A, A: Copy,
               S: DataMut<Elem=A>
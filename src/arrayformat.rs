@@ -8,78 +8,304 @@
 use std::fmt;
 use super::{
     ArrayBase,
+    ArrayView,
+    Axis,
     Data,
     Dimension,
+    Ix,
+    RawData,
 };
 
-fn format_array<A, S, D, F>(view: &ArrayBase<S, D>, f: &mut fmt::Formatter,
-                            mut format: F)
-    -> fmt::Result
-    where F: FnMut(&A, &mut fmt::Formatter) -> fmt::Result,
+/// Options for formatting arrays.
+///
+/// Arrays with more elements than the *threshold* are summarized: along
+/// axes longer than twice the number of *edge items*, only the first and
+/// last edge items are shown, with `...` in between. The elements are
+/// right-aligned to the width of the widest shown element, or aligned with
+/// the fill and alignment of the format string if it has one, like
+/// `{:*<4}`, unless alignment is turned off.
+///
+/// The formatting traits of arrays use the default options; use
+/// [`.format_with()`](struct.ArrayBase.html#method.format_with) to format
+/// an array with other options.
+///
+/// ```
+/// use ndarray::{OwnedArray, FormatOptions};
+///
+/// let a = OwnedArray::range(0., 10., 1.);
+/// let opts = FormatOptions::new().threshold(5).edge_items(2);
+/// assert_eq!(format!("{}", a.format_with(opts)), "[0, 1, ..., 8, 9]");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FormatOptions {
+    threshold: usize,
+    edge_items: usize,
+    align: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            threshold: 1000,
+            edge_items: 3,
+            align: true,
+        }
+    }
+}
+
+impl FormatOptions {
+    /// Create the default options: summarize arrays of more than 1000
+    /// elements with 3 edge items, and align the elements.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Summarize arrays with strictly more than `threshold` elements.
+    pub fn threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Show `edge_items` elements at the start and end of each summarized
+    /// axis.
+    pub fn edge_items(mut self, edge_items: usize) -> Self {
+        self.edge_items = edge_items;
+        self
+    }
+
+    /// Set whether to align the elements to a common width.
+    pub fn align(mut self, align: bool) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Return the indices to show along an axis of length `len`; `None`
+    /// marks the elided elements.
+    fn shown_indices(&self, len: Ix, summarize: bool) -> Vec<Option<Ix>> {
+        if summarize && len > 2 * self.edge_items {
+            (0..self.edge_items).map(Some)
+                .chain(Some(None))
+                .chain((len - self.edge_items..len).map(Some))
+                .collect()
+        } else {
+            (0..len).map(Some).collect()
+        }
+    }
+}
+
+/// An array with options for formatting it.
+///
+/// See [`.format_with()`](struct.ArrayBase.html#method.format_with).
+pub struct FormatWith<'a, S: 'a, D: 'a>
+    where S: RawData
+{
+    array: &'a ArrayBase<S, D>,
+    options: FormatOptions,
+}
+
+impl<A, S, D> ArrayBase<S, D>
+    where S: Data<Elem=A>,
           D: Dimension,
-          S: Data<Elem=A>,
 {
-    let ndim = view.dim.slice().len();
-    /* private nowadays
-    if ndim > 0 && f.width.is_none() {
-        f.width = Some(4)
-    }
-    */
-    // None will be an empty iter.
-    let mut last_index = match view.dim.first_index() {
-        None => view.dim.clone(),
-        Some(ix) => ix,
+    /// Return the array with the formatting options `options`, for use
+    /// with any of the formatting traits that the array implements.
+    ///
+    /// ```
+    /// use ndarray::{arr2, FormatOptions};
+    ///
+    /// let a = arr2(&[[1, 20],
+    ///                [300, 4]]);
+    /// assert_eq!(format!("{}", a), "[[  1,  20],\n [300,   4]]");
+    /// let opts = FormatOptions::new().align(false);
+    /// assert_eq!(format!("{}", a.format_with(opts)), "[[1, 20],\n [300, 4]]");
+    /// ```
+    pub fn format_with(&self, options: FormatOptions) -> FormatWith<S, D> {
+        FormatWith {
+            array: self,
+            options: options,
+        }
+    }
+}
+
+/// Forward to the element formatting function `format`.
+struct Elem<'a, A: 'a, F: 'a>(&'a A, &'a F);
+
+impl<'a, A, F> fmt::Display for Elem<'a, A, F>
+    where F: Fn(&A, &mut fmt::Formatter) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self.1)(self.0, f)
+    }
+}
+
+/// Format `elt` to a string, with the formatting parameters of `f`.
+fn render<T: fmt::Display>(elt: T, f: &fmt::Formatter) -> String {
+    let align = explicit_align(f);
+    let s = render_unaligned(elt, f, if align.is_some() { 0 } else { f.width().unwrap_or(0) });
+    match align {
+        Some(align) => pad(s, f.width().unwrap_or(0), f.fill(), align),
+        None => s,
+    }
+}
+
+/// Return the alignment requested in `f`, unless zero padding takes
+/// precedence over it.
+fn explicit_align(f: &fmt::Formatter) -> Option<fmt::Alignment> {
+    if f.sign_aware_zero_pad() { None } else { f.align() }
+}
+
+/// Pad `s` with `fill` to `width` characters, as `align` says.
+fn pad(s: String, width: usize, fill: char, align: fmt::Alignment) -> String {
+    let len = s.chars().count();
+    if len >= width {
+        return s;
+    }
+    let (pre, post) = match align {
+        fmt::Alignment::Left => (0, width - len),
+        fmt::Alignment::Right => (width - len, 0),
+        fmt::Alignment::Center => ((width - len) / 2, (width - len + 1) / 2),
     };
-    for _ in 0..ndim {
-        try!(write!(f, "["));
-    }
-    let mut first = true;
-    // Simply use the indexed iterator, and take the index wraparounds
-    // as cues for when to add []'s and how many to add.
-    for (index, elt) in view.indexed_iter() {
-        let take_n = if ndim == 0 { 1 } else { ndim - 1 };
-        let mut update_index = false;
-        for (i, (a, b)) in index.slice()
-                                .iter()
-                                .take(take_n)
-                                .zip(last_index.slice().iter())
-                                .enumerate() {
-            if a != b {
-                // New row.
-                // # of ['s needed
-                let n = ndim - i - 1;
-                for _ in 0..n {
-                    try!(write!(f, "]"));
-                }
-                try!(write!(f, ","));
+    let mut padded = String::with_capacity(s.len() + (pre + post) * fill.len_utf8());
+    padded.extend((0..pre).map(|_| fill));
+    padded.push_str(&s);
+    padded.extend((0..post).map(|_| fill));
+    padded
+}
+
+/// Format `elt` to a string, with width `w` and the formatting parameters
+/// of `f` except for width, fill and alignment.
+fn render_unaligned<T: fmt::Display>(elt: T, f: &fmt::Formatter, w: usize) -> String {
+    match (f.precision(), f.sign_plus(), f.alternate(), f.sign_aware_zero_pad()) {
+        (None, false, false, false) => format!("{:w$}", elt, w = w),
+        (None, false, false, true) => format!("{:0w$}", elt, w = w),
+        (None, false, true, false) => format!("{:#w$}", elt, w = w),
+        (None, false, true, true) => format!("{:#0w$}", elt, w = w),
+        (None, true, false, false) => format!("{:+w$}", elt, w = w),
+        (None, true, false, true) => format!("{:+0w$}", elt, w = w),
+        (None, true, true, false) => format!("{:+#w$}", elt, w = w),
+        (None, true, true, true) => format!("{:+#0w$}", elt, w = w),
+        (Some(p), false, false, false) => format!("{:w$.p$}", elt, w = w, p = p),
+        (Some(p), false, false, true) => format!("{:0w$.p$}", elt, w = w, p = p),
+        (Some(p), false, true, false) => format!("{:#w$.p$}", elt, w = w, p = p),
+        (Some(p), false, true, true) => format!("{:#0w$.p$}", elt, w = w, p = p),
+        (Some(p), true, false, false) => format!("{:+w$.p$}", elt, w = w, p = p),
+        (Some(p), true, false, true) => format!("{:+0w$.p$}", elt, w = w, p = p),
+        (Some(p), true, true, false) => format!("{:+#w$.p$}", elt, w = w, p = p),
+        (Some(p), true, true, true) => format!("{:+#0w$.p$}", elt, w = w, p = p),
+    }
+}
+
+/// Format the shown elements of `view` to strings, in logical order.
+fn render_elements<A, F>(view: ArrayView<A, Vec<Ix>>, options: &FormatOptions,
+                         summarize: bool, f: &fmt::Formatter, format: &F,
+                         out: &mut Vec<String>)
+    where F: Fn(&A, &mut fmt::Formatter) -> fmt::Result,
+{
+    if view.ndim() == 0 {
+        out.push(render(Elem(view.iter().next().unwrap(), format), f));
+        return;
+    }
+    for index in options.shown_indices(view.shape()[0], summarize) {
+        if let Some(i) = index {
+            render_elements(view.subview(Axis(0), i), options, summarize, f, format, out);
+        }
+    }
+}
+
+fn write_view<A, I>(view: ArrayView<A, Vec<Ix>>, depth: usize, options: &FormatOptions,
+                    summarize: bool, width: usize, elements: &mut I,
+                    f: &mut fmt::Formatter)
+    -> fmt::Result
+    where I: Iterator<Item=String>,
+{
+    if view.ndim() == 0 {
+        let mut elt = elements.next().unwrap();
+        if options.align {
+            // columns are padded like the elements, or right aligned
+            elt = match explicit_align(f) {
+                Some(align) => pad(elt, width, f.fill(), align),
+                None => pad(elt, width, ' ', fmt::Alignment::Right),
+            };
+        }
+        return write!(f, "{}", elt);
+    }
+    if view.shape()[0] == 0 {
+        // nothing to show: only the brackets of the remaining axes, like `[[]]`
+        for _ in 0..view.ndim() {
+            try!(write!(f, "["));
+        }
+        for _ in 0..view.ndim() {
+            try!(write!(f, "]"));
+        }
+        return Ok(());
+    }
+    try!(write!(f, "["));
+    for (n, index) in options.shown_indices(view.shape()[0], summarize).into_iter().enumerate() {
+        if n > 0 {
+            try!(write!(f, ","));
+            if view.ndim() == 1 {
+                try!(write!(f, " "));
+            } else {
                 if !f.alternate() {
                     try!(write!(f, "\n"));
                 }
-                for _ in 0..ndim - n {
+                for _ in 0..depth + 1 {
                     try!(write!(f, " "));
                 }
-                for _ in 0..n {
-                    try!(write!(f, "["));
-                }
-                first = true;
-                update_index = true;
-                break;
             }
         }
-        if !first {
-            try!(write!(f, ", "));
+        match index {
+            Some(i) => try!(write_view(view.subview(Axis(0), i), depth + 1, options,
+                                       summarize, width, elements, f)),
+            None => try!(write!(f, "...")),
         }
-        first = false;
-        try!(format(elt, f));
+    }
+    write!(f, "]")
+}
 
-        if update_index {
-            last_index = index;
-        }
+fn format_array<A, S, D, F>(view: &ArrayBase<S, D>, f: &mut fmt::Formatter,
+                            options: &FormatOptions, format: F)
+    -> fmt::Result
+    where F: Fn(&A, &mut fmt::Formatter) -> fmt::Result,
+          D: Dimension,
+          S: Data<Elem=A>,
+{
+    let view = unsafe {
+        ArrayView::new_(view.ptr, view.dim.slice().to_vec(), view.strides.slice().to_vec())
+    };
+    let summarize = view.len() > options.threshold;
+    let mut elements = Vec::new();
+    render_elements(view.view(), options, summarize, f, &format, &mut elements);
+    let width = elements.iter().map(|s| s.chars().count()).max().unwrap_or(0);
+    write_view(view, 0, options, summarize, width, &mut elements.into_iter(), f)
+}
+
+macro_rules! impl_format_with {
+    ($trait_:ident) => {
+impl<'a, A: fmt::$trait_, S, D: Dimension> fmt::$trait_ for FormatWith<'a, S, D>
+    where S: Data<Elem=A>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        format_array(self.array, f, &self.options, <A as fmt::$trait_>::fmt)
     }
-    for _ in 0..ndim {
-        try!(write!(f, "]"));
+}
+    }
+}
+
+impl_format_with!(Display);
+impl_format_with!(LowerExp);
+impl_format_with!(UpperExp);
+impl_format_with!(LowerHex);
+
+impl<'a, A: fmt::Debug, S, D: Dimension> fmt::Debug for FormatWith<'a, S, D>
+    where S: Data<Elem=A>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Add extra information for Debug
+        try!(format_array(self.array, f, &self.options, <A as fmt::Debug>::fmt));
+        try!(write!(f, " shape={:?}, strides={:?}", self.array.shape(), self.array.strides()));
+        Ok(())
     }
-    Ok(())
 }
 
 // NOTE: We can impl other fmt traits here
@@ -87,12 +313,13 @@ fn format_array<A, S, D, F>(view: &ArrayBase<S, D>, f: &mut fmt::Formatter,
 /// to each element.
 ///
 /// The array is shown in multiline style, unless the alternate form 
-/// is used, `{:#}`.
+/// is used, `{:#}`. Large arrays are summarized, see
+/// [`FormatOptions`](struct.FormatOptions.html).
 impl<'a, A: fmt::Display, S, D: Dimension> fmt::Display for ArrayBase<S, D>
     where S: Data<Elem=A>,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        format_array(self, f, <_>::fmt)
+        fmt::Display::fmt(&self.format_with(FormatOptions::default()), f)
     }
 }
 
@@ -100,15 +327,13 @@ impl<'a, A: fmt::Display, S, D: Dimension> fmt::Display for ArrayBase<S, D>
 /// to each element.
 ///
 /// The array is shown in multiline style, unless the alternate form 
-/// is used, `{:#?}`.
+/// is used, `{:#?}`. Large arrays are summarized, see
+/// [`FormatOptions`](struct.FormatOptions.html).
 impl<'a, A: fmt::Debug, S, D: Dimension> fmt::Debug for ArrayBase<S, D>
     where S: Data<Elem=A>,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        // Add extra information for Debug
-        try!(format_array(self, f, <_>::fmt));
-        try!(write!(f, " shape={:?}, strides={:?}", self.shape(), self.strides()));
-        Ok(())
+        fmt::Debug::fmt(&self.format_with(FormatOptions::default()), f)
     }
 }
 
@@ -116,12 +341,13 @@ impl<'a, A: fmt::Debug, S, D: Dimension> fmt::Debug for ArrayBase<S, D>
 /// to each element.
 ///
 /// The array is shown in multiline style, unless the alternate form
-/// is used, `{:#e}`.
+/// is used, `{:#e}`. Large arrays are summarized, see
+/// [`FormatOptions`](struct.FormatOptions.html).
 impl<'a, A: fmt::LowerExp, S, D: Dimension> fmt::LowerExp for ArrayBase<S, D>
     where S: Data<Elem=A>,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        format_array(self, f, <_>::fmt)
+        fmt::LowerExp::fmt(&self.format_with(FormatOptions::default()), f)
     }
 }
 
@@ -129,24 +355,25 @@ impl<'a, A: fmt::LowerExp, S, D: Dimension> fmt::LowerExp for ArrayBase<S, D>
 /// to each element.
 ///
 /// The array is shown in multiline style, unless the alternate form
-/// is used, `{:#E}`.
+/// is used, `{:#E}`. Large arrays are summarized, see
+/// [`FormatOptions`](struct.FormatOptions.html).
 impl<'a, A: fmt::UpperExp, S, D: Dimension> fmt::UpperExp for ArrayBase<S, D>
     where S: Data<Elem=A>,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        format_array(self, f, <_>::fmt)
+        fmt::UpperExp::fmt(&self.format_with(FormatOptions::default()), f)
     }
 }
 /// Format the array using `LowerHex` and apply the formatting parameters used
 /// to each element.
 ///
 /// The array is shown in multiline style, unless the alternate form
-/// is used, `{:#x}`.
+/// is used, `{:#x}`. Large arrays are summarized, see
+/// [`FormatOptions`](struct.FormatOptions.html).
 impl<'a, A: fmt::LowerHex, S, D: Dimension> fmt::LowerHex for ArrayBase<S, D>
     where S: Data<Elem=A>,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        format_array(self, f, <_>::fmt)
+        fmt::LowerHex::fmt(&self.format_with(FormatOptions::default()), f)
     }
 }
-
@@ -5,63 +5,209 @@
 // <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
-use serde::ser::impls::SeqIteratorVisitor;
-use serde::{self, Serialize, Deserialize};
-
-use super::{
-    Array,
-    Dimension,
-    Ix,
-    Elements,
-};
-
-struct AVisitor<'a, A: 'a, D: 'a> {
-    arr: &'a RcArray<A, D>,
-    state: u32,
-}
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::ser::{SerializeSeq, SerializeStruct};
+use serde::de::{self, Visitor, SeqAccess, MapAccess};
+
+use imp_prelude::*;
+use arraytraits::ARRAY_FORMAT_VERSION;
+use Elements;
 
-impl<A: Serialize, D: Serialize> Serialize for RcArray<A, D>
-    where D: Dimension
+/// The elements of an array in logical order, serialized as a sequence.
+struct Sequence<'a, A: 'a, D>(Elements<'a, A, D>);
+
+impl<'a, A, D> Serialize for Sequence<'a, A, D>
+    where A: Serialize,
+          D: Dimension,
 {
-    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
-        where S: serde::Serializer
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+        where Se: Serializer
     {
-        serializer.visit_named_map("Array",
-                                   AVisitor {
-                                       arr: self,
-                                       state: 0,
-                                   })
+        let iter = self.0.clone();
+        let mut seq = try!(serializer.serialize_seq(Some(iter.len())));
+        for elt in iter {
+            try!(seq.serialize_element(elt));
+        }
+        seq.end()
     }
 }
 
-impl<'a, A: Serialize, D: Serialize> Serialize for Elements<'a, A, D>
-    where D: Dimension
+/// **Requires crate feature `"serde"`**
+///
+/// The array is serialized as a struct with the fields `v` (the format
+/// version), `dim` (the shape, in the form of `D`) and `data` (the elements
+/// in logical order), the same as with `"rustc-serialize"`.
+impl<A, S, D> Serialize for ArrayBase<S, D>
+    where A: Serialize,
+          D: Dimension + Serialize,
+          S: Data<Elem = A>
 {
-    fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
-        where S: serde::Serializer
+    fn serialize<Se>(&self, serializer: Se) -> Result<Se::Ok, Se::Error>
+        where Se: Serializer
     {
-        serializer.visit_seq(SeqIteratorVisitor::new(self.clone(), None))
+        let mut state = try!(serializer.serialize_struct("Array", 3));
+        try!(state.serialize_field("v", &ARRAY_FORMAT_VERSION));
+        try!(state.serialize_field("dim", &self.dim));
+        try!(state.serialize_field("data", &Sequence(self.iter())));
+        state.end()
     }
 }
 
-impl<'a, A, D> serde::ser::MapVisitor for AVisitor<'a, A, D>
-    where A: Serialize,
-          D: Serialize + Dimension
-{
-    fn visit<S>(&mut self, serializer: &mut S) -> Result<Option<()>, S::Error>
-        where S: serde::Serializer
+const FIELDS: &'static [&'static str] = &["v", "dim", "data"];
+
+enum Field {
+    Version,
+    Dim,
+    Data,
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<De>(deserializer: De) -> Result<Field, De::Error>
+        where De: Deserializer<'de>
     {
-        match self.state {
-            0 => {
-                self.state += 1;
-                Ok(Some(try!(serializer.visit_map_elt("shape", self.arr.dim()))))
+        struct FieldVisitor;
+
+        impl<'de> Visitor<'de> for FieldVisitor {
+            type Value = Field;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("`v`, `dim` or `data`")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Field, E>
+                where E: de::Error
+            {
+                match value {
+                    "v" => Ok(Field::Version),
+                    "dim" => Ok(Field::Dim),
+                    "data" => Ok(Field::Data),
+                    _ => Err(de::Error::unknown_field(value, FIELDS)),
+                }
             }
-            1 => {
-                self.state += 1;
-                Ok(Some(try!(serializer.visit_map_elt("data", self.arr.iter()))))
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+struct ArrayVisitor<S, D> {
+    marker: PhantomData<(S, D)>,
+}
+
+fn verify_version<E: de::Error>(version: u8) -> Result<(), E> {
+    if version > ARRAY_FORMAT_VERSION {
+        Err(de::Error::custom(format_args!("unknown array version: {}", version)))
+    } else {
+        Ok(())
+    }
+}
+
+fn build_array<A, S, D, E>(dim: D, data: Vec<A>) -> Result<ArrayBase<S, D>, E>
+    where S: DataOwned<Elem = A>,
+          D: Dimension,
+          E: de::Error,
+{
+    ArrayBase::from_vec_dim(dim, data).map_err(|_| {
+        de::Error::custom("data and dimension must match in size")
+    })
+}
+
+impl<'de, A, S, D> Visitor<'de> for ArrayVisitor<S, D>
+    where A: Deserialize<'de>,
+          D: Dimension + Deserialize<'de>,
+          S: DataOwned<Elem = A>
+{
+    type Value = ArrayBase<S, D>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("ndarray representation")
+    }
+
+    fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+        where V: SeqAccess<'de>
+    {
+        let version: u8 = match try!(visitor.next_element()) {
+            Some(value) => value,
+            None => return Err(de::Error::invalid_length(0, &self)),
+        };
+        try!(verify_version(version));
+        let dim: D = match try!(visitor.next_element()) {
+            Some(value) => value,
+            None => return Err(de::Error::invalid_length(1, &self)),
+        };
+        let data: Vec<A> = match try!(visitor.next_element()) {
+            Some(value) => value,
+            None => return Err(de::Error::invalid_length(2, &self)),
+        };
+        build_array(dim, data)
+    }
+
+    fn visit_map<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+        where V: MapAccess<'de>
+    {
+        let mut version: Option<u8> = None;
+        let mut dim: Option<D> = None;
+        let mut data: Option<Vec<A>> = None;
+        while let Some(key) = try!(visitor.next_key()) {
+            match key {
+                Field::Version => {
+                    if version.is_some() {
+                        return Err(de::Error::duplicate_field("v"));
+                    }
+                    let value = try!(visitor.next_value());
+                    try!(verify_version(value));
+                    version = Some(value);
+                }
+                Field::Dim => {
+                    if dim.is_some() {
+                        return Err(de::Error::duplicate_field("dim"));
+                    }
+                    dim = Some(try!(visitor.next_value()));
+                }
+                Field::Data => {
+                    if data.is_some() {
+                        return Err(de::Error::duplicate_field("data"));
+                    }
+                    data = Some(try!(visitor.next_value()));
+                }
             }
-            _ => Ok(None),
         }
+        if version.is_none() {
+            return Err(de::Error::missing_field("v"));
+        }
+        let dim = match dim {
+            Some(dim) => dim,
+            None => return Err(de::Error::missing_field("dim")),
+        };
+        let data = match data {
+            Some(data) => data,
+            None => return Err(de::Error::missing_field("data")),
+        };
+        build_array(dim, data)
+    }
+}
 
+/// **Requires crate feature `"serde"`**
+///
+/// Deserializes into `OwnedArray` and `RcArray`. **Errors** if the format
+/// version is unknown or if the number of elements does not match the
+/// shape.
+///
+/// The elements are deserialized into a new `Vec`, which becomes the
+/// array’s storage without being copied again. Deserializing a view that
+/// borrows the elements from the input is not supported, since the
+/// elements are a sequence and not a byte string.
+impl<'de, A, S, D> Deserialize<'de> for ArrayBase<S, D>
+    where A: Deserialize<'de>,
+          D: Dimension + Deserialize<'de>,
+          S: DataOwned<Elem = A>
+{
+    fn deserialize<De>(deserializer: De) -> Result<ArrayBase<S, D>, De::Error>
+        where De: Deserializer<'de>
+    {
+        deserializer.deserialize_struct("Array", FIELDS, ArrayVisitor { marker: PhantomData })
     }
 }
@@ -210,9 +210,9 @@ unsafe impl<S, D> Send for ArrayBase<S, D>
 { }
 
 
-#[cfg(feature = "rustc-serialize")]
+#[cfg(any(feature = "rustc-serialize", feature = "serde"))]
 // Use version number so we can add a packed format later.
-static ARRAY_FORMAT_VERSION: u8 = 1u8;
+pub static ARRAY_FORMAT_VERSION: u8 = 1u8;
 
 /// **Requires crate feature `"rustc-serialize"`**
 #[cfg(feature = "rustc-serialize")]
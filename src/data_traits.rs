@@ -10,21 +10,33 @@
 
 use std::mem;
 use std::rc::Rc;
+use std::sync::Arc;
 
 use {
     ArrayBase,
+    CowRepr,
     Dimension,
+    RawViewRepr,
     ViewRepr,
 };
 
+/// Array representation trait.
+///
+/// The element type of any array, including raw views, whose elements
+/// may not be safe to access.
+///
+/// ***Internal trait, see `Data`.***
+pub unsafe trait RawData {
+    /// The array element type.
+    type Elem;
+}
+
 /// Array representation trait.
 ///
 /// ***Note:*** `Data` is not an extension interface at this point.
 /// Traits in Rust can serve many different roles. This trait is public because
 /// it is used as a bound on public methods.
-pub unsafe trait Data {
-    /// The array element type.
-    type Elem;
+pub unsafe trait Data : RawData {
     #[doc(hidden)]
     // This method is only used for debugging
     fn _data_slice(&self) -> &[Self::Elem];
@@ -48,6 +60,16 @@ pub unsafe trait DataMut : Data {
     fn is_unique(&mut self) -> bool {
         true
     }
+
+    #[doc(hidden)]
+    /// Return storage owning `elements`, or `None` if the representation
+    /// can not own its elements.
+    #[inline]
+    fn _new_owned(_elements: Vec<Self::Elem>) -> Option<Self>
+        where Self: Sized
+    {
+        None
+    }
 }
 
 /// Array representation trait.
@@ -55,14 +77,17 @@ pub unsafe trait DataMut : Data {
 /// An array representation that can be cloned.
 ///
 /// ***Internal trait, see `Data`.***
-pub unsafe trait DataClone : Data {
+pub unsafe trait DataClone : RawData {
     #[doc(hidden)]
     /// Unsafe because, `ptr` must point inside the current storage.
     unsafe fn clone_with_ptr(&self, ptr: *mut Self::Elem) -> (Self, *mut Self::Elem);
 }
 
-unsafe impl<A> Data for Rc<Vec<A>> {
+unsafe impl<A> RawData for Rc<Vec<A>> {
     type Elem = A;
+}
+
+unsafe impl<A> Data for Rc<Vec<A>> {
     fn _data_slice(&self) -> &[A] {
         self
     }
@@ -101,6 +126,10 @@ unsafe impl<A> DataMut for Rc<Vec<A>>
     fn is_unique(&mut self) -> bool {
         Rc::get_mut(self).is_some()
     }
+
+    fn _new_owned(elements: Vec<A>) -> Option<Self> {
+        Some(Rc::new(elements))
+    }
 }
 
 unsafe impl<A> DataClone for Rc<Vec<A>> {
@@ -110,14 +139,77 @@ unsafe impl<A> DataClone for Rc<Vec<A>> {
     }
 }
 
-unsafe impl<A> Data for Vec<A> {
+unsafe impl<A> RawData for Arc<Vec<A>> {
     type Elem = A;
+}
+
+unsafe impl<A> Data for Arc<Vec<A>> {
     fn _data_slice(&self) -> &[A] {
         self
     }
 }
 
-unsafe impl<A> DataMut for Vec<A> { }
+// NOTE: Copy on write
+unsafe impl<A> DataMut for Arc<Vec<A>>
+    where A: Clone
+{
+    fn ensure_unique<D>(self_: &mut ArrayBase<Self, D>)
+        where Self: Sized,
+              D: Dimension
+    {
+        if Arc::get_mut(&mut self_.data).is_some() {
+            return;
+        }
+        if self_.dim.size() <= self_.data.len() / 2 {
+            // Create a new vec if the current view is less than half of
+            // backing data.
+            unsafe {
+                *self_ = ArrayBase::from_vec_dim_unchecked(self_.dim.clone(),
+                                                           self_.iter()
+                                                            .cloned()
+                                                            .collect());
+            }
+            return;
+        }
+        let our_off = (self_.ptr as isize - self_.data.as_ptr() as isize) /
+                      mem::size_of::<A>() as isize;
+        let rvec = Arc::make_mut(&mut self_.data);
+        unsafe {
+            self_.ptr = rvec.as_mut_ptr().offset(our_off);
+        }
+    }
+
+    fn is_unique(&mut self) -> bool {
+        Arc::get_mut(self).is_some()
+    }
+
+    fn _new_owned(elements: Vec<A>) -> Option<Self> {
+        Some(Arc::new(elements))
+    }
+}
+
+unsafe impl<A> DataClone for Arc<Vec<A>> {
+    unsafe fn clone_with_ptr(&self, ptr: *mut Self::Elem) -> (Self, *mut Self::Elem) {
+        // pointer is preserved
+        (self.clone(), ptr)
+    }
+}
+
+unsafe impl<A> RawData for Vec<A> {
+    type Elem = A;
+}
+
+unsafe impl<A> Data for Vec<A> {
+    fn _data_slice(&self) -> &[A] {
+        self
+    }
+}
+
+unsafe impl<A> DataMut for Vec<A> {
+    fn _new_owned(elements: Vec<A>) -> Option<Self> {
+        Some(elements)
+    }
+}
 
 unsafe impl<A> DataClone for Vec<A>
     where A: Clone
@@ -131,8 +223,11 @@ unsafe impl<A> DataClone for Vec<A>
     }
 }
 
-unsafe impl<'a, A> Data for ViewRepr<&'a A> {
+unsafe impl<'a, A> RawData for ViewRepr<&'a A> {
     type Elem = A;
+}
+
+unsafe impl<'a, A> Data for ViewRepr<&'a A> {
     fn _data_slice(&self) -> &[A] {
         &[]
     }
@@ -144,8 +239,11 @@ unsafe impl<'a, A> DataClone for ViewRepr<&'a A> {
     }
 }
 
-unsafe impl<'a, A> Data for ViewRepr<&'a mut A> {
+unsafe impl<'a, A> RawData for ViewRepr<&'a mut A> {
     type Elem = A;
+}
+
+unsafe impl<'a, A> Data for ViewRepr<&'a mut A> {
     fn _data_slice(&self) -> &[A] {
         &[]
     }
@@ -153,6 +251,56 @@ unsafe impl<'a, A> Data for ViewRepr<&'a mut A> {
 
 unsafe impl<'a, A> DataMut for ViewRepr<&'a mut A> { }
 
+unsafe impl<'a, A> RawData for CowRepr<'a, A> {
+    type Elem = A;
+}
+
+unsafe impl<'a, A> Data for CowRepr<'a, A> {
+    fn _data_slice(&self) -> &[A] {
+        match *self {
+            CowRepr::View(ref view) => view._data_slice(),
+            CowRepr::Owned(ref data) => data._data_slice(),
+        }
+    }
+}
+
+unsafe impl<'a, A> DataClone for CowRepr<'a, A>
+    where A: Clone
+{
+    unsafe fn clone_with_ptr(&self, ptr: *mut Self::Elem) -> (Self, *mut Self::Elem) {
+        match *self {
+            CowRepr::View(ref view) => {
+                let (view, ptr) = view.clone_with_ptr(ptr);
+                (CowRepr::View(view), ptr)
+            }
+            CowRepr::Owned(ref data) => {
+                let (data, ptr) = data.clone_with_ptr(ptr);
+                (CowRepr::Owned(data), ptr)
+            }
+        }
+    }
+}
+
+unsafe impl<A> RawData for RawViewRepr<*const A> {
+    type Elem = A;
+}
+
+unsafe impl<A> DataClone for RawViewRepr<*const A> {
+    unsafe fn clone_with_ptr(&self, ptr: *mut Self::Elem) -> (Self, *mut Self::Elem) {
+        (*self, ptr)
+    }
+}
+
+unsafe impl<A> RawData for RawViewRepr<*mut A> {
+    type Elem = A;
+}
+
+unsafe impl<A> DataClone for RawViewRepr<*mut A> {
+    unsafe fn clone_with_ptr(&self, ptr: *mut Self::Elem) -> (Self, *mut Self::Elem) {
+        (*self, ptr)
+    }
+}
+
 /// Array representation trait.
 ///
 /// A representation that is a unique or shared owner of its data.
@@ -163,6 +311,8 @@ pub unsafe trait DataOwned : Data {
     fn new(elements: Vec<Self::Elem>) -> Self;
     #[doc(hidden)]
     fn into_shared(self) -> Rc<Vec<Self::Elem>>;
+    #[doc(hidden)]
+    fn into_shared_sync(self) -> Arc<Vec<Self::Elem>> where Self::Elem: Clone;
 }
 
 /// Array representation trait.
@@ -173,6 +323,7 @@ pub unsafe trait DataOwned : Data {
 pub unsafe trait DataShared : Clone + DataClone { }
 
 unsafe impl<A> DataShared for Rc<Vec<A>> {}
+unsafe impl<A> DataShared for Arc<Vec<A>> {}
 unsafe impl<'a, A> DataShared for ViewRepr<&'a A> {}
 
 unsafe impl<A> DataOwned for Vec<A> {
@@ -182,6 +333,9 @@ unsafe impl<A> DataOwned for Vec<A> {
     fn into_shared(self) -> Rc<Vec<A>> {
         Rc::new(self)
     }
+    fn into_shared_sync(self) -> Arc<Vec<A>> {
+        Arc::new(self)
+    }
 }
 
 unsafe impl<A> DataOwned for Rc<Vec<A>> {
@@ -191,5 +345,24 @@ unsafe impl<A> DataOwned for Rc<Vec<A>> {
     fn into_shared(self) -> Rc<Vec<A>> {
         self
     }
+    fn into_shared_sync(self) -> Arc<Vec<A>>
+        where A: Clone
+    {
+        Arc::new(Rc::try_unwrap(self).unwrap_or_else(|rc| (*rc).clone()))
+    }
+}
+
+unsafe impl<A> DataOwned for Arc<Vec<A>>
+    where A: Clone
+{
+    fn new(elements: Vec<A>) -> Self {
+        Arc::new(elements)
+    }
+    fn into_shared(self) -> Rc<Vec<A>> {
+        Rc::new(Arc::try_unwrap(self).unwrap_or_else(|arc| (*arc).clone()))
+    }
+    fn into_shared_sync(self) -> Arc<Vec<A>> {
+        self
+    }
 }
 
@@ -5,14 +5,14 @@
 // <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
-use std::cmp::Ordering;
+use std::cmp::{self, Ordering};
 use std::fmt::Debug;
 use std::slice;
 use itertools::free::enumerate;
 
-use super::{Si, Ix, Ixs};
+use super::{Si, Ix, Ixs, INFER};
 use super::zipsl;
-use error::{from_kind, ErrorKind, ShapeError};
+use error::{self, from_kind, ErrorKind, ShapeError};
 
 /// Calculate offset from `Ix` stride converting sign properly
 #[inline]
@@ -20,13 +20,23 @@ pub fn stride_offset(n: Ix, stride: Ix) -> isize {
     (n as isize) * ((stride as Ixs) as isize)
 }
 
+/// Return the absolute value of a stride (stored as `Ix`) without overflow.
+#[inline]
+fn abs_stride(stride: Ix) -> Ix {
+    if (stride as Ixs) < 0 {
+        stride.wrapping_neg()
+    } else {
+        stride
+    }
+}
+
 /// Check whether the given `dim` and `stride` lead to overlapping indices
 ///
 /// There is overlap if, when iterating through the dimensions in the order
 /// of maximum variation, the current stride is inferior to the sum of all
 /// preceding strides multiplied by their corresponding dimensions.
 ///
-/// The current implementation assumes strides to be positive
+/// The sign of the strides does not matter, only their absolute values.
 pub fn dim_stride_overlap<D: Dimension>(dim: &D, strides: &D) -> bool {
     let order = strides._fastest_varying_stride_order();
 
@@ -35,12 +45,12 @@ pub fn dim_stride_overlap<D: Dimension>(dim: &D, strides: &D) -> bool {
     let mut prev_offset = 1;
     for &index in order.slice() {
         let d = dim[index];
-        let s = strides[index];
+        let s = abs_stride(strides[index]);
         // any stride is ok if dimension is 1
-        if d != 1 && (s as isize) < prev_offset {
+        if d != 1 && s < prev_offset {
             return true;
         }
-        prev_offset = stride_offset(d, s);
+        prev_offset = d.saturating_mul(s);
     }
     false
 }
@@ -48,74 +58,159 @@ pub fn dim_stride_overlap<D: Dimension>(dim: &D, strides: &D) -> bool {
 /// Check whether the given dimension and strides are memory safe
 /// to index the provided slice.
 ///
-/// To be safe, no stride may be negative, and the offset corresponding
-/// to the last element of each dimension should be smaller than the length
-/// of the slice. Also, the strides should not allow a same element to be
+/// To be safe, the distance from the element with the lowest address to
+/// the element with the highest address must be smaller than the length of
+/// the slice. Also, the strides should not allow a same element to be
 /// referenced by two different index.
+///
+/// Strides may be negative; see `offset_from_ptr_to_memory` for where
+/// the first element of such an array is placed in the slice.
 pub fn can_index_slice<A, D: Dimension>(data: &[A], dim: &D, strides: &D)
     -> Result<(), ShapeError>
+{
+    can_index_slice_len(data.len(), dim, strides)
+}
+
+/// Like `can_index_slice`, for a slice of length `data_len`.
+pub fn can_index_slice_len<D: Dimension>(data_len: usize, dim: &D, strides: &D)
+    -> Result<(), ShapeError>
 {
     // check lengths of axes.
     let len = match dim.size_checked() {
         Some(l) => l,
         None => return Err(from_kind(ErrorKind::OutOfBounds)),
     };
-    // check if strides are strictly positive (zero ok for len 0)
-    for &s in strides.slice() {
-        let s = s as Ixs;
-        if s < 1 && (len != 0 || s < 0) {
-            return Err(from_kind(ErrorKind::Unsupported));
-        }
-    }
     if len == 0 {
         return Ok(());
     }
-    // check that the maximum index is in bounds
-    let mut last_index = dim.clone();
-    for mut index in last_index.slice_mut().iter_mut() {
-        *index -= 1;
+    // check that strides are nonzero
+    if strides.slice().iter().any(|&s| s == 0) {
+        return Err(from_kind(ErrorKind::Unsupported));
     }
-    if let Some(offset) = stride_offset_checked_arithmetic(dim,
-                                                           strides,
-                                                           &last_index)
-    {
-        // offset is guaranteed to be positive so no issue converting
-        // to usize here
-        if (offset as usize) >= data.len() {
-            return Err(from_kind(ErrorKind::OutOfBounds));
+    // check that the maximum offset from the lowest address is in bounds
+    let mut max_offset: Ix = 0;
+    for (&d, &s) in zipsl(dim.slice(), strides.slice()) {
+        let axis_offset = (d - 1).checked_mul(abs_stride(s));
+        match axis_offset.and_then(|x| x.checked_add(max_offset)) {
+            Some(offset) if offset <= Ixs::max_value() as Ix => max_offset = offset,
+            _ => return Err(from_kind(ErrorKind::OutOfBounds)),
         }
-        if dim_stride_overlap(dim, strides) {
-            return Err(from_kind(ErrorKind::Unsupported));
-        }
-    } else {
+    }
+    if max_offset >= data_len {
         return Err(from_kind(ErrorKind::OutOfBounds));
     }
+    if dim_stride_overlap(dim, strides) {
+        return Err(from_kind(ErrorKind::Unsupported));
+    }
     Ok(())
 }
 
-/// Return stride offset for this dimension and index.
+/// Replace the axis of `shape` with length `INFER`, if any, with
+/// the length that makes the number of elements `size`.
 ///
-/// Return None if the indices are out of bounds, or the calculation would wrap
-/// around.
-fn stride_offset_checked_arithmetic<D>(dim: &D, strides: &D, index: &D)
-    -> Option<isize>
-    where D: Dimension
+/// **Errors** if more than one axis is inferred, or if the length can not
+/// be inferred.
+pub fn infer_shape<D: Dimension>(mut shape: D, size: usize) -> Result<D, ShapeError> {
+    let mut inferred = None;
+    let mut known: usize = 1;
+    for (i, &d) in shape.slice().iter().enumerate() {
+        if d == INFER {
+            if inferred.is_some() {
+                return Err(from_kind(ErrorKind::IncompatibleShape));
+            }
+            inferred = Some(i);
+        } else {
+            known = match known.checked_mul(d) {
+                Some(n) => n,
+                None => return Err(from_kind(ErrorKind::RangeLimited)),
+            };
+        }
+    }
+    if let Some(i) = inferred {
+        if known == 0 || size % known != 0 {
+            return Err(from_kind(ErrorKind::IncompatibleShape));
+        }
+        shape.slice_mut()[i] = size / known;
+    }
+    Ok(shape)
+}
+
+/// Compute the strides of an array with shape `dim` and strides `strides`
+/// reshaped to `to` in C order (or F order if `f_order` is true), so that
+/// it has the same elements without copying.
+///
+/// Return `None` if the elements can not be arranged with the new shape
+/// by strides alone. `dim` and `to` must have the same number of elements.
+pub fn reshape_strides<D, E>(dim: &D, strides: &D, to: &E, f_order: bool) -> Option<E>
+    where D: Dimension,
+          E: Dimension,
 {
-    let mut offset = 0;
-    for ((&d, &i), &s) in zipsl(zipsl(dim.slice(), index.slice()), strides.slice()) {
-        if i >= d {
-            return None;
+    let mut new_strides = to.clone();
+    if dim.size() == 0 {
+        return Some(if f_order { to.fortran_strides() } else { to.default_strides() });
+    }
+    // work in C order, with the axes reversed for F order;
+    // axes of length one do not constrain the layout
+    let mut old: Vec<(Ix, Ixs)> = zipsl(dim.slice(), strides.slice())
+        .filter(|&(&d, _)| d != 1)
+        .map(|(&d, &s)| (d, s as Ixs))
+        .collect();
+    let mut new: Vec<Ix> = to.slice().to_vec();
+    if f_order {
+        old.reverse();
+        new.reverse();
+    }
+    let mut out: Vec<Ixs> = vec![1; new.len()];
+
+    let (mut oi, mut ni) = (0, 0);
+    while oi < old.len() && ni < new.len() {
+        // find the smallest groups of axes old[oi..oj] and new[ni..nj]
+        // with the same number of elements
+        let (mut oj, mut nj) = (oi + 1, ni + 1);
+        let (mut op, mut np) = (old[oi].0, new[ni]);
+        while op != np {
+            if np < op {
+                np *= new[nj];
+                nj += 1;
+            } else {
+                op *= old[oj].0;
+                oj += 1;
+            }
         }
+        // the old group must be contiguous with itself
+        for k in oi..oj - 1 {
+            if old[k].1 != old[k + 1].1 * old[k + 1].0 as Ixs {
+                return None;
+            }
+        }
+        out[nj - 1] = old[oj - 1].1;
+        for k in (ni..nj - 1).rev() {
+            out[k] = out[k + 1] * new[k + 1] as Ixs;
+        }
+        oi = oj;
+        ni = nj;
+    }
+    if f_order {
+        out.reverse();
+    }
+    for (s, &o) in new_strides.slice_mut().iter_mut().zip(&out) {
+        *s = o as Ix;
+    }
+    Some(new_strides)
+}
 
-        if let Some(offset_) = (i as isize)
-                                   .checked_mul((s as Ixs) as isize)
-                                   .and_then(|x| x.checked_add(offset)) {
-            offset = offset_;
-        } else {
-            return None;
+/// Return the offset from the first element of an array (the element at
+/// index zero) to the element with the lowest memory address.
+///
+/// The offset is zero if no stride is negative, and otherwise negative.
+pub fn offset_from_ptr_to_memory<D: Dimension>(dim: &D, strides: &D) -> isize {
+    let mut offset = 0;
+    for (&d, &s) in zipsl(dim.slice(), strides.slice()) {
+        if (s as Ixs) < 0 && d > 1 {
+            offset += stride_offset(d - 1, s);
         }
     }
-    Some(offset)
+    offset
 }
 
 /// Array shape and index trait.
@@ -142,6 +237,15 @@ pub unsafe trait Dimension : Clone + Eq + Debug + Send + Sync {
     type SliceArg: ?Sized + AsRef<[Si]>;
     #[doc(hidden)]
     fn ndim(&self) -> usize;
+
+    #[doc(hidden)]
+    /// Create a dimension (or index) of `ndim` axes, all zero.
+    ///
+    /// **Panics** if `ndim` does not match the number of axes of `Self`.
+    fn zero_index_with_ndim(ndim: usize) -> Self;
+    #[doc(hidden)]
+    /// Return the number of axes of `Self`, or `None` if it is dynamic.
+    fn fixed_ndim() -> Option<usize>;
     #[doc(hidden)]
     fn slice(&self) -> &[Ix] {
         unsafe {
@@ -308,7 +412,7 @@ pub unsafe trait Dimension : Clone + Eq + Debug + Send + Sync {
             // Increase start pointer by
             // old stride * (old dim - 1)
             // to put the pointer completely in the other end
-            if s1 < 0 {
+            if s1 < 0 && m > 0 {
                 offset += stride_offset(m - 1, *sr);
             }
 
@@ -328,8 +432,8 @@ pub unsafe trait Dimension : Clone + Eq + Debug + Send + Sync {
     /// Return the axis ordering corresponding to the fastest variation
     /// (in ascending order).
     ///
-    /// Assumes that no stride value appears twice. This cannot yield the correct
-    /// result the strides are not positive.
+    /// Assumes that no stride value appears twice. Strides are compared by
+    /// their absolute value, so negative strides are ordered by magnitude.
     #[doc(hidden)]
     fn _fastest_varying_stride_order(&self) -> Self {
         let mut indices = self.clone();
@@ -337,7 +441,7 @@ pub unsafe trait Dimension : Clone + Eq + Debug + Send + Sync {
             *elt = i;
         }
         let strides = self.slice();
-        indices.slice_mut().sort_by_key(|&i| strides[i]);
+        indices.slice_mut().sort_by_key(|&i| abs_stride(strides[i]));
         indices
     }
 }
@@ -418,6 +522,9 @@ unsafe impl Dimension for () {
     // empty product is 1 -> size is 1
     #[inline]
     fn ndim(&self) -> usize { 0 }
+    #[inline]
+    fn zero_index_with_ndim(ndim: usize) -> Self { assert_eq!(ndim, 0); }
+    fn fixed_ndim() -> Option<usize> { Some(0) }
     fn slice(&self) -> &[Ix] { &[] }
     fn slice_mut(&mut self) -> &mut [Ix] { &mut [] }
 }
@@ -427,6 +534,9 @@ unsafe impl Dimension for Ix {
     #[inline]
     fn ndim(&self) -> usize { 1 }
     #[inline]
+    fn zero_index_with_ndim(ndim: usize) -> Self { assert_eq!(ndim, 1); 0 }
+    fn fixed_ndim() -> Option<usize> { Some(1) }
+    #[inline]
     fn size(&self) -> usize { *self as usize }
     #[inline]
     fn size_checked(&self) -> Option<usize> { Some(*self as usize) }
@@ -473,6 +583,9 @@ unsafe impl Dimension for (Ix, Ix) {
     type SliceArg = [Si; 2];
     #[inline]
     fn ndim(&self) -> usize { 2 }
+    #[inline]
+    fn zero_index_with_ndim(ndim: usize) -> Self { assert_eq!(ndim, 2); (0, 0) }
+    fn fixed_ndim() -> Option<usize> { Some(2) }
 
     #[inline]
     fn size(&self) -> usize { let (m, n) = *self; m as usize * n as usize }
@@ -492,7 +605,7 @@ unsafe impl Dimension for (Ix, Ix) {
 
     #[inline]
     fn _fastest_varying_stride_order(&self) -> Self {
-        if self.0 as Ixs <= self.1 as Ixs { (0, 1) } else { (1, 0) }
+        if abs_stride(self.0) <= abs_stride(self.1) { (0, 1) } else { (1, 0) }
     }
 
     #[inline]
@@ -547,6 +660,9 @@ unsafe impl Dimension for (Ix, Ix, Ix) {
     #[inline]
     fn ndim(&self) -> usize { 3 }
     #[inline]
+    fn zero_index_with_ndim(ndim: usize) -> Self { assert_eq!(ndim, 3); (0, 0, 0) }
+    fn fixed_ndim() -> Option<usize> { Some(3) }
+    #[inline]
     fn size(&self) -> usize { let (m, n, o) = *self; m as usize * n as usize * o as usize }
     #[inline]
     fn next_for(&self, index: (Ix, Ix, Ix)) -> Option<(Ix, Ix, Ix)> {
@@ -577,7 +693,7 @@ unsafe impl Dimension for (Ix, Ix, Ix) {
 
     #[inline]
     fn _fastest_varying_stride_order(&self) -> Self {
-        let mut stride = *self;
+        let mut stride = (abs_stride(self.0), abs_stride(self.1), abs_stride(self.2));
         let mut order = (0, 1, 2);
         macro_rules! swap {
             ($stride:expr, $order:expr, $x:expr, $y:expr) => {
@@ -605,6 +721,12 @@ macro_rules! large_dim {
             type SliceArg = [Si; $n];
             #[inline]
             fn ndim(&self) -> usize { $n }
+            #[inline]
+            fn zero_index_with_ndim(ndim: usize) -> Self {
+                assert_eq!(ndim, $n);
+                ($(0 as $ix),+)
+            }
+            fn fixed_ndim() -> Option<usize> { Some($n) }
         }
     )
 }
@@ -625,6 +747,8 @@ unsafe impl Dimension for Vec<Ix>
 {
     type SliceArg = [Si];
     fn ndim(&self) -> usize { self.len() }
+    fn zero_index_with_ndim(ndim: usize) -> Self { vec![0; ndim] }
+    fn fixed_ndim() -> Option<usize> { None }
     fn slice(&self) -> &[Ix] { self }
     fn slice_mut(&mut self) -> &mut [Ix] { self }
 }
@@ -685,6 +809,173 @@ impl RemoveAxis for Vec<Ix> {
     }
 }
 
+/// Array shape with a next larger dimension.
+///
+/// `InsertAxis` defines a smaller-than relation for array shapes:
+/// inserting one axis into *Self* gives larger dimension *Larger*.
+pub trait InsertAxis : Dimension {
+    type Larger: RemoveAxis;
+    /// Insert an axis of length one before `axis`, or last if `axis` is
+    /// equal to the number of axes.
+    ///
+    /// **Panics** if `axis` is strictly greater than the number of axes.
+    fn insert_axis(&self, axis: Axis) -> Self::Larger;
+}
+
+#[inline]
+fn insert_axis_into<D, L>(dim: &D, axis: Axis) -> L
+    where D: Dimension,
+          L: Dimension,
+{
+    let ndim = dim.ndim();
+    assert!(axis.axis() <= ndim,
+            "ndarray: can not insert axis {} into an array with {} axes", axis.axis(), ndim);
+    let mut res = L::zero_index_with_ndim(ndim + 1);
+    {
+        let (front, rest) = res.slice_mut().split_at_mut(axis.axis());
+        front.copy_from_slice(&dim.slice()[..axis.axis()]);
+        rest[0] = 1;
+        rest[1..].copy_from_slice(&dim.slice()[axis.axis()..]);
+    }
+    res
+}
+
+macro_rules! impl_grow(
+    ($from:ty => $to:ty) => (
+impl InsertAxis for $from
+{
+    type Larger = $to;
+    #[inline]
+    fn insert_axis(&self, axis: Axis) -> $to {
+        insert_axis_into(self, axis)
+    }
+}
+    )
+);
+
+impl_grow!(() => Ix);
+impl_grow!(Ix => (Ix, Ix));
+impl_grow!((Ix, Ix) => (Ix, Ix, Ix));
+impl_grow!((Ix, Ix, Ix) => (Ix, Ix, Ix, Ix));
+impl_grow!((Ix, Ix, Ix, Ix) => (Ix, Ix, Ix, Ix, Ix));
+impl_grow!((Ix, Ix, Ix, Ix, Ix) => (Ix, Ix, Ix, Ix, Ix, Ix));
+impl_grow!((Ix, Ix, Ix, Ix, Ix, Ix) => (Ix, Ix, Ix, Ix, Ix, Ix, Ix));
+impl_grow!((Ix, Ix, Ix, Ix, Ix, Ix, Ix) => (Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix));
+impl_grow!((Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix) => (Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix));
+impl_grow!((Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix) =>
+           (Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix));
+impl_grow!((Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix) =>
+           (Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix));
+impl_grow!((Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix) =>
+           (Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix));
+impl_grow!((Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix, Ix) => Vec<Ix>);
+impl_grow!(Vec<Ix> => Vec<Ix>);
+
+/// Array shape that can hold the result of broadcasting two shapes together.
+///
+/// `DimMax<E>` for `D` names the dimension type `Output` of an arithmetic
+/// operation between arrays of dimension `D` and `E`; it is the one with
+/// the most axes, or `Vec<Ix>` if either operand is dynamic.
+///
+/// ```
+/// use ndarray::{arr1, arr2, OwnedArray, Ix};
+///
+/// let a = arr2(&[[1., 2.],
+///                [3., 4.]]);
+/// let b = arr1(&[10., 20.]);
+/// let c: OwnedArray<f64, (Ix, Ix)> = &b + &a;
+/// assert_eq!(c, arr2(&[[11., 22.],
+///                      [13., 24.]]));
+/// ```
+pub trait DimMax<Other: Dimension> : Dimension {
+    /// The resulting dimension type after broadcasting.
+    type Output: Dimension;
+}
+
+impl<D: Dimension> DimMax<D> for D {
+    type Output = D;
+}
+
+macro_rules! impl_dim_max {
+    ($smaller:ty, $larger:ty) => {
+        impl DimMax<$larger> for $smaller {
+            type Output = $larger;
+        }
+
+        impl DimMax<$smaller> for $larger {
+            type Output = $larger;
+        }
+    }
+}
+
+impl_dim_max!((), Ix);
+impl_dim_max!((), (Ix, Ix));
+impl_dim_max!((), (Ix, Ix, Ix));
+impl_dim_max!((), (Ix, Ix, Ix, Ix));
+impl_dim_max!((), (Ix, Ix, Ix, Ix, Ix));
+impl_dim_max!((), (Ix, Ix, Ix, Ix, Ix, Ix));
+impl_dim_max!(Ix, (Ix, Ix));
+impl_dim_max!(Ix, (Ix, Ix, Ix));
+impl_dim_max!(Ix, (Ix, Ix, Ix, Ix));
+impl_dim_max!(Ix, (Ix, Ix, Ix, Ix, Ix));
+impl_dim_max!(Ix, (Ix, Ix, Ix, Ix, Ix, Ix));
+impl_dim_max!((Ix, Ix), (Ix, Ix, Ix));
+impl_dim_max!((Ix, Ix), (Ix, Ix, Ix, Ix));
+impl_dim_max!((Ix, Ix), (Ix, Ix, Ix, Ix, Ix));
+impl_dim_max!((Ix, Ix), (Ix, Ix, Ix, Ix, Ix, Ix));
+impl_dim_max!((Ix, Ix, Ix), (Ix, Ix, Ix, Ix));
+impl_dim_max!((Ix, Ix, Ix), (Ix, Ix, Ix, Ix, Ix));
+impl_dim_max!((Ix, Ix, Ix), (Ix, Ix, Ix, Ix, Ix, Ix));
+impl_dim_max!((Ix, Ix, Ix, Ix), (Ix, Ix, Ix, Ix, Ix));
+impl_dim_max!((Ix, Ix, Ix, Ix), (Ix, Ix, Ix, Ix, Ix, Ix));
+impl_dim_max!((Ix, Ix, Ix, Ix, Ix), (Ix, Ix, Ix, Ix, Ix, Ix));
+impl_dim_max!((), Vec<Ix>);
+impl_dim_max!(Ix, Vec<Ix>);
+impl_dim_max!((Ix, Ix), Vec<Ix>);
+impl_dim_max!((Ix, Ix, Ix), Vec<Ix>);
+impl_dim_max!((Ix, Ix, Ix, Ix), Vec<Ix>);
+impl_dim_max!((Ix, Ix, Ix, Ix, Ix), Vec<Ix>);
+impl_dim_max!((Ix, Ix, Ix, Ix, Ix, Ix), Vec<Ix>);
+
+/// Compute the shape that arrays of shape `shape1` and `shape2` can both
+/// be broadcast to.
+///
+/// Axes are compared beginning with the *last* axis of each shape; two axes
+/// are compatible if they are equal, or one of them is 1. Missing axes of
+/// the shorter shape count as 1.
+///
+/// **Errors** if the shapes can not be broadcast together.<br>
+/// **Panics** if `Output` can not hold the number of axes of the result.
+pub fn co_broadcast<D1, D2, Output>(shape1: &D1, shape2: &D2)
+    -> Result<Output, ShapeError>
+    where D1: Dimension,
+          D2: Dimension,
+          Output: Dimension,
+{
+    let (k1, k2) = (shape1.ndim(), shape2.ndim());
+    let (long, short) = if k1 >= k2 {
+        (shape1.slice(), shape2.slice())
+    } else {
+        (shape2.slice(), shape1.slice())
+    };
+    let mut out = Output::zero_index_with_ndim(cmp::max(k1, k2));
+    {
+        let out_s = out.slice_mut();
+        out_s.copy_from_slice(long);
+        for (o, &s) in out_s.iter_mut().rev().zip(short.iter().rev()) {
+            if *o == s || s == 1 {
+                continue;
+            } else if *o == 1 {
+                *o = s;
+            } else {
+                return Err(error::in_operation(error::incompatible_shapes(shape1, shape2),
+                                               "broadcast"));
+            }
+        }
+    }
+    Ok(out)
+}
+
 /// Tuple or fixed size arrays that can be used to index an array.
 ///
 /// ```
@@ -786,6 +1077,9 @@ mod test {
         let strides = (2, 4, 12);
         assert_eq!(super::can_index_slice(&v, &dim, &strides),
                    Err(from_kind(ErrorKind::OutOfBounds)));
+
+        let strides = (-1isize as usize, 2, -6isize as usize);
+        assert!(super::can_index_slice(&v, &dim, &strides).is_ok());
     }
 
     #[test]
@@ -8,14 +8,45 @@
 use std::fmt;
 use std::error::Error;
 use super::{
+    Axis,
     Dimension,
+    Ix,
 };
 
 /// An error related to array shape or layout.
+///
+/// Besides its [`ErrorKind`](enum.ErrorKind.html), the error records what
+/// is known about the failure: the operation, the source and target shapes
+/// and the axis involved. They are shown when the error is formatted, and
+/// an error caused by another one returns it from `Error::source`.
+///
+/// ```
+/// use ndarray::{arr1, ErrorKind};
+///
+/// let err = arr1(&[1., 2., 3.]).into_shape((2, 2)).unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::IncompatibleShape);
+/// assert_eq!(err.operation(), Some("into_shape"));
+/// assert_eq!(err.source_shape(), Some(&[3][..]));
+/// assert_eq!(err.target_shape(), Some(&[2, 2][..]));
+/// assert_eq!(err.to_string(),
+///            "ShapeError/IncompatibleShape: incompatible shapes in into_shape \
+///             (from shape [3] to [2, 2])");
+/// ```
 #[derive(Clone)]
 pub struct ShapeError {
     // we want to be able to change this representation later
     repr: ErrorKind,
+    info: Option<Box<ErrorInfo>>,
+}
+
+#[derive(Clone, Default)]
+struct ErrorInfo {
+    operation: Option<&'static str>,
+    from: Option<Vec<Ix>>,
+    to: Option<Vec<Ix>>,
+    axis: Option<Axis>,
+    detail: Option<String>,
+    source: Option<ShapeError>,
 }
 
 impl ShapeError {
@@ -29,6 +60,35 @@ impl ShapeError {
     pub fn from_kind(error: ErrorKind) -> Self {
         from_kind(error)
     }
+
+    /// Return the name of the operation that failed, if known.
+    pub fn operation(&self) -> Option<&str> {
+        self.info.as_ref().and_then(|info| info.operation)
+    }
+
+    /// Return the shape of the input of the operation, if known.
+    pub fn source_shape(&self) -> Option<&[Ix]> {
+        self.info.as_ref().and_then(|info| info.from.as_ref()).map(|s| &s[..])
+    }
+
+    /// Return the shape that the operation required, if known.
+    pub fn target_shape(&self) -> Option<&[Ix]> {
+        self.info.as_ref().and_then(|info| info.to.as_ref()).map(|s| &s[..])
+    }
+
+    /// Return the axis along which the shapes did not match, if known.
+    pub fn axis(&self) -> Option<Axis> {
+        self.info.as_ref().and_then(|info| info.axis)
+    }
+
+    /// Return a description of which argument was at fault, if known.
+    pub fn detail(&self) -> Option<&str> {
+        self.info.as_ref().and_then(|info| info.detail.as_ref()).map(|s| &s[..])
+    }
+
+    fn info_mut(&mut self) -> &mut ErrorInfo {
+        self.info.get_or_insert_with(Default::default)
+    }
 }
 
 /// Error code for an error related to array shape or layout.
@@ -54,7 +114,8 @@ pub enum ErrorKind {
 #[inline(always)]
 pub fn from_kind(k: ErrorKind) -> ShapeError {
     ShapeError {
-        repr: k
+        repr: k,
+        info: None,
     }
 }
 
@@ -83,23 +144,100 @@ impl Error for ShapeError {
             ErrorKind::__Incomplete => "this error variant is not in use",
         }
     }
+
+    fn source(&self) -> Option<&(Error + 'static)> {
+        match self.info {
+            Some(ref info) => info.source.as_ref().map(|e| e as &Error),
+            None => None,
+        }
+    }
 }
 
 impl fmt::Display for ShapeError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "ShapeError/{:?}: {}", self.kind(), self.description())
+        try!(write!(f, "ShapeError/{:?}: {}", self.kind(), self.description()));
+        if let Some(op) = self.operation() {
+            try!(write!(f, " in {}", op));
+        }
+        let mut details = Vec::new();
+        if let Some(detail) = self.detail() {
+            details.push(detail.to_string());
+        }
+        match (self.source_shape(), self.target_shape()) {
+            (Some(from), Some(to)) => details.push(format!("from shape {:?} to {:?}", from, to)),
+            (Some(from), None) => details.push(format!("shape {:?}", from)),
+            (None, Some(to)) => details.push(format!("to shape {:?}", to)),
+            (None, None) => {}
+        }
+        if let Some(axis) = self.axis() {
+            details.push(format!("along axis {}", axis.axis()));
+        }
+        if !details.is_empty() {
+            try!(write!(f, " ({})", details.join(", ")));
+        }
+        Ok(())
     }
 }
 
 impl fmt::Debug for ShapeError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "ShapeError/{:?}: {}", self.kind(), self.description())
+        try!(fmt::Display::fmt(self, f));
+        if let Some(source) = self.source() {
+            try!(write!(f, "; caused by {}", source));
+        }
+        Ok(())
     }
 }
 
-pub fn incompatible_shapes<D, E>(_a: &D, _b: &E) -> ShapeError
+/// Error for an operation from shape `a` to the incompatible shape `b`.
+pub fn incompatible_shapes<D, E>(a: &D, b: &E) -> ShapeError
+    where D: Dimension,
+          E: Dimension
+{
+    shapes(from_kind(ErrorKind::IncompatibleShape), a, b)
+}
+
+/// Error for arrays whose shapes `a` and `b` differ along `axis`.
+pub fn incompatible_shapes_along<D, E>(axis: Axis, a: &D, b: &E) -> ShapeError
+    where D: Dimension,
+          E: Dimension
+{
+    along(incompatible_shapes(a, b), axis)
+}
+
+/// Record `axis` as the axis involved in `err`.
+pub fn along(mut err: ShapeError, axis: Axis) -> ShapeError {
+    err.info_mut().axis = Some(axis);
+    err
+}
+
+/// Record the source shape `a` and target shape `b` in `err`.
+pub fn shapes<D, E>(mut err: ShapeError, a: &D, b: &E) -> ShapeError
     where D: Dimension,
           E: Dimension
 {
-    from_kind(ErrorKind::IncompatibleShape)
+    {
+        let info = err.info_mut();
+        info.from = Some(a.slice().to_vec());
+        info.to = Some(b.slice().to_vec());
+    }
+    err
+}
+
+/// Record a description `detail` of which argument was at fault in `err`.
+pub fn with_detail(mut err: ShapeError, detail: String) -> ShapeError {
+    err.info_mut().detail = Some(detail);
+    err
+}
+
+/// Record the name of the operation that failed in `err`.
+pub fn in_operation(mut err: ShapeError, operation: &'static str) -> ShapeError {
+    err.info_mut().operation = Some(operation);
+    err
+}
+
+/// Record `source` as the error that caused `err`.
+pub fn caused_by(mut err: ShapeError, source: ShapeError) -> ShapeError {
+    err.info_mut().source = Some(source);
+    err
 }
@@ -8,12 +8,15 @@
 
 //! Constructor methods for ndarray
 //!
+use std::mem::{ManuallyDrop, MaybeUninit};
+
 use libnum;
 
 use imp_prelude::*;
 use dimension;
 use linspace;
 use error::{self, ShapeError, ErrorKind};
+use {Indexes, ShapeBuilder, StrideShape};
 
 /// Constructor methods for one-dimensional arrays.
 ///
@@ -109,18 +112,27 @@ macro_rules! size_checked_unwrap {
 }
 
 /// Constructor methods for n-dimensional arrays.
+///
+/// The shape argument `shape` can be a dimension like `(3, 4)`, which
+/// uses C (row major) memory order, or it can select the memory order with
+/// the [`ShapeBuilder`](trait.ShapeBuilder.html) methods, like
+/// `(3, 4).f()` for F (column major) order. The constructors that use
+/// existing data also accept custom strides, like
+/// `(3, 4).strides((1, 3))`.
 impl<S, A, D> ArrayBase<S, D>
     where S: DataOwned<Elem=A>,
           D: Dimension,
 {
-    /// Create an array with copies of `elem`, dimension `dim`.
+    /// Create an array with copies of `elem`, shape `shape`.
     ///
-    /// **Panics** if the number of elements in `dim` would overflow usize.
+    /// **Panics** if the number of elements in `shape` would overflow usize.
     ///
     /// ```
     /// use ndarray::OwnedArray;
     /// use ndarray::arr3;
     ///
+    /// use ndarray::ShapeBuilder;
+    ///
     /// let a = OwnedArray::from_elem((2, 2, 2), 1.);
     ///
     /// assert!(
@@ -130,16 +142,21 @@ impl<S, A, D> ArrayBase<S, D>
     ///                  [1., 1.]]])
     /// );
     /// assert!(a.strides() == &[4, 2, 1]);
+    ///
+    /// let b = OwnedArray::from_elem((2, 2, 2).f(), 1.);
+    /// assert!(b.strides() == &[1, 2, 4]);
     /// ```
-    pub fn from_elem(dim: D, elem: A) -> ArrayBase<S, D>
-        where A: Clone
+    pub fn from_elem<Sh>(shape: Sh, elem: A) -> ArrayBase<S, D>
+        where A: Clone,
+              Sh: ShapeBuilder<Dim=D>,
     {
         // Note: We don't need to check the case of a size between
         // isize::MAX -> usize::MAX; in this case, the vec constructor itself
         // panics.
-        let size = size_checked_unwrap!(dim);
+        let shape = shape.into_shape();
+        let size = size_checked_unwrap!(shape.dim);
         let v = vec![elem; size];
-        unsafe { Self::from_vec_dim_unchecked(dim, v) }
+        unsafe { Self::from_vec_dim_unchecked(shape, v) }
     }
 
     /// Create an array with copies of `elem`, dimension `dim` and fortran
@@ -156,18 +173,17 @@ impl<S, A, D> ArrayBase<S, D>
     pub fn from_elem_f(dim: D, elem: A) -> ArrayBase<S, D>
         where A: Clone
     {
-        let size = size_checked_unwrap!(dim);
-        let v = vec![elem; size];
-        unsafe { Self::from_vec_dim_unchecked_f(dim, v) }
+        Self::from_elem(dim.f(), elem)
     }
 
-    /// Create an array with zeros, dimension `dim`.
+    /// Create an array with zeros, shape `shape`.
     ///
-    /// **Panics** if the number of elements in `dim` would overflow usize.
-    pub fn zeros(dim: D) -> ArrayBase<S, D>
-        where A: Clone + libnum::Zero
+    /// **Panics** if the number of elements in `shape` would overflow usize.
+    pub fn zeros<Sh>(shape: Sh) -> ArrayBase<S, D>
+        where A: Clone + libnum::Zero,
+              Sh: ShapeBuilder<Dim=D>,
     {
-        Self::from_elem(dim, libnum::zero())
+        Self::from_elem(shape, libnum::zero())
     }
 
     /// Create an array with zeros, dimension `dim` and fortran memory order.
@@ -176,27 +192,90 @@ impl<S, A, D> ArrayBase<S, D>
     pub fn zeros_f(dim: D) -> ArrayBase<S, D>
         where A: Clone + libnum::Zero
     {
-        Self::from_elem_f(dim, libnum::zero())
+        Self::from_elem(dim.f(), libnum::zero())
     }
 
-    /// Create an array with default values, dimension `dim`.
+    /// Create an array with default values, shape `shape`.
     ///
-    /// **Panics** if the number of elements in `dim` would overflow usize.
-    pub fn default(dim: D) -> ArrayBase<S, D>
-        where A: Default
+    /// **Panics** if the number of elements in `shape` would overflow usize.
+    pub fn default<Sh>(shape: Sh) -> ArrayBase<S, D>
+        where A: Default,
+              Sh: ShapeBuilder<Dim=D>,
     {
-        let v = (0..dim.size()).map(|_| A::default()).collect();
-        unsafe { Self::from_vec_dim_unchecked(dim, v) }
+        let shape = shape.into_shape();
+        let size = size_checked_unwrap!(shape.dim);
+        let v = (0..size).map(|_| A::default()).collect();
+        unsafe { Self::from_vec_dim_unchecked(shape, v) }
     }
 
-    /// Create an array from a vector (no copying needed).
+    /// Create an array with values created by the function `f`, shape
+    /// `shape`.
     ///
-    /// **Errors** if `dim` does not correspond to the number of elements in `v`.
-    pub fn from_vec_dim(dim: D, v: Vec<A>) -> Result<ArrayBase<S, D>, ShapeError> {
-        if dim.size_checked() != Some(v.len()) {
-            return Err(error::incompatible_shapes(&v.len(), &dim));
+    /// `f` is called with the index of each element, in the memory order
+    /// of the array.
+    ///
+    /// **Panics** if the number of elements in `shape` would overflow usize.
+    ///
+    /// ```
+    /// use ndarray::OwnedArray;
+    /// use ndarray::ShapeBuilder;
+    /// use ndarray::arr2;
+    ///
+    /// let a = OwnedArray::from_shape_fn((2, 3), |(i, j)| 10 * i + j);
+    /// assert_eq!(a, arr2(&[[0, 1, 2], [10, 11, 12]]));
+    ///
+    /// let b = OwnedArray::from_shape_fn((2, 3).f(), |(i, j)| 10 * i + j);
+    /// assert_eq!(a, b);
+    /// assert_eq!(b.as_slice_memory_order(), Some(&[0, 10, 1, 11, 2, 12][..]));
+    /// ```
+    pub fn from_shape_fn<Sh, F>(shape: Sh, f: F) -> ArrayBase<S, D>
+        where Sh: ShapeBuilder<Dim=D>,
+              F: FnMut(D) -> A,
+    {
+        let shape = shape.into_shape();
+        size_checked_unwrap!(shape.dim);
+        let v = if shape.is_c {
+            Indexes::new(shape.dim.clone()).map(f).collect()
+        } else {
+            // visit the indices in F order: C order of the reversed axes
+            let mut dim = shape.dim.clone();
+            dim.slice_mut().reverse();
+            let mut f = f;
+            Indexes::new(dim).map(|mut index| {
+                index.slice_mut().reverse();
+                f(index)
+            }).collect()
+        };
+        unsafe { Self::from_vec_dim_unchecked(shape, v) }
+    }
+
+    /// Create an array from a vector (no copying needed), shape `shape`.
+    ///
+    /// With custom strides, like `(3, 4).strides((1, 3))`, this is the same
+    /// as [`from_vec_dim_stride`](#method.from_vec_dim_stride).
+    ///
+    /// **Errors** if `shape` does not correspond to the number of elements in `v`.
+    ///
+    /// ```
+    /// use ndarray::OwnedArray;
+    /// use ndarray::ShapeBuilder;
+    /// use ndarray::arr2;
+    ///
+    /// let a = OwnedArray::from_vec_dim((2, 2).f(), vec![1., 2., 3., 4.]).unwrap();
+    /// assert_eq!(a, arr2(&[[1., 3.], [2., 4.]]));
+    /// ```
+    pub fn from_vec_dim<Sh>(shape: Sh, v: Vec<A>) -> Result<ArrayBase<S, D>, ShapeError>
+        where Sh: Into<StrideShape<D>>,
+    {
+        let shape = shape.into();
+        if shape.custom {
+            try!(dimension::can_index_slice(&v, &shape.dim, &shape.strides)
+                     .map_err(|e| error::in_operation(e, "from_vec_dim")));
+        } else if shape.dim.size_checked() != Some(v.len()) {
+            return Err(error::in_operation(error::incompatible_shapes(&v.len(), &shape.dim),
+                                           "from_vec_dim"));
         }
-        unsafe { Ok(Self::from_vec_dim_unchecked(dim, v)) }
+        unsafe { Ok(Self::from_vec_dim_unchecked(shape, v)) }
     }
 
     /// Create an array from a vector (no copying needed) using fortran
@@ -204,23 +283,18 @@ impl<S, A, D> ArrayBase<S, D>
     ///
     /// **Errors** if `dim` does not correspond to the number of elements in `v`.
     pub fn from_vec_dim_f(dim: D, v: Vec<A>) -> Result<ArrayBase<S, D>, ShapeError> {
-        if dim.size_checked() != Some(v.len()) {
-            return Err(error::incompatible_shapes(&v.len(), &dim));
-        }
-        unsafe { Ok(Self::from_vec_dim_unchecked_f(dim, v)) }
+        Self::from_vec_dim(dim.f(), v)
     }
 
-    /// Create an array from a vector (no copying needed).
+    /// Create an array from a vector (no copying needed), shape `shape`.
     ///
-    /// Unsafe because dimension is unchecked, and must be correct.
-    pub unsafe fn from_vec_dim_unchecked(dim: D, mut v: Vec<A>) -> ArrayBase<S, D> {
-        debug_assert!(dim.size_checked() == Some(v.len()));
-        ArrayBase {
-            ptr: v.as_mut_ptr(),
-            data: DataOwned::new(v),
-            strides: dim.default_strides(),
-            dim: dim,
-        }
+    /// Unsafe because the shape is unchecked, and must be correct.
+    pub unsafe fn from_vec_dim_unchecked<Sh>(shape: Sh, v: Vec<A>) -> ArrayBase<S, D>
+        where Sh: Into<StrideShape<D>>,
+    {
+        let shape = shape.into();
+        debug_assert!(shape.custom || shape.dim.size_checked() == Some(v.len()));
+        Self::from_vec_dim_stride_unchecked(shape.dim, shape.strides, v)
     }
 
     /// Create an array from a vector (with no copying needed),
@@ -228,9 +302,7 @@ impl<S, A, D> ArrayBase<S, D>
     ///
     /// Unsafe because dimension is unchecked, and must be correct.
     pub unsafe fn from_vec_dim_unchecked_f(dim: D, v: Vec<A>) -> ArrayBase<S, D> {
-        debug_assert!(dim.size_checked() == Some(v.len()));
-        let strides = dim.fortran_strides();
-        Self::from_vec_dim_stride_unchecked(dim, strides, v)
+        Self::from_vec_dim_unchecked(dim.f(), v)
     }
 
     /// Create an array from a vector and interpret it according to the
@@ -244,11 +316,7 @@ impl<S, A, D> ArrayBase<S, D>
     pub fn from_vec_dim_stride(dim: D, strides: D, v: Vec<A>)
         -> Result<ArrayBase<S, D>, ShapeError>
     {
-        dimension::can_index_slice(&v, &dim, &strides).map(|_| {
-            unsafe {
-                Self::from_vec_dim_stride_unchecked(dim, strides, v)
-            }
-        })
+        Self::from_vec_dim(dim.strides(strides), v)
     }
 
     /// Create an array from a vector and interpret it according to the
@@ -267,8 +335,10 @@ impl<S, A, D> ArrayBase<S, D>
                 _ => true,
             }
         });
+        // with negative strides, the first element is not the first in memory
+        let offset = dimension::offset_from_ptr_to_memory(&dim, &strides);
         ArrayBase {
-            ptr: v.as_mut_ptr(),
+            ptr: v.as_mut_ptr().offset(-offset),
             data: DataOwned::new(v),
             strides: strides,
             dim: dim
@@ -276,3 +346,84 @@ impl<S, A, D> ArrayBase<S, D>
     }
 
 }
+
+/// # Uninitialized Arrays
+///
+/// Constructors for an `OwnedArray` whose elements are allocated but not
+/// yet written, for filling an array without first writing every element
+/// with zeros or a default value.
+///
+/// The elements have type `MaybeUninit<A>`; write each of them through a
+/// view, then convert the array with
+/// [`.assume_init()`](#method.assume_init).
+///
+/// ```
+/// use std::mem::MaybeUninit;
+/// use ndarray::OwnedArray;
+/// use ndarray::arr2;
+///
+/// let mut a = OwnedArray::<f64, _>::uninit((2, 3));
+/// for ((i, j), elt) in a.indexed_iter_mut() {
+///     *elt = MaybeUninit::new((10 * i + j) as f64);
+/// }
+/// let a = unsafe { a.assume_init() };
+/// assert_eq!(a, arr2(&[[0., 1., 2.], [10., 11., 12.]]));
+/// ```
+impl<A, D> ArrayBase<Vec<A>, D>
+    where D: Dimension,
+{
+    /// Create an array with uninitialized elements, shape `shape`.
+    ///
+    /// **Panics** if the number of elements in `shape` would overflow usize.
+    pub fn uninit<Sh>(shape: Sh) -> OwnedArray<MaybeUninit<A>, D>
+        where Sh: ShapeBuilder<Dim=D>,
+    {
+        let shape = shape.into_shape();
+        let size = size_checked_unwrap!(shape.dim);
+        unsafe { OwnedArray::from_vec_dim_unchecked(shape, uninit_vec(size)) }
+    }
+
+    /// Create an array with uninitialized elements, dimension `dim` and
+    /// fortran memory order.
+    ///
+    /// **Panics** if the number of elements in `dim` would overflow usize.
+    ///
+    /// ```
+    /// use ndarray::OwnedArray;
+    ///
+    /// let a = OwnedArray::<f32, _>::uninit_f((2, 2, 2));
+    /// assert!(a.strides() == &[1, 2, 4]);
+    /// ```
+    pub fn uninit_f(dim: D) -> OwnedArray<MaybeUninit<A>, D> {
+        Self::uninit(dim.f())
+    }
+}
+
+fn uninit_vec<A>(size: usize) -> Vec<MaybeUninit<A>> {
+    let mut v = Vec::with_capacity(size);
+    // MaybeUninit elements need no initialization
+    unsafe {
+        v.set_len(size);
+    }
+    v
+}
+
+impl<A, D> ArrayBase<Vec<MaybeUninit<A>>, D>
+    where D: Dimension,
+{
+    /// Convert the array of uninitialized elements into an array of `A`,
+    /// keeping its shape and memory layout. No copying needed.
+    ///
+    /// Unsafe because every element must have been written with a valid
+    /// value of `A`.
+    pub unsafe fn assume_init(self) -> OwnedArray<A, D> {
+        let mut v = ManuallyDrop::new(self.data);
+        let data = Vec::from_raw_parts(v.as_mut_ptr() as *mut A, v.len(), v.capacity());
+        ArrayBase {
+            data: data,
+            ptr: self.ptr as *mut A,
+            dim: self.dim,
+            strides: self.strides,
+        }
+    }
+}
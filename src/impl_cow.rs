@@ -0,0 +1,73 @@
+// Copyright 2016 bluss and ndarray developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use imp_prelude::*;
+use {CowArray, CowRepr};
+
+/// # Methods for Copy-on-write Arrays
+///
+/// Methods for arrays `CowArray<'a, A, D>` that either borrow or own
+/// their elements.
+impl<'a, A, D> ArrayBase<CowRepr<'a, A>, D>
+    where D: Dimension,
+{
+    /// Return `true` if the array borrows its elements.
+    pub fn is_view(&self) -> bool {
+        match self.data {
+            CowRepr::View(_) => true,
+            CowRepr::Owned(_) => false,
+        }
+    }
+
+    /// Return `true` if the array owns its elements.
+    pub fn is_owned(&self) -> bool {
+        !self.is_view()
+    }
+
+    /// Convert into an array that owns its elements, cloning them if they
+    /// are borrowed.
+    pub fn into_owned(self) -> OwnedArray<A, D>
+        where A: Clone,
+    {
+        match self.data {
+            CowRepr::View(_) => self.to_owned(),
+            CowRepr::Owned(data) => ArrayBase {
+                data: data,
+                ptr: self.ptr,
+                dim: self.dim,
+                strides: self.strides,
+            },
+        }
+    }
+}
+
+impl<'a, A, D> From<ArrayView<'a, A, D>> for CowArray<'a, A, D>
+    where D: Dimension,
+{
+    fn from(view: ArrayView<'a, A, D>) -> Self {
+        ArrayBase {
+            data: CowRepr::View(view.data),
+            ptr: view.ptr,
+            dim: view.dim,
+            strides: view.strides,
+        }
+    }
+}
+
+impl<'a, A, D> From<OwnedArray<A, D>> for CowArray<'a, A, D>
+    where D: Dimension,
+{
+    fn from(array: OwnedArray<A, D>) -> Self {
+        ArrayBase {
+            data: CowRepr::Owned(array.data),
+            ptr: array.ptr,
+            dim: array.dim,
+            strides: array.strides,
+        }
+    }
+}
@@ -7,6 +7,7 @@
 // except according to those terms.
 
 use std::cmp;
+use std::mem;
 use std::ptr as std_ptr;
 use std::slice;
 
@@ -15,10 +16,14 @@ use imp_prelude::*;
 use arraytraits;
 use dimension;
 use iterators;
+use lanes;
+use windows;
 use error::{self, ShapeError};
 use super::zipsl;
 use {
     NdIndex,
+    RawArrayView,
+    RawArrayViewMut,
     AxisChunksIter,
     AxisChunksIterMut,
     Elements,
@@ -29,10 +34,18 @@ use {
     InnerIterMut,
     AxisIter,
     AxisIterMut,
+    Windows,
+    ExactChunks,
+    ExactChunksMut,
+    Lanes,
+    LanesMut,
+    Zip,
+    CowArray,
+    Order,
 };
 use stacking::stack;
 
-impl<A, S, D> ArrayBase<S, D> where S: Data<Elem=A>, D: Dimension
+impl<A, S, D> ArrayBase<S, D> where S: RawData<Elem=A>, D: Dimension
 {
     /// Return the total number of elements in the array.
     pub fn len(&self) -> usize {
@@ -63,6 +76,23 @@ impl<A, S, D> ArrayBase<S, D> where S: Data<Elem=A>, D: Dimension
         self.dim.ndim()
     }
 
+    /// Return a pointer to the first element in the array.
+    ///
+    /// Raw access to array elements needs to follow the strided indexing
+    /// scheme: an element at multi-index *I* in an array with strides *S* is
+    /// located at offset
+    ///
+    /// *Σ<sub>0 ≤ k < d</sub> I<sub>k</sub> × S<sub>k</sub>*
+    ///
+    /// where *d* is `self.ndim()`.
+    #[inline(always)]
+    pub fn as_ptr(&self) -> *const A {
+        self.ptr
+    }
+}
+
+impl<A, S, D> ArrayBase<S, D> where S: Data<Elem=A>, D: Dimension
+{
     /// Return a read-only view of the array
     pub fn view(&self) -> ArrayView<A, D> {
         debug_assert!(self.pointer_is_inbounds());
@@ -81,6 +111,25 @@ impl<A, S, D> ArrayBase<S, D> where S: Data<Elem=A>, D: Dimension
         }
     }
 
+    /// Return a raw view of the array.
+    pub fn raw_view(&self) -> RawArrayView<A, D> {
+        unsafe {
+            RawArrayView::from_shape_stride_ptr(self.dim.clone(), self.strides.clone(), self.ptr)
+        }
+    }
+
+    /// Return a raw mutable view of the array.
+    pub fn raw_view_mut(&mut self) -> RawArrayViewMut<A, D>
+        where S: DataMut,
+    {
+        self.ensure_unique();
+        unsafe {
+            RawArrayViewMut::from_shape_stride_ptr(self.dim.clone(),
+                                                   self.strides.clone(),
+                                                   self.ptr)
+        }
+    }
+
     /// Return an uniquely owned copy of the array
     pub fn to_owned(&self) -> OwnedArray<A, D>
         where A: Clone
@@ -105,18 +154,59 @@ impl<A, S, D> ArrayBase<S, D> where S: Data<Elem=A>, D: Dimension
 
     /// Turn the array into a shared ownership (copy on write) array,
     /// without any copying.
+    ///
+    /// An `ArcArray` is copied only if its data is shared.
     pub fn into_shared(self) -> RcArray<A, D>
         where S: DataOwned,
     {
+        let offset = self.offset_in_data();
         let data = self.data.into_shared();
         ArrayBase {
+            ptr: unsafe { (data.as_ptr() as *mut A).offset(offset) },
             data: data,
-            ptr: self.ptr,
             dim: self.dim,
             strides: self.strides,
         }
     }
 
+    /// Return a thread-safe shared ownership (copy on write) array.
+    pub fn to_shared_sync(&self) -> ArcArray<A, D>
+        where A: Clone
+    {
+        self.to_owned().into_shared_sync()
+    }
+
+    /// Turn the array into a thread-safe shared ownership (copy on write)
+    /// array, which can be sent to other threads.
+    ///
+    /// An `OwnedArray` is not copied, and an `RcArray` is copied only if its
+    /// data is shared.
+    pub fn into_shared_sync(self) -> ArcArray<A, D>
+        where S: DataOwned,
+              A: Clone,
+    {
+        let offset = self.offset_in_data();
+        let data = self.data.into_shared_sync();
+        ArrayBase {
+            ptr: unsafe { (data.as_ptr() as *mut A).offset(offset) },
+            data: data,
+            dim: self.dim,
+            strides: self.strides,
+        }
+    }
+
+    /// Return the offset in elements from the start of the owned data to
+    /// the array's pointer.
+    fn offset_in_data(&self) -> isize
+        where S: DataOwned,
+    {
+        let size = mem::size_of::<A>();
+        if size == 0 {
+            return 0;
+        }
+        (self.ptr as isize - self.data._data_slice().as_ptr() as isize) / size as isize
+    }
+
     /// Return an iterator of references to the elements of the array.
     ///
     /// Iterator element type is `&A`.
@@ -366,6 +456,50 @@ impl<A, S, D> ArrayBase<S, D> where S: Data<Elem=A>, D: Dimension
         self.view_mut().into_subview(axis, index)
     }
 
+    /// Along `axis`, select the subview `index` and return a view with
+    /// that axis removed, like [`.subview()`](#method.subview).
+    ///
+    /// **Errors** with `OutOfBounds` if `axis` or `index` is out of bounds.
+    ///
+    /// ```
+    /// use ndarray::{arr2, aview1, Axis, ErrorKind};
+    ///
+    /// let a = arr2(&[[1., 2.],
+    ///                [3., 4.]]);
+    /// assert_eq!(a.try_subview(Axis(1), 1).unwrap(), aview1(&[2., 4.]));
+    /// assert_eq!(a.try_subview(Axis(1), 2).unwrap_err().kind(), ErrorKind::OutOfBounds);
+    /// assert_eq!(a.try_subview(Axis(2), 0).unwrap_err().kind(), ErrorKind::OutOfBounds);
+    /// ```
+    pub fn try_subview(&self, axis: Axis, index: Ix)
+        -> Result<ArrayView<A, D::Smaller>, ShapeError>
+        where D: RemoveAxis,
+    {
+        try!(self.check_subview(axis, index));
+        Ok(self.subview(axis, index))
+    }
+
+    /// Along `axis`, select the subview `index` and return a read-write
+    /// view with that axis removed, like
+    /// [`.subview_mut()`](#method.subview_mut).
+    ///
+    /// **Errors** with `OutOfBounds` if `axis` or `index` is out of bounds.
+    pub fn try_subview_mut(&mut self, axis: Axis, index: Ix)
+        -> Result<ArrayViewMut<A, D::Smaller>, ShapeError>
+        where S: DataMut,
+              D: RemoveAxis,
+    {
+        try!(self.check_subview(axis, index));
+        Ok(self.subview_mut(axis, index))
+    }
+
+    fn check_subview(&self, axis: Axis, index: Ix) -> Result<(), ShapeError> {
+        if axis.axis() >= self.ndim() || index >= self.shape()[axis.axis()] {
+            let err = error::from_kind(error::ErrorKind::OutOfBounds);
+            return Err(error::in_operation(error::along(err, axis), "subview"));
+        }
+        Ok(())
+    }
+
     /// Collapse dimension `axis` into length one,
     /// and select the subview of `index` along that axis.
     ///
@@ -394,6 +528,167 @@ impl<A, S, D> ArrayBase<S, D> where S: Data<Elem=A>, D: Dimension
         }
     }
 
+    /// Along `axis`, select arbitrary subviews corresponding to `indices`
+    /// and copy them into a new array.
+    ///
+    /// Indices may be repeated and appear in any order.
+    ///
+    /// **Panics** if `axis` or an element of `indices` is out of bounds.
+    ///
+    /// ```
+    /// use ndarray::{arr2, Axis};
+    ///
+    /// let x = arr2(&[[0., 1.],
+    ///                [2., 3.],
+    ///                [4., 5.],
+    ///                [6., 7.],
+    ///                [8., 9.]]);
+    ///
+    /// let r = x.select(Axis(0), &[0, 4, 3]);
+    /// assert!(
+    ///         r == arr2(&[[0., 1.],
+    ///                     [8., 9.],
+    ///                     [6., 7.]])
+    ///);
+    /// ```
+    pub fn select(&self, axis: Axis, indices: &[Ix]) -> OwnedArray<A, D>
+        where A: Clone,
+    {
+        // The result is filled one subview at a time, so `axis` is the
+        // slowest varying axis in memory and the rest are in standard order.
+        let mut subview_dim = self.dim.clone();
+        subview_dim.set_axis(axis, 1);
+        let mut strides = subview_dim.default_strides();
+        strides.set_axis(axis, subview_dim.size());
+        let mut dim = self.dim.clone();
+        dim.set_axis(axis, indices.len());
+
+        let mut v = Vec::with_capacity(dim.size());
+        for &index in indices {
+            let mut subview = self.view();
+            subview.isubview(axis, index);
+            v.extend(subview.iter().cloned());
+        }
+        unsafe {
+            ArrayBase::from_vec_dim_stride_unchecked(dim, strides, v)
+        }
+    }
+
+    /// Along `axis`, take the subviews corresponding to `indices`
+    /// and copy them into a new array.
+    ///
+    /// This is the checked version of [`.select()`](#method.select).
+    ///
+    /// **Errors** if `axis` or an element of `indices` is out of bounds.
+    pub fn take(&self, axis: Axis, indices: &[Ix]) -> Result<OwnedArray<A, D>, ShapeError>
+        where A: Clone,
+    {
+        try!(check_axis_indices(&self.dim, axis, indices));
+        Ok(self.select(axis, indices))
+    }
+
+    /// Along `axis`, assign the subviews of `values` to the subviews of
+    /// `self` at `indices`: subview *k* of `values` is assigned to subview
+    /// `indices[k]` of `self`.
+    ///
+    /// If an index is repeated, the last assignment wins.
+    ///
+    /// **Errors** if `axis` or an element of `indices` is out of bounds.<br>
+    /// **Errors** if the shape of `values` is not the shape of `self` with
+    /// the length of `axis` replaced by `indices.len()`.
+    ///
+    /// ```
+    /// use ndarray::{arr2, Axis};
+    ///
+    /// let mut x = arr2(&[[0, 0, 0],
+    ///                    [0, 0, 0]]);
+    /// x.put(Axis(1), &[2, 0], &arr2(&[[1, 2],
+    ///                                 [3, 4]])).unwrap();
+    /// assert_eq!(x, arr2(&[[2, 0, 1],
+    ///                      [4, 0, 3]]));
+    /// ```
+    pub fn put<S2>(&mut self, axis: Axis, indices: &[Ix], values: &ArrayBase<S2, D>)
+        -> Result<(), ShapeError>
+        where S: DataMut,
+              S2: Data<Elem=A>,
+              A: Clone,
+    {
+        try!(check_axis_indices(&self.dim, axis, indices));
+        let mut expected_dim = self.dim.clone();
+        expected_dim.set_axis(axis, indices.len());
+        if values.dim != expected_dim {
+            return Err(error::in_operation(error::incompatible_shapes(&expected_dim, &values.dim),
+                                           "put"));
+        }
+        for (k, &index) in indices.iter().enumerate() {
+            let mut target = self.view_mut();
+            target.isubview(axis, index);
+            let mut source = values.view();
+            source.isubview(axis, k);
+            target.assign(&source);
+        }
+        Ok(())
+    }
+
+    /// Return a one-dimensional array of the elements where `mask` is `true`,
+    /// in logical order.
+    ///
+    /// **Errors** if the shape of `mask` is not the same as the shape of
+    /// `self`.
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    ///
+    /// let a = arr2(&[[1, -2],
+    ///                [-3, 4]]);
+    /// let positive = a.mask_select(&a.map(|&x| x > 0)).unwrap();
+    /// assert_eq!(positive.as_slice(), Some(&[1, 4][..]));
+    /// ```
+    pub fn mask_select<S2>(&self, mask: &ArrayBase<S2, D>)
+        -> Result<OwnedArray<A, Ix>, ShapeError>
+        where S2: Data<Elem=bool>,
+              A: Clone,
+    {
+        if self.dim != mask.dim {
+            return Err(error::in_operation(error::incompatible_shapes(&self.dim, &mask.dim),
+                                           "mask_select"));
+        }
+        let v = self.iter().zip(mask.iter())
+                    .filter(|&(_, &m)| m)
+                    .map(|(x, _)| x.clone())
+                    .collect::<Vec<_>>();
+        Ok(OwnedArray::from_vec(v))
+    }
+
+    /// Assign `value` to the elements where `mask` is `true`.
+    ///
+    /// **Errors** if the shape of `mask` is not the same as the shape of
+    /// `self`.
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    ///
+    /// let mut a = arr2(&[[1, -2],
+    ///                    [-3, 4]]);
+    /// let negative = a.map(|&x| x < 0);
+    /// a.assign_where(&negative, &0).unwrap();
+    /// assert_eq!(a, arr2(&[[1, 0],
+    ///                      [0, 4]]));
+    /// ```
+    pub fn assign_where<S2>(&mut self, mask: &ArrayBase<S2, D>, value: &A)
+        -> Result<(), ShapeError>
+        where S: DataMut,
+              S2: Data<Elem=bool>,
+              A: Clone,
+    {
+        if self.dim != mask.dim {
+            return Err(error::in_operation(error::incompatible_shapes(&self.dim, &mask.dim),
+                                           "assign_where"));
+        }
+        self.zip_mut_with_same_shape(mask, |x, &m| if m { *x = value.clone(); });
+        Ok(())
+    }
+
     /// Return an iterator that traverses over all dimensions but the innermost,
     /// and yields each inner row.
     ///
@@ -545,6 +840,135 @@ impl<A, S, D> ArrayBase<S, D> where S: Data<Elem=A>, D: Dimension
         iterators::new_chunk_iter_mut(self.view_mut(), axis.axis(), size)
     }
 
+    /// Return a producer of the overlapping windows of shape `window` of
+    /// the array; there is a window starting at each element where it fits
+    /// inside the array, or none if it does not fit at all.
+    ///
+    /// The producer is an `NdProducer` with one axis for each axis of the
+    /// array, so it can be used with [`Zip`](struct.Zip.html), and it is an
+    /// iterable of `ArrayView<A, D>` in logical order. Use
+    /// [`.stride()`](struct.Windows.html#method.stride) and
+    /// [`.dilation()`](struct.Windows.html#method.dilation) on it to space
+    /// out the windows or their elements.
+    ///
+    /// **Panics** if `window` does not have one non-zero length per axis of
+    /// the array.
+    ///
+    /// ```
+    /// use ndarray::{OwnedArray, Zip};
+    /// use ndarray::arr2;
+    ///
+    /// let a = OwnedArray::from_iter(0..12).into_shape((3, 4)).unwrap();
+    /// let mut sums = OwnedArray::zeros((2, 3));
+    /// Zip::from(&mut sums).and(a.windows((2, 2))).apply(|s, w| *s = w.scalar_sum());
+    /// assert_eq!(sums, arr2(&[[10, 14, 18],
+    ///                         [26, 30, 34]]));
+    /// ```
+    pub fn windows(&self, window: D) -> Windows<A, D> {
+        windows::new_windows(self.view(), window)
+    }
+
+    /// Return a producer of the non-overlapping chunks of shape `chunk` of
+    /// the array, the N-dimensional tiles of the array. Elements at the end
+    /// of an axis that do not fill a whole chunk are left out.
+    ///
+    /// Like [`.windows()`](#method.windows), the producer can be used with
+    /// `Zip` and iterated over.
+    ///
+    /// **Panics** if `chunk` does not have one non-zero length per axis of
+    /// the array.
+    ///
+    /// ```
+    /// use ndarray::OwnedArray;
+    /// use ndarray::arr2;
+    ///
+    /// let a = OwnedArray::from_iter(0..15).into_shape((3, 5)).unwrap();
+    /// let chunks: Vec<_> = a.exact_chunks((2, 2)).into_iter().collect();
+    /// assert_eq!(chunks.len(), 2);
+    /// assert_eq!(chunks[1], arr2(&[[2, 3], [7, 8]]));
+    /// ```
+    pub fn exact_chunks(&self, chunk: D) -> ExactChunks<A, D> {
+        windows::new_exact_chunks(self.view(), chunk)
+    }
+
+    /// Return a producer of the non-overlapping read-write chunks of shape
+    /// `chunk` of the array, like [`.exact_chunks()`](#method.exact_chunks).
+    ///
+    /// **Panics** if `chunk` does not have one non-zero length per axis of
+    /// the array.
+    pub fn exact_chunks_mut(&mut self, chunk: D) -> ExactChunksMut<A, D>
+        where S: DataMut
+    {
+        windows::new_exact_chunks_mut(self.view_mut(), chunk)
+    }
+
+    /// Return a producer of the 1-dimensional lanes along `axis`: for each
+    /// index of the other axes, the view of the elements along `axis`.
+    ///
+    /// The producer has the remaining axes of the array, so it can be used
+    /// with [`Zip`](struct.Zip.html), and it is an iterable of
+    /// `ArrayView<A, Ix>` in logical order, with an exact size, iteration
+    /// from the back and `split_at`. `.lanes(Axis(n - 1))` of an array with
+    /// `n` axes gives the same views as
+    /// [`.inner_iter()`](#method.inner_iter).
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// ```
+    /// use ndarray::OwnedArray;
+    /// use ndarray::{arr2, aview1, Axis};
+    ///
+    /// let a = OwnedArray::from_iter(0..12).into_shape((2, 3, 2)).unwrap();
+    /// let mut columns = a.lanes(Axis(1)).into_iter();
+    /// assert_eq!(columns.len(), 4);
+    /// assert_eq!(columns.next().unwrap(), aview1(&[0, 2, 4]));
+    /// assert_eq!(columns.next_back().unwrap(), aview1(&[7, 9, 11]));
+    ///
+    /// assert_eq!(a.lanes(Axis(1)).raw_dim(), (2, 2));
+    /// ```
+    pub fn lanes(&self, axis: Axis) -> Lanes<A, D::Smaller>
+        where D: RemoveAxis
+    {
+        lanes::new_lanes(self.view(), axis)
+    }
+
+    /// Return a producer of the 1-dimensional read-write lanes along
+    /// `axis`, like [`.lanes()`](#method.lanes).
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    pub fn lanes_mut(&mut self, axis: Axis) -> LanesMut<A, D::Smaller>
+        where S: DataMut,
+              D: RemoveAxis
+    {
+        lanes::new_lanes_mut(self.view_mut(), axis)
+    }
+
+    /// Call `f` with each 1-dimensional lane along `axis`, and return the
+    /// array of the results, which has `axis` removed.
+    ///
+    /// If `axis` has length zero, `f` is called with empty lanes.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// ```
+    /// use ndarray::{arr2, aview1, Axis};
+    ///
+    /// let a = arr2(&[[1., 2., 3.],
+    ///                [4., 5., 6.]]);
+    /// let ranges = a.map_axis(Axis(0), |col| col[1] - col[0]);
+    /// assert_eq!(ranges, aview1(&[3., 3., 3.]));
+    /// let firsts = a.map_axis(Axis(1), |row| row[0]);
+    /// assert_eq!(firsts, aview1(&[1., 4.]));
+    /// ```
+    pub fn map_axis<'a, B, F>(&'a self, axis: Axis, f: F)
+        -> OwnedArray<B, D::Smaller>
+        where D: RemoveAxis,
+              F: FnMut(ArrayView<'a, A, Ix>) -> B,
+              A: 'a,
+    {
+        Zip::from(self.lanes(axis)).map_collect(f)
+    }
+
     // Return (length, stride) for diagonal
     fn diag_params(&self) -> (Ix, Ixs) {
         /* empty shape has len 1 */
@@ -614,21 +1038,22 @@ impl<A, S, D> ArrayBase<S, D> where S: Data<Elem=A>, D: Dimension
         true
     }
 
+    /// Return `true` if the array elements are contiguous in memory, in
+    /// any order of axes and with any sign of strides.
     fn is_contiguous(&self) -> bool {
         let defaults = self.dim.default_strides();
         if self.strides == defaults {
             return true;
         }
-        if self.ndim() == 1 { return false; }
+        if self.ndim() == 1 { return self.strides()[0] == -1; }
         let order = self.strides._fastest_varying_stride_order();
         let strides = self.strides.slice();
 
-        // FIXME: Negative strides
         let dim = self.dim.slice();
         let mut cstride = 1;
         for &i in order.slice() {
             // a dimension of length 1 can have unequal strides
-            if dim[i] != 1 && strides[i] != cstride {
+            if dim[i] != 1 && (strides[i] as Ixs).abs() as Ix != cstride {
                 return false;
             }
             cstride *= dim[i];
@@ -636,18 +1061,12 @@ impl<A, S, D> ArrayBase<S, D> where S: Data<Elem=A>, D: Dimension
         true
     }
 
-    /// Return a pointer to the first element in the array.
-    ///
-    /// Raw access to array elements needs to follow the strided indexing
-    /// scheme: an element at multi-index *I* in an array with strides *S* is
-    /// located at offset
-    ///
-    /// *Σ<sub>0 ≤ k < d</sub> I<sub>k</sub> × S<sub>k</sub>*
-    ///
-    /// where *d* is `self.ndim()`.
-    #[inline(always)]
-    pub fn as_ptr(&self) -> *const A {
-        self.ptr
+    /// Return a pointer to the element with the lowest memory address.
+    fn memory_start_ptr(&self) -> *mut A {
+        let offset = dimension::offset_from_ptr_to_memory(&self.dim, &self.strides);
+        unsafe {
+            self.ptr.offset(offset)
+        }
     }
 
     /// Return a mutable pointer to the first element in the array.
@@ -693,13 +1112,23 @@ impl<A, S, D> ArrayBase<S, D> where S: Data<Elem=A>, D: Dimension
     /// return `None` otherwise.
     ///
     /// If this function returns `Some(_)`, then the elements in the slice
-    /// have whatever order the elements have in memory.
+    /// have whatever order the elements have in memory. Arrays with negative
+    /// strides are supported; the slice starts at the element with the
+    /// lowest address, which is not the array’s first element.
     ///
-    /// Implementation notes: Does not yet support negatively strided arrays.
+    /// ```
+    /// use ndarray::{arr2, Axis};
+    ///
+    /// let mut a = arr2(&[[1, 2],
+    ///                    [3, 4]]);
+    /// a.invert_axis(Axis(1));
+    /// assert_eq!(a.as_slice(), None);
+    /// assert_eq!(a.as_slice_memory_order(), Some(&[1, 2, 3, 4][..]));
+    /// ```
     pub fn as_slice_memory_order(&self) -> Option<&[A]> {
         if self.is_contiguous() {
             unsafe {
-                Some(slice::from_raw_parts(self.ptr, self.len()))
+                Some(slice::from_raw_parts(self.memory_start_ptr(), self.len()))
             }
         } else {
             None
@@ -714,7 +1143,7 @@ impl<A, S, D> ArrayBase<S, D> where S: Data<Elem=A>, D: Dimension
         if self.is_contiguous() {
             self.ensure_unique();
             unsafe {
-                Some(slice::from_raw_parts_mut(self.ptr, self.len()))
+                Some(slice::from_raw_parts_mut(self.memory_start_ptr(), self.len()))
             }
         } else {
             None
@@ -742,25 +1171,48 @@ impl<A, S, D> ArrayBase<S, D> where S: Data<Elem=A>, D: Dimension
         where S: DataShared + DataOwned,
               A: Clone,
               E: Dimension,
+    {
+        match self.try_reshape(shape) {
+            Ok(a) => a,
+            Err(e) => panic!("ndarray: {}", e),
+        }
+    }
+
+    /// Transform the array into `shape`, like
+    /// [`.reshape()`](#method.reshape).
+    ///
+    /// **Errors** with `IncompatibleShape` if the number of elements of
+    /// `shape` is not that of the array.
+    ///
+    /// ```
+    /// use ndarray::{rcarr1, ErrorKind};
+    ///
+    /// let a = rcarr1(&[1., 2., 3., 4.]);
+    /// assert_eq!(a.try_reshape((2, 2)).unwrap().shape(), &[2, 2]);
+    /// assert_eq!(a.try_reshape((3, 2)).unwrap_err().kind(), ErrorKind::IncompatibleShape);
+    /// ```
+    pub fn try_reshape<E>(&self, shape: E) -> Result<ArrayBase<S, E>, ShapeError>
+        where S: DataShared + DataOwned,
+              A: Clone,
+              E: Dimension,
     {
         if shape.size_checked() != Some(self.dim.size()) {
-            panic!("ndarray: incompatible shapes in reshape, attempted from: {:?}, to: {:?}",
-                   self.dim.slice(),
-                   shape.slice())
+            return Err(error::in_operation(error::incompatible_shapes(&self.dim, &shape),
+                                           "reshape"));
         }
         // Check if contiguous, if not => copy all, else just adapt strides
         if self.is_standard_layout() {
             let cl = self.clone();
-            ArrayBase {
+            Ok(ArrayBase {
                 data: cl.data,
                 ptr: cl.ptr,
                 strides: shape.default_strides(),
                 dim: shape,
-            }
+            })
         } else {
             let v = self.iter().map(|x| x.clone()).collect::<Vec<A>>();
             unsafe {
-                ArrayBase::from_vec_dim_unchecked(shape, v)
+                Ok(ArrayBase::from_vec_dim_unchecked(shape, v))
             }
         }
     }
@@ -785,7 +1237,8 @@ impl<A, S, D> ArrayBase<S, D> where S: Data<Elem=A>, D: Dimension
         where E: Dimension
     {
         if shape.size_checked() != Some(self.dim.size()) {
-            return Err(error::incompatible_shapes(&self.dim, &shape));
+            return Err(error::in_operation(error::incompatible_shapes(&self.dim, &shape),
+                                           "into_shape"));
         }
         // Check if contiguous, if not => copy all, else just adapt strides
         if self.is_standard_layout() {
@@ -803,10 +1256,122 @@ impl<A, S, D> ArrayBase<S, D> where S: Data<Elem=A>, D: Dimension
                 dim: shape,
             })
         } else {
-            Err(error::from_kind(error::ErrorKind::IncompatibleLayout))
+            let err = error::from_kind(error::ErrorKind::IncompatibleLayout);
+            Err(error::in_operation(error::shapes(err, &self.dim, &shape), "into_shape"))
+        }
+    }
+
+    /// Transform the array into `shape`, reading the elements in the
+    /// logical order `order`, without copying.
+    ///
+    /// Any shape with the same number of elements is accepted, and one axis
+    /// of `shape` can have length [`INFER`](constant.INFER.html), to have
+    /// its length inferred from the number of elements.
+    ///
+    /// New strides are computed whenever the existing strides permit it,
+    /// even if the array is not contiguous; use
+    /// [`.to_shape()`](#method.to_shape) to copy the elements otherwise.
+    ///
+    /// **Errors** with `IncompatibleShape` if the shapes are incompatible,
+    /// or with `IncompatibleLayout` if the elements can not be arranged
+    /// with the new shape without copying.
+    ///
+    /// ```
+    /// use ndarray::{arr2, aview1, Order, S, Si, INFER};
+    ///
+    /// let a = arr2(&[[1, 2, 3],
+    ///                [4, 5, 6]]);
+    /// assert_eq!(a.view().into_shape_with_order(6, Order::C).unwrap(),
+    ///            aview1(&[1, 2, 3, 4, 5, 6]));
+    /// // the transpose is contiguous in F order
+    /// assert_eq!(a.t().into_shape_with_order((2, INFER), Order::F).unwrap(),
+    ///            arr2(&[[1, 3, 5], [2, 4, 6]]));
+    ///
+    /// // every other column: not contiguous, but the rows can be split
+    /// let v = a.slice(&[S, Si(0, None, 2)]);
+    /// assert_eq!(v.into_shape_with_order((2, 2, 1), Order::C).unwrap().shape(), &[2, 2, 1]);
+    /// assert!(v.into_shape_with_order(4, Order::C).is_err());
+    /// ```
+    pub fn into_shape_with_order<E>(self, shape: E, order: Order)
+        -> Result<ArrayBase<S, E>, ShapeError>
+        where E: Dimension
+    {
+        let shape = try!(dimension::infer_shape(shape, self.dim.size())
+                             .map_err(|e| error::in_operation(e, "into_shape_with_order")));
+        if shape.size_checked() != Some(self.dim.size()) {
+            return Err(error::in_operation(error::incompatible_shapes(&self.dim, &shape),
+                                           "into_shape_with_order"));
+        }
+        match dimension::reshape_strides(&self.dim, &self.strides, &shape, order == Order::F) {
+            Some(strides) => Ok(ArrayBase {
+                data: self.data,
+                ptr: self.ptr,
+                dim: shape,
+                strides: strides,
+            }),
+            None => {
+                let err = error::from_kind(error::ErrorKind::IncompatibleLayout);
+                Err(error::in_operation(error::shapes(err, &self.dim, &shape),
+                                        "into_shape_with_order"))
+            }
         }
     }
 
+    /// Transform the array into `shape`, reading the elements in C order
+    /// for a shape like `(2, 3)`, or in F order for a shape like
+    /// `(2, 3).f()`.
+    ///
+    /// Return a view if the elements can be arranged with the new shape
+    /// without copying, like with
+    /// [`.into_shape_with_order()`](#method.into_shape_with_order), and a
+    /// new array with the elements in the order of the shape otherwise. One
+    /// axis of `shape` can have length [`INFER`](constant.INFER.html), to
+    /// have its length inferred from the number of elements.
+    ///
+    /// **Errors** with `IncompatibleShape` if the shapes are incompatible.
+    ///
+    /// ```
+    /// use ndarray::{arr2, aview1, ShapeBuilder};
+    ///
+    /// let a = arr2(&[[1, 2, 3],
+    ///                [4, 5, 6]]);
+    /// let c = a.to_shape(6).unwrap();
+    /// assert!(c.is_view());
+    /// assert_eq!(c, aview1(&[1, 2, 3, 4, 5, 6]));
+    ///
+    /// let f = a.to_shape(6.f()).unwrap();
+    /// assert!(f.is_owned());
+    /// assert_eq!(f, aview1(&[1, 4, 2, 5, 3, 6]));
+    /// ```
+    pub fn to_shape<Sh, E>(&self, shape: Sh) -> Result<CowArray<A, E>, ShapeError>
+        where Sh: ShapeBuilder<Dim=E>,
+              A: Clone,
+              E: Dimension,
+    {
+        let shape = shape.into_shape();
+        let order = if shape.is_c { Order::C } else { Order::F };
+        let dim = try!(dimension::infer_shape(shape.dim, self.dim.size())
+                           .map_err(|e| error::in_operation(e, "to_shape")));
+        if dim.size_checked() != Some(self.dim.size()) {
+            return Err(error::in_operation(error::incompatible_shapes(&self.dim, &dim),
+                                           "to_shape"));
+        }
+        if let Ok(view) = self.view().into_shape_with_order(dim.clone(), order) {
+            return Ok(CowArray::from(view));
+        }
+        let array = match order {
+            Order::C => {
+                let v = self.iter().cloned().collect::<Vec<A>>();
+                unsafe { OwnedArray::from_vec_dim_unchecked(dim, v) }
+            }
+            Order::F => {
+                let v = self.view().reversed_axes().iter().cloned().collect::<Vec<A>>();
+                unsafe { OwnedArray::from_vec_dim_unchecked(dim.f(), v) }
+            }
+        };
+        Ok(CowArray::from(array))
+    }
+
     /// Act like a larger size and/or shape array by *broadcasting*
     /// into a larger shape, if possible.
     ///
@@ -923,6 +1488,36 @@ impl<A, S, D> ArrayBase<S, D> where S: Data<Elem=A>, D: Dimension
         self
     }
 
+    /// Reverse the stride of `axis`.
+    ///
+    /// This does not move any data, it just adjusts the array’s pointer and
+    /// strides so that the elements along `axis` are visited in reverse
+    /// order. The result is the same as slicing `axis` with `s![..;-1]`.
+    ///
+    /// **Panics** if the axis is out of bounds.
+    ///
+    /// ```
+    /// use ndarray::{arr2, Axis};
+    ///
+    /// let mut a = arr2(&[[1., 2., 3.],
+    ///                    [4., 5., 6.]]);
+    /// a.invert_axis(Axis(1));
+    /// assert!(
+    ///     a == arr2(&[[3., 2., 1.],
+    ///                 [6., 5., 4.]])
+    /// );
+    /// ```
+    pub fn invert_axis(&mut self, axis: Axis) {
+        let len = self.dim.axis(axis);
+        let stride = self.strides.axis(axis);
+        if len > 1 {
+            unsafe {
+                self.ptr = self.ptr.offset(dimension::stride_offset(len - 1, stride));
+            }
+        }
+        self.strides.set_axis(axis, (-(stride as Ixs)) as Ix);
+    }
+
     /// Return a transposed view of the array.
     ///
     /// This is a shorthand for `self.view().reversed_axes()`.
@@ -932,6 +1527,186 @@ impl<A, S, D> ArrayBase<S, D> where S: Data<Elem=A>, D: Dimension
         self.view().reversed_axes()
     }
 
+    /// Insert a new axis of length one at `axis`, increasing the number of
+    /// axes by one.
+    ///
+    /// The new axis is placed before `axis`, or last if `axis` is equal to
+    /// the number of axes.
+    ///
+    /// **Panics** if `axis` is strictly greater than the number of axes.
+    ///
+    /// ```
+    /// use ndarray::{arr1, arr2, Axis};
+    ///
+    /// let a = arr1(&[1, 2, 3]);
+    /// assert_eq!(a.view().insert_axis(Axis(0)), arr2(&[[1, 2, 3]]));
+    /// assert_eq!(a.insert_axis(Axis(1)), arr2(&[[1], [2], [3]]));
+    /// ```
+    pub fn insert_axis(self, axis: Axis) -> ArrayBase<S, <D as InsertAxis>::Larger>
+        where D: InsertAxis,
+    {
+        let dim = self.dim.insert_axis(axis);
+        let mut strides = self.strides.insert_axis(axis);
+        // pick the stride the axis would have in a contiguous array, so that
+        // standard layout is preserved
+        if axis.axis() < self.dim.ndim() {
+            let stride = (self.strides.axis(axis) as Ixs) * self.dim.axis(axis) as Ixs;
+            strides.set_axis(axis, stride as Ix);
+        }
+        ArrayBase {
+            data: self.data,
+            ptr: self.ptr,
+            dim: dim,
+            strides: strides,
+        }
+    }
+
+    /// Remove the axis `axis`, which must have length one, decreasing the
+    /// number of axes by one.
+    ///
+    /// **Panics** if `axis` is out of bounds or its length is not one.
+    ///
+    /// ```
+    /// use ndarray::{arr1, arr2, Axis};
+    ///
+    /// let a = arr2(&[[1, 2, 3]]);
+    /// assert_eq!(a.remove_axis(Axis(0)), arr1(&[1, 2, 3]));
+    /// ```
+    pub fn remove_axis(self, axis: Axis) -> ArrayBase<S, <D as RemoveAxis>::Smaller>
+        where D: RemoveAxis,
+    {
+        assert!(self.dim.axis(axis) == 1,
+                "ndarray: can not remove axis {} of length {}, its length must be one",
+                axis.axis(), self.dim.axis(axis));
+        self.into_subview(axis, 0)
+    }
+
+    /// Remove all axes of length one.
+    ///
+    /// The number of remaining axes is not known until runtime, so the
+    /// result uses a dynamic dimension.
+    ///
+    /// ```
+    /// use ndarray::OwnedArray;
+    ///
+    /// let a = OwnedArray::<f32, _>::zeros((1, 3, 1, 2));
+    /// assert_eq!(a.squeeze().shape(), &[3, 2]);
+    /// ```
+    pub fn squeeze(self) -> ArrayBase<S, Vec<Ix>> {
+        let mut dim = Vec::with_capacity(self.ndim());
+        let mut strides = Vec::with_capacity(self.ndim());
+        for (&d, &s) in zipsl(self.dim.slice(), self.strides.slice()) {
+            if d != 1 {
+                dim.push(d);
+                strides.push(s);
+            }
+        }
+        ArrayBase {
+            data: self.data,
+            ptr: self.ptr,
+            dim: dim,
+            strides: strides,
+        }
+    }
+
+    /// Permute the axes.
+    ///
+    /// Axis *i* of the result is axis `axes[i]` of `self`. This does not move
+    /// any data, it just adjusts the array’s dimensions and strides.
+    ///
+    /// **Panics** if `axes` is not a permutation of the axes of the array,
+    /// i.e. if an axis is out of bounds or occurs more than once.
+    ///
+    /// ```
+    /// use ndarray::OwnedArray;
+    ///
+    /// let a = OwnedArray::<f32, _>::zeros((2, 3, 4));
+    /// assert_eq!(a.permuted_axes((1, 2, 0)).shape(), &[3, 4, 2]);
+    /// ```
+    pub fn permuted_axes(self, axes: D) -> ArrayBase<S, D> {
+        let ndim = self.ndim();
+        assert_eq!(axes.ndim(), ndim,
+                   "ndarray: expected a permutation of {} axes, got {} axes",
+                   ndim, axes.ndim());
+        let mut seen = vec![false; ndim];
+        for &ax in axes.slice() {
+            assert!(ax < ndim && !seen[ax],
+                    "ndarray: {:?} is not a permutation of the axes", axes.slice());
+            seen[ax] = true;
+        }
+        let mut dim = self.dim.clone();
+        let mut strides = self.strides.clone();
+        for (i, &ax) in axes.slice().iter().enumerate() {
+            dim.slice_mut()[i] = self.dim.slice()[ax];
+            strides.slice_mut()[i] = self.strides.slice()[ax];
+        }
+        ArrayBase {
+            data: self.data,
+            ptr: self.ptr,
+            dim: dim,
+            strides: strides,
+        }
+    }
+
+    /// Merge axis `take` into axis `into`, decreasing the number of axes
+    /// by one.
+    ///
+    /// The resulting axis `into` has the combined length of both axes and
+    /// visits the elements in the same order as nested iteration with
+    /// `take` outermost. This does not move any data, so it requires that
+    /// stepping once along `take` is the same as stepping through all of
+    /// `into`; axes of length zero or one can always be merged.
+    ///
+    /// Axis `into` is named by its index before the merge.
+    ///
+    /// **Panics** if an axis is out of bounds or if `take` and `into` are
+    /// the same axis.<br>
+    /// **Errors** with `IncompatibleLayout` if the strides do not permit
+    /// the merge.
+    ///
+    /// ```
+    /// use ndarray::{arr1, arr2, Axis, ErrorKind};
+    ///
+    /// let a = arr2(&[[1, 2, 3],
+    ///                [4, 5, 6]]);
+    /// assert_eq!(a.view().merge_axes(Axis(0), Axis(1)).unwrap(),
+    ///            arr1(&[1, 2, 3, 4, 5, 6]));
+    /// assert_eq!(a.t().merge_axes(Axis(1), Axis(0)).unwrap(),
+    ///            arr1(&[1, 2, 3, 4, 5, 6]));
+    /// assert_eq!(a.view().merge_axes(Axis(1), Axis(0)).unwrap_err().kind(),
+    ///            ErrorKind::IncompatibleLayout);
+    /// ```
+    pub fn merge_axes(self, take: Axis, into: Axis)
+        -> Result<ArrayBase<S, <D as RemoveAxis>::Smaller>, ShapeError>
+        where D: RemoveAxis,
+    {
+        assert!(take != into, "ndarray: can not merge axis {} into itself", take.axis());
+        let take_len = self.dim.axis(take);
+        let into_len = self.dim.axis(into);
+        let take_stride = self.strides.axis(take) as Ixs;
+        let into_stride = self.strides.axis(into) as Ixs;
+        let stride = if take_len <= 1 {
+            into_stride
+        } else if into_len <= 1 {
+            take_stride
+        } else if take_stride == into_stride * into_len as Ixs {
+            into_stride
+        } else {
+            let err = error::from_kind(error::ErrorKind::IncompatibleLayout);
+            return Err(error::in_operation(error::along(err, take), "merge_axes"));
+        };
+        let mut dim = self.dim.clone();
+        let mut strides = self.strides.clone();
+        dim.set_axis(into, take_len * into_len);
+        strides.set_axis(into, stride as Ix);
+        Ok(ArrayBase {
+            data: self.data,
+            ptr: self.ptr,
+            dim: dim.remove_axis(take),
+            strides: strides.remove_axis(take),
+        })
+    }
+
     fn pointer_is_inbounds(&self) -> bool {
         let slc = self.data._data_slice();
         if slc.is_empty() {
@@ -958,6 +1733,34 @@ impl<A, S, D> ArrayBase<S, D> where S: Data<Elem=A>, D: Dimension
         self.zip_mut_with(rhs, |x, y| *x = y.clone());
     }
 
+    /// Perform an elementwise assigment to `self` from `rhs`, like
+    /// [`.assign()`](#method.assign).
+    ///
+    /// **Errors** with `IncompatibleShape` if `rhs` can not be broadcast to
+    /// the shape of `self`; `self` is unchanged.
+    ///
+    /// ```
+    /// use ndarray::{arr1, OwnedArray};
+    ///
+    /// let mut a = OwnedArray::zeros((2, 2));
+    /// assert!(a.try_assign(&arr1(&[1., 2.])).is_ok());
+    /// assert!(a.try_assign(&arr1(&[1., 2., 3.])).is_err());
+    /// assert_eq!(a.row(1), arr1(&[1., 2.]));
+    /// ```
+    pub fn try_assign<E: Dimension, S2>(&mut self, rhs: &ArrayBase<S2, E>)
+        -> Result<(), ShapeError>
+        where S: DataMut,
+              A: Clone,
+              S2: Data<Elem=A>,
+    {
+        if rhs.ndim() != 0 && rhs.broadcast(self.dim()).is_none() {
+            return Err(error::in_operation(error::incompatible_shapes(&rhs.dim, &self.dim),
+                                           "assign"));
+        }
+        self.assign(rhs);
+        Ok(())
+    }
+
     /// Perform an elementwise assigment to `self` from scalar `x`.
     pub fn assign_scalar(&mut self, x: &A)
         where S: DataMut, A: Clone,
@@ -1107,6 +1910,9 @@ impl<A, S, D> ArrayBase<S, D> where S: Data<Elem=A>, D: Dimension
         where F: FnMut(&'a A) -> B,
               A: 'a,
     {
+        // `to_vec` writes each result once into uninitialized capacity, and
+        // keeps the length current so a panic in `f` drops only the written
+        // elements; there is no zeroing here for `uninit` to avoid.
         if let Some(slc) = self.as_slice_memory_order() {
             let v = ::iterators::to_vec(slc.iter().map(f));
             unsafe {
@@ -1234,3 +2040,17 @@ impl<A, S, D> ArrayBase<S, D> where S: Data<Elem=A>, D: Dimension
         res
     }
 }
+
+/// Check that `axis` and all of `indices` are in bounds for `dim`.
+fn check_axis_indices<D: Dimension>(dim: &D, axis: Axis, indices: &[Ix])
+    -> Result<(), ShapeError>
+{
+    if axis.axis() >= dim.ndim() {
+        return Err(error::from_kind(error::ErrorKind::OutOfBounds));
+    }
+    let len = dim.axis(axis);
+    if indices.iter().any(|&i| i >= len) {
+        return Err(error::from_kind(error::ErrorKind::OutOfBounds));
+    }
+    Ok(())
+}
@@ -9,6 +9,10 @@
 use std::any::Any;
 use libnum::Complex;
 
+use imp_prelude::*;
+use dimension::{co_broadcast, DimMax};
+use error::{self, ShapeError};
+
 /// Elements that can be used as direct operands in arithmetic with arrays.
 ///
 /// For example, `f64` is a `ScalarOperand` which means that for an array `a`,
@@ -58,18 +62,19 @@ macro_rules! impl_binary_op(
 ///
 /// `self` must be an `OwnedArray` or `RcArray`.
 ///
-/// If their shapes disagree, `rhs` is broadcast to the shape of `self`.
+/// If their shapes disagree, `self` and `rhs` are both broadcast to
+/// their common shape, see [`DimMax`](trait.DimMax.html).
 ///
 /// **Panics** if broadcasting isn’t possible.
 impl<A, S, S2, D, E> $trt<ArrayBase<S2, E>> for ArrayBase<S, D>
     where A: Clone + $trt<A, Output=A>,
           S: DataOwned<Elem=A> + DataMut,
           S2: Data<Elem=A>,
-          D: Dimension,
+          D: Dimension + DimMax<E>,
           E: Dimension,
 {
-    type Output = ArrayBase<S, D>;
-    fn $mth(self, rhs: ArrayBase<S2, E>) -> ArrayBase<S, D>
+    type Output = ArrayBase<S, <D as DimMax<E>>::Output>;
+    fn $mth(self, rhs: ArrayBase<S2, E>) -> Self::Output
     {
         self.$mth(&rhs)
     }
@@ -80,23 +85,28 @@ impl<A, S, S2, D, E> $trt<ArrayBase<S2, E>> for ArrayBase<S, D>
 /// between `self` and reference `rhs`,
 /// and return the result (based on `self`).
 ///
-/// If their shapes disagree, `rhs` is broadcast to the shape of `self`.
+/// If their shapes disagree, `self` and `rhs` are both broadcast to
+/// their common shape, see [`DimMax`](trait.DimMax.html). The result
+/// reuses the elements of `self` if `self` already has that shape, and
+/// is a new allocation otherwise. An array view can not be reallocated,
+/// so for an `ArrayViewMut` `self`, `rhs` must broadcast to its shape.
 ///
 /// **Panics** if broadcasting isn’t possible.
 impl<'a, A, S, S2, D, E> $trt<&'a ArrayBase<S2, E>> for ArrayBase<S, D>
     where A: Clone + $trt<A, Output=A>,
           S: DataMut<Elem=A>,
           S2: Data<Elem=A>,
-          D: Dimension,
+          D: Dimension + DimMax<E>,
           E: Dimension,
 {
-    type Output = ArrayBase<S, D>;
-    fn $mth(mut self, rhs: &ArrayBase<S2, E>) -> ArrayBase<S, D>
+    type Output = ArrayBase<S, <D as DimMax<E>>::Output>;
+    fn $mth(self, rhs: &ArrayBase<S2, E>) -> Self::Output
     {
-        self.zip_mut_with(rhs, |x, y| {
-            *x = as_expr!(x.clone() $operator y.clone());
-        });
-        self
+        let shape = match co_broadcast_shape(&self.dim, &rhs.dim, stringify!($mth)) {
+            Ok(shape) => shape,
+            Err(_) => co_broadcast_panic(&self.dim, &rhs.dim),
+        };
+        self.zip_into_shape(rhs, shape, |x, y| as_expr!(x.clone() $operator y.clone()))
     }
 }
 
@@ -105,20 +115,24 @@ impl<'a, A, S, S2, D, E> $trt<&'a ArrayBase<S2, E>> for ArrayBase<S, D>
 /// between references `self` and `rhs`,
 /// and return the result as a new `OwnedArray`.
 ///
-/// If their shapes disagree, `rhs` is broadcast to the shape of `self`.
+/// If their shapes disagree, `self` and `rhs` are both broadcast to
+/// their common shape, see [`DimMax`](trait.DimMax.html).
 ///
 /// **Panics** if broadcasting isn’t possible.
 impl<'a, 'b, A, S, S2, D, E> $trt<&'a ArrayBase<S2, E>> for &'b ArrayBase<S, D>
     where A: Clone + $trt<A, Output=A>,
           S: Data<Elem=A>,
           S2: Data<Elem=A>,
-          D: Dimension,
+          D: Dimension + DimMax<E>,
           E: Dimension,
 {
-    type Output = OwnedArray<A, D>;
-    fn $mth(self, rhs: &'a ArrayBase<S2, E>) -> OwnedArray<A, D> {
-        // FIXME: Can we co-broadcast arrays here? And how?
-        self.to_owned().$mth(rhs)
+    type Output = OwnedArray<A, <D as DimMax<E>>::Output>;
+    fn $mth(self, rhs: &'a ArrayBase<S2, E>) -> Self::Output {
+        match self.zip_with_co_broadcast(rhs, stringify!($mth),
+                                         |x, y| as_expr!(x.clone() $operator y.clone())) {
+            Ok(result) => result,
+            Err(_) => co_broadcast_panic(&self.dim, &rhs.dim),
+        }
     }
 }
 
@@ -161,6 +175,121 @@ impl<'a, A, S, D, B> $trt<B> for &'a ArrayBase<S, D>
     );
 );
 
+macro_rules! impl_try_binary_op(
+    ($trt:ident, $operator:tt, $try_mth:ident, $doc:expr) => (
+    /// Perform elementwise
+    #[doc=$doc]
+    /// between references `self` and `rhs`,
+    /// and return the result as a new `OwnedArray`.
+    ///
+    /// If their shapes disagree, `self` and `rhs` are both broadcast to
+    /// their common shape.
+    ///
+    /// **Errors** if broadcasting to a common shape isn’t possible.
+    pub fn $try_mth<S2, E>(&self, rhs: &ArrayBase<S2, E>)
+        -> Result<OwnedArray<A, <D as DimMax<E>>::Output>, ShapeError>
+        where A: Clone + $trt<A, Output=A>,
+              S2: Data<Elem=A>,
+              D: DimMax<E>,
+              E: Dimension,
+    {
+        self.zip_with_co_broadcast(rhs, stringify!($try_mth),
+                                   |x, y| as_expr!(x.clone() $operator y.clone()))
+    }
+    );
+);
+
+#[cold]
+#[inline(never)]
+fn co_broadcast_panic<D, E>(shape1: &D, shape2: &E) -> !
+    where D: Dimension,
+          E: Dimension,
+{
+    panic!("ndarray: could not broadcast arrays with shapes: {:?} and {:?} together",
+           shape1.slice(), shape2.slice())
+}
+
+impl<A, S, D> ArrayBase<S, D>
+    where S: Data<Elem=A>,
+          D: Dimension,
+{
+    /// Broadcast `self` and `rhs` to their common shape, and return a new
+    /// array with the result of `f` for each pair of elements.
+    ///
+    /// **Errors** for the operation named `op` if broadcasting isn’t
+    /// possible.
+    fn zip_with_co_broadcast<B, S2, E, F>(&self, rhs: &ArrayBase<S2, E>, op: &'static str,
+                                          mut f: F)
+        -> Result<OwnedArray<A, <D as DimMax<E>>::Output>, ShapeError>
+        where A: Clone,
+              S2: Data<Elem=B>,
+              D: DimMax<E>,
+              E: Dimension,
+              F: FnMut(&A, &B) -> A,
+    {
+        let shape = try!(co_broadcast_shape(&self.dim, &rhs.dim, op));
+        let mut result = self.broadcast_unwrap(shape).to_owned();
+        result.zip_mut_with(rhs, |x, y| *x = f(x, y));
+        Ok(result)
+    }
+
+    /// Compute `f` for each pair of elements of `self` and `rhs`, both
+    /// broadcast to `shape`, reusing the storage of `self` if it already
+    /// has that shape.
+    ///
+    /// **Panics** if `self` has another shape and its storage can not own
+    /// a new allocation, because `rhs` then does not broadcast to `self`.
+    fn zip_into_shape<B, S2, E, F, Sh>(mut self, rhs: &ArrayBase<S2, E>, shape: Sh, mut f: F)
+        -> ArrayBase<S, Sh>
+        where A: Clone,
+              S: DataMut,
+              S2: Data<Elem=B>,
+              E: Dimension,
+              F: FnMut(&A, &B) -> A,
+              Sh: Dimension,
+    {
+        if shape.slice() != self.dim.slice() {
+            let mut result = self.broadcast_unwrap(shape.clone()).to_owned();
+            result.zip_mut_with(rhs, |x, y| *x = f(x, y));
+            let ptr = result.ptr;
+            // moving the vector into `S` does not move its elements
+            if let Some(data) = S::_new_owned(result.data) {
+                return ArrayBase {
+                    ptr: ptr,
+                    data: data,
+                    dim: result.dim,
+                    strides: result.strides,
+                };
+            }
+        }
+        // a view keeps its shape, so this panics unless `rhs` broadcasts to it
+        self.zip_mut_with(rhs, |x, y| *x = f(x, y));
+        let mut strides = Sh::zero_index_with_ndim(self.ndim());
+        strides.slice_mut().copy_from_slice(self.strides.slice());
+        ArrayBase {
+            data: self.data,
+            ptr: self.ptr,
+            dim: shape,
+            strides: strides,
+        }
+    }
+}
+
+/// Return the common shape of `shape1` and `shape2`.
+///
+/// **Errors** for the operation named `op` if broadcasting isn’t possible.
+fn co_broadcast_shape<D, E>(shape1: &D, shape2: &E, op: &'static str)
+    -> Result<<D as DimMax<E>>::Output, ShapeError>
+    where D: DimMax<E>,
+          E: Dimension,
+{
+    co_broadcast::<_, _, <D as DimMax<E>>::Output>(shape1, shape2)
+        .map_err(|e| {
+            let err = error::incompatible_shapes(shape1, shape2);
+            error::caused_by(error::in_operation(err, op), e)
+        })
+}
+
 // Pick the expression $a for commutative and $b for ordered binop
 macro_rules! if_commutative {
     (Commute { $a:expr } or { $b:expr }) => ($a);
@@ -215,7 +344,6 @@ impl<'a, S, D> $trt<&'a ArrayBase<S, D>> for $scalar
 
 mod arithmetic_ops {
     use super::*;
-    use imp_prelude::*;
 
     use std::ops::*;
     use libnum::Complex;
@@ -231,6 +359,24 @@ mod arithmetic_ops {
     impl_binary_op!(Shl, <<, shl, <<=, "left shift");
     impl_binary_op!(Shr, >>, shr, >>=, "right shift");
 
+    /// Arithmetic operations that return an error instead of panicking
+    /// when the array shapes are incompatible.
+    impl<A, S, D> ArrayBase<S, D>
+        where S: Data<Elem=A>,
+              D: Dimension,
+    {
+    impl_try_binary_op!(Add, +, try_add, "addition");
+    impl_try_binary_op!(Sub, -, try_sub, "subtraction");
+    impl_try_binary_op!(Mul, *, try_mul, "multiplication");
+    impl_try_binary_op!(Div, /, try_div, "division");
+    impl_try_binary_op!(Rem, %, try_rem, "remainder");
+    impl_try_binary_op!(BitAnd, &, try_bitand, "bit and");
+    impl_try_binary_op!(BitOr, |, try_bitor, "bit or");
+    impl_try_binary_op!(BitXor, ^, try_bitxor, "bit xor");
+    impl_try_binary_op!(Shl, <<, try_shl, "left shift");
+    impl_try_binary_op!(Shr, >>, try_shr, "right shift");
+    }
+
     macro_rules! all_scalar_ops {
         ($int_scalar:ty) => (
             impl_scalar_lhs_op!($int_scalar, Commute, +, Add, add, "addition");
@@ -313,7 +459,6 @@ mod arithmetic_ops {
 
 mod assign_ops {
     use super::*;
-    use imp_prelude::*;
 
     macro_rules! impl_assign_op {
         ($trt:ident, $method:ident, $doc:expr) => {
@@ -0,0 +1,170 @@
+// Copyright 2016 bluss and ndarray developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::mem;
+
+use imp_prelude::*;
+use {RawArrayView, RawArrayViewMut, RawViewRepr, StrideShape};
+
+/// # Methods for Raw Array Views
+///
+/// Methods for read-only raw array views `RawArrayView<A, D>`
+///
+/// Raw views are created from a pointer with `from_shape_ptr` or from an
+/// array with [`.raw_view()`](#method.raw_view); the elements are accessed
+/// by converting them into regular views.
+impl<A, D> ArrayBase<RawViewRepr<*const A>, D>
+    where D: Dimension,
+{
+    /// Create a raw view of the elements at `ptr` with shape `shape`, which
+    /// is in standard layout for a dimension like `(2, 3)`, and can select
+    /// F order or custom strides with the
+    /// [`ShapeBuilder`](trait.ShapeBuilder.html) methods.
+    ///
+    /// Unsafe because `ptr` is not checked: it must point to the elements
+    /// for any conversion of the raw view into a view, see
+    /// [`.deref_into_view()`](#method.deref_into_view).
+    ///
+    /// ```
+    /// use ndarray::RawArrayView;
+    /// use ndarray::arr2;
+    ///
+    /// let data = [1, 2, 3, 4, 5, 6];
+    /// let raw = unsafe { RawArrayView::from_shape_ptr((2, 3), data.as_ptr()) };
+    /// assert_eq!(raw.shape(), &[2, 3]);
+    /// let view = unsafe { raw.deref_into_view() };
+    /// assert_eq!(view, arr2(&[[1, 2, 3], [4, 5, 6]]));
+    ///
+    /// use ndarray::ShapeBuilder;
+    /// let raw = unsafe { RawArrayView::from_shape_ptr((3, 2).f(), data.as_ptr()) };
+    /// let view = unsafe { raw.deref_into_view() };
+    /// assert_eq!(view, arr2(&[[1, 4], [2, 5], [3, 6]]));
+    /// ```
+    pub unsafe fn from_shape_ptr<Sh>(shape: Sh, ptr: *const A) -> Self
+        where Sh: Into<StrideShape<D>>,
+    {
+        let shape = shape.into();
+        Self::from_shape_stride_ptr(shape.dim, shape.strides, ptr)
+    }
+
+    /// Create a raw view of the elements at `ptr` with shape `dim` and
+    /// element strides `strides`. The strides can be any values, including
+    /// negative (stored as `Ix`, e.g. `-1isize as Ix`) and zero; `ptr` is
+    /// the first element (the element at index zero).
+    ///
+    /// Unsafe for the same reason as
+    /// [`from_shape_ptr`](#method.from_shape_ptr).
+    pub unsafe fn from_shape_stride_ptr(dim: D, strides: D, ptr: *const A) -> Self {
+        ArrayBase {
+            data: RawViewRepr::new(),
+            ptr: ptr as *mut A,
+            dim: dim,
+            strides: strides,
+        }
+    }
+
+    /// Convert the raw view into a regular view with lifetime `'a`.
+    ///
+    /// Unsafe because every element must be valid for reads and be a valid
+    /// value of `A` for the lifetime `'a`, and must not be mutated during
+    /// it (except through `Cell` and similar types).
+    pub unsafe fn deref_into_view<'a>(self) -> ArrayView<'a, A, D> {
+        ArrayView::new_(self.ptr, self.dim, self.strides)
+    }
+
+    /// Cast the raw view to a raw view of elements of type `B`, with the
+    /// same shape and strides.
+    ///
+    /// **Panics** if `A` and `B` have different sizes.
+    pub fn cast<B>(self) -> RawArrayView<B, D> {
+        assert_eq!(mem::size_of::<A>(), mem::size_of::<B>(),
+                   "ndarray: cast requires elements of the same size");
+        unsafe {
+            RawArrayView::from_shape_stride_ptr(self.dim, self.strides, self.ptr as *const B)
+        }
+    }
+}
+
+impl<A, D> ArrayBase<RawViewRepr<*mut A>, D>
+    where D: Dimension,
+{
+    /// Create a raw mutable view of the elements at `ptr` with shape
+    /// `shape`, in standard layout, F order or with custom strides like for
+    /// [`RawArrayView`](type.RawArrayView.html).
+    ///
+    /// Unsafe because `ptr` is not checked: it must point to the elements
+    /// for any conversion of the raw view into a view, see
+    /// [`.deref_into_view_mut()`](#method.deref_into_view_mut).
+    pub unsafe fn from_shape_ptr<Sh>(shape: Sh, ptr: *mut A) -> Self
+        where Sh: Into<StrideShape<D>>,
+    {
+        let shape = shape.into();
+        Self::from_shape_stride_ptr(shape.dim, shape.strides, ptr)
+    }
+
+    /// Create a raw mutable view of the elements at `ptr` with shape `dim`
+    /// and element strides `strides`, which can be any values, like for
+    /// [`RawArrayView`](type.RawArrayView.html).
+    ///
+    /// Unsafe for the same reason as
+    /// [`from_shape_ptr`](#method.from_shape_ptr-1).
+    pub unsafe fn from_shape_stride_ptr(dim: D, strides: D, ptr: *mut A) -> Self {
+        ArrayBase {
+            data: RawViewRepr::new(),
+            ptr: ptr,
+            dim: dim,
+            strides: strides,
+        }
+    }
+
+    /// Return a mutable pointer to the first element in the array.
+    #[inline(always)]
+    pub fn as_mut_ptr(&self) -> *mut A {
+        self.ptr
+    }
+
+    /// Convert into a read-only raw view.
+    pub fn into_raw_view(self) -> RawArrayView<A, D> {
+        unsafe {
+            RawArrayView::from_shape_stride_ptr(self.dim, self.strides, self.ptr)
+        }
+    }
+
+    /// Convert the raw view into a regular read-only view with lifetime
+    /// `'a`.
+    ///
+    /// Unsafe for the same reasons as
+    /// [`RawArrayView::deref_into_view`](#method.deref_into_view).
+    pub unsafe fn deref_into_view<'a>(self) -> ArrayView<'a, A, D> {
+        ArrayView::new_(self.ptr, self.dim, self.strides)
+    }
+
+    /// Convert the raw view into a regular read-write view with lifetime
+    /// `'a`.
+    ///
+    /// Unsafe because every element must be valid for reads and writes and
+    /// be a valid value of `A` for the lifetime `'a`, and must not be
+    /// accessed by anything else during it. In particular, two different
+    /// indices must not reach the same element, so zero strides are only
+    /// allowed on axes of length one.
+    pub unsafe fn deref_into_view_mut<'a>(self) -> ArrayViewMut<'a, A, D> {
+        ArrayViewMut::new_(self.ptr, self.dim, self.strides)
+    }
+
+    /// Cast the raw view to a raw view of elements of type `B`, with the
+    /// same shape and strides; for example from `MaybeUninit<A>` to `A`.
+    ///
+    /// **Panics** if `A` and `B` have different sizes.
+    pub fn cast<B>(self) -> RawArrayViewMut<B, D> {
+        assert_eq!(mem::size_of::<A>(), mem::size_of::<B>(),
+                   "ndarray: cast requires elements of the same size");
+        unsafe {
+            RawArrayViewMut::from_shape_stride_ptr(self.dim, self.strides, self.ptr as *mut B)
+        }
+    }
+}
@@ -8,7 +8,8 @@
 
 use imp_prelude::*;
 use dimension::{self, stride_offset};
-use error::ShapeError;
+use error::{self, ShapeError};
+use StrideShape;
 
 /// # Methods for Array Views
 ///
@@ -19,11 +20,44 @@ use error::ShapeError;
 impl<'a, A, D> ArrayBase<ViewRepr<&'a A>, D>
     where D: Dimension,
 {
+    /// Create a read-only array view borrowing its data from a slice, with
+    /// shape `shape`: C order for a dimension like `(2, 3)`, or F order or
+    /// custom strides with the [`ShapeBuilder`](trait.ShapeBuilder.html)
+    /// methods.
+    ///
+    /// **Errors** if the shape does not correspond to the length of the
+    /// slice, or like [`from_slice_dim_stride`](#method.from_slice_dim_stride)
+    /// for custom strides.
+    ///
+    /// ```
+    /// use ndarray::ArrayView;
+    /// use ndarray::ShapeBuilder;
+    /// use ndarray::arr2;
+    ///
+    /// // column major data
+    /// let s = [1, 4, 2, 5, 3, 6];
+    /// let a = ArrayView::from_shape((2, 3).f(), &s).unwrap();
+    /// assert_eq!(a, arr2(&[[1, 2, 3], [4, 5, 6]]));
+    /// ```
+    pub fn from_shape<Sh>(shape: Sh, xs: &'a [A]) -> Result<Self, ShapeError>
+        where Sh: Into<StrideShape<D>>,
+    {
+        let shape = shape.into();
+        if !shape.custom && shape.dim.size_checked() != Some(xs.len()) {
+            return Err(error::in_operation(error::incompatible_shapes(&xs.len(), &shape.dim),
+                                           "from_shape"));
+        }
+        Self::from_slice_dim_stride(shape.dim, shape.strides, xs)
+    }
+
     /// Create a read-only array view borrowing its data from a slice.
     ///
     /// Checks whether `dim` and `strides` are compatible with the slice's
     /// length, returning an `Err` if not compatible.
     ///
+    /// Strides may be negative (stored as `Ix`, e.g. `-1isize as Ix`): an
+    /// axis with a negative stride starts at the far end of the slice.
+    ///
     /// ```
     /// use ndarray::ArrayView;
     /// use ndarray::arr3;
@@ -48,7 +82,8 @@ impl<'a, A, D> ArrayBase<ViewRepr<&'a A>, D>
     {
         dimension::can_index_slice(xs, &dim, &strides).map(|_| {
             unsafe {
-                Self::new_(xs.as_ptr(), dim, strides)
+                let offset = dimension::offset_from_ptr_to_memory(&dim, &strides);
+                Self::new_(xs.as_ptr().offset(-offset), dim, strides)
             }
         })
     }
@@ -103,6 +138,24 @@ impl<'a, A, D> ArrayBase<ViewRepr<&'a A>, D>
 impl<'a, A, D> ArrayBase<ViewRepr<&'a mut A>, D>
     where D: Dimension,
 {
+    /// Create a read-write array view borrowing its data from a slice, with
+    /// shape `shape`, like [`ArrayView::from_shape`](#method.from_shape).
+    ///
+    /// **Errors** if the shape does not correspond to the length of the
+    /// slice, or like
+    /// [`from_slice_dim_stride`](#method.from_slice_dim_stride-1) for
+    /// custom strides.
+    pub fn from_shape<Sh>(shape: Sh, xs: &'a mut [A]) -> Result<Self, ShapeError>
+        where Sh: Into<StrideShape<D>>,
+    {
+        let shape = shape.into();
+        if !shape.custom && shape.dim.size_checked() != Some(xs.len()) {
+            return Err(error::in_operation(error::incompatible_shapes(&xs.len(), &shape.dim),
+                                           "from_shape"));
+        }
+        Self::from_slice_dim_stride(shape.dim, shape.strides, xs)
+    }
+
     /// Create a read-write array view borrowing its data from a slice.
     ///
     /// Checks whether `dim` and `strides` are compatible with the slice's
@@ -133,7 +186,8 @@ impl<'a, A, D> ArrayBase<ViewRepr<&'a mut A>, D>
     {
         dimension::can_index_slice(xs, &dim, &strides).map(|_| {
             unsafe {
-                Self::new_(xs.as_mut_ptr(), dim, strides)
+                let offset = dimension::offset_from_ptr_to_memory(&dim, &strides);
+                Self::new_(xs.as_mut_ptr().offset(-offset), dim, strides)
             }
         })
     }
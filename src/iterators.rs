@@ -75,7 +75,7 @@ impl<'a, A, D: Dimension> Baseiter<'a, A, D> {
         unsafe { self.next().map(|p| &mut *p) }
     }
 
-    fn size_hint(&self) -> usize {
+    pub fn size_hint(&self) -> usize {
         match self.index {
             None => 0,
             Some(ref ix) => {
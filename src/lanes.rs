@@ -0,0 +1,339 @@
+// Copyright 2016 bluss and ndarray developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Producers and iterators of the 1-dimensional lanes of an array.
+
+use std::marker::PhantomData;
+
+use imp_prelude::*;
+use zip::{Layout, NdProducer};
+
+/// A producer of the 1-dimensional lanes of an array along an axis.
+///
+/// The lanes are laid out in an array of their own, with the remaining
+/// axes of the array, so that the producer can be used with
+/// [`Zip`](struct.Zip.html), or iterated over in logical order.
+///
+/// Iterator element type is `ArrayView<'a, A, Ix>`.
+///
+/// See [`.lanes()`](struct.ArrayBase.html#method.lanes) for more
+/// information.
+pub struct Lanes<'a, A: 'a, D> {
+    /// the first element of each lane
+    base: ArrayView<'a, A, D>,
+    inner_len: Ix,
+    inner_stride: Ixs,
+}
+
+/// A producer of the 1-dimensional read-write lanes of an array along an
+/// axis.
+///
+/// Iterator element type is `ArrayViewMut<'a, A, Ix>`.
+///
+/// See [`.lanes_mut()`](struct.ArrayBase.html#method.lanes_mut) for more
+/// information.
+pub struct LanesMut<'a, A: 'a, D> {
+    base: ArrayViewMut<'a, A, D>,
+    inner_len: Ix,
+    inner_stride: Ixs,
+}
+
+pub fn new_lanes<A, D>(v: ArrayView<A, D>, axis: Axis) -> Lanes<A, D::Smaller>
+    where D: RemoveAxis
+{
+    let inner_len = v.shape()[axis.axis()];
+    let inner_stride = v.strides()[axis.axis()];
+    Lanes {
+        base: unsafe {
+            ArrayView::new_(v.ptr, v.dim.remove_axis(axis), v.strides.remove_axis(axis))
+        },
+        inner_len: inner_len,
+        inner_stride: inner_stride,
+    }
+}
+
+pub fn new_lanes_mut<A, D>(v: ArrayViewMut<A, D>, axis: Axis) -> LanesMut<A, D::Smaller>
+    where D: RemoveAxis
+{
+    let inner_len = v.shape()[axis.axis()];
+    let inner_stride = v.strides()[axis.axis()];
+    LanesMut {
+        base: unsafe {
+            ArrayViewMut::new_(v.ptr, v.dim.remove_axis(axis), v.strides.remove_axis(axis))
+        },
+        inner_len: inner_len,
+        inner_stride: inner_stride,
+    }
+}
+
+/// An iterator over the lanes of an array.
+///
+/// Iterator element type is `ArrayView<'a, A, Ix>`.
+///
+/// See [`Lanes`](struct.Lanes.html).
+pub struct LanesIter<'a, A: 'a, D> {
+    core: LanesCore<A, D>,
+    life: PhantomData<&'a A>,
+}
+
+/// An iterator over the read-write lanes of an array.
+///
+/// Iterator element type is `ArrayViewMut<'a, A, Ix>`.
+///
+/// See [`LanesMut`](struct.LanesMut.html).
+pub struct LanesIterMut<'a, A: 'a, D> {
+    core: LanesCore<A, D>,
+    life: PhantomData<&'a mut A>,
+}
+
+/// The lanes between the `front` and `back` positions, counted in logical
+/// order.
+struct LanesCore<A, D> {
+    ptr: *mut A,
+    dim: D,
+    strides: D,
+    front: usize,
+    back: usize,
+    inner_len: Ix,
+    inner_stride: Ixs,
+}
+
+impl<A, D: Dimension> LanesCore<A, D> {
+    fn new(ptr: *mut A, dim: D, strides: D, inner_len: Ix, inner_stride: Ixs) -> Self {
+        LanesCore {
+            ptr: ptr,
+            back: dim.size(),
+            dim: dim,
+            strides: strides,
+            front: 0,
+            inner_len: inner_len,
+            inner_stride: inner_stride,
+        }
+    }
+
+    /// Return a pointer to the first element of lane number `n`.
+    fn lane_ptr(&self, mut n: usize) -> *mut A {
+        let mut offset = 0;
+        for (&len, &stride) in self.dim.slice().iter().zip(self.strides.slice()).rev() {
+            offset += (n % len) as Ixs * stride as Ixs;
+            n /= len;
+        }
+        unsafe { self.ptr.offset(offset) }
+    }
+
+    fn next(&mut self) -> Option<*mut A> {
+        if self.front == self.back {
+            None
+        } else {
+            self.front += 1;
+            Some(self.lane_ptr(self.front - 1))
+        }
+    }
+
+    fn next_back(&mut self) -> Option<*mut A> {
+        if self.front == self.back {
+            None
+        } else {
+            self.back -= 1;
+            Some(self.lane_ptr(self.back))
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.back - self.front
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        assert!(index <= self.len());
+        let mid = self.front + index;
+        let left = LanesCore {
+            ptr: self.ptr,
+            dim: self.dim.clone(),
+            strides: self.strides.clone(),
+            front: self.front,
+            back: mid,
+            inner_len: self.inner_len,
+            inner_stride: self.inner_stride,
+        };
+        let right = LanesCore {
+            front: mid,
+            ..self
+        };
+        (left, right)
+    }
+}
+
+macro_rules! lanes_impl {
+    ($name:ident, $iter:ident, $view:ident) => {
+impl<'a, A, D> $name<'a, A, D>
+    where D: Dimension
+{
+    /// Return the number of lanes along each of the remaining axes.
+    pub fn raw_dim(&self) -> D {
+        self.base.dim.clone()
+    }
+
+    /// Split the producer along `axis` at lane `index`, yielding two
+    /// disjoint sets of lanes.
+    ///
+    /// **Panics** if `axis` or `index` is out of bounds.
+    pub fn split_at(self, axis: Axis, index: Ix) -> (Self, Self) {
+        let (a, b) = self.base.split_at(axis, index);
+        let left = $name {
+            base: a,
+            inner_len: self.inner_len,
+            inner_stride: self.inner_stride,
+        };
+        let right = $name {
+            base: b,
+            inner_len: self.inner_len,
+            inner_stride: self.inner_stride,
+        };
+        (left, right)
+    }
+}
+
+impl<'a, A, D> IntoIterator for $name<'a, A, D>
+    where D: Dimension
+{
+    type Item = $view<'a, A, Ix>;
+    type IntoIter = $iter<'a, A, D>;
+    fn into_iter(self) -> Self::IntoIter {
+        $iter {
+            core: LanesCore::new(self.base.ptr, self.base.dim, self.base.strides,
+                                 self.inner_len, self.inner_stride),
+            life: PhantomData,
+        }
+    }
+}
+
+impl<'a, A, D> NdProducer for $name<'a, A, D>
+    where D: Dimension,
+{
+    type Item = $view<'a, A, Ix>;
+    type Dim = D;
+    type Ptr = *mut A;
+    type Stride = isize;
+
+    fn layout(&self) -> Layout {
+        self.base.layout()
+    }
+
+    fn raw_dim(&self) -> D {
+        self.base.dim.clone()
+    }
+
+    fn as_ptr(&self) -> *mut A {
+        self.base.ptr
+    }
+
+    #[inline(always)]
+    unsafe fn as_ref(&self, ptr: *mut A) -> Self::Item {
+        $view::new_(ptr, self.inner_len, self.inner_stride as Ix)
+    }
+
+    #[inline(always)]
+    unsafe fn uget_ptr(&self, index: &D) -> *mut A {
+        self.base.uget_ptr(index)
+    }
+
+    fn stride_of(&self, axis: Axis) -> isize {
+        self.base.stride_of(axis)
+    }
+
+    #[inline(always)]
+    fn contiguous_stride(&self) -> isize {
+        self.base.contiguous_stride()
+    }
+
+    fn split_at(self, axis: Axis, index: Ix) -> (Self, Self) {
+        self.split_at(axis, index)
+    }
+}
+
+impl<'a, A, D> $iter<'a, A, D>
+    where D: Dimension
+{
+    /// Split the iterator at lane `index`, yielding two disjoint iterators.
+    ///
+    /// **Panics** if `index` is strictly greater than the iterator's length.
+    pub fn split_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.core.split_at(index);
+        ($iter { core: left, life: PhantomData }, $iter { core: right, life: PhantomData })
+    }
+}
+
+impl<'a, A, D> Iterator for $iter<'a, A, D>
+    where D: Dimension
+{
+    type Item = $view<'a, A, Ix>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (len, stride) = (self.core.inner_len, self.core.inner_stride);
+        self.core.next().map(|ptr| unsafe { $view::new_(ptr, len, stride as Ix) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.core.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, A, D> DoubleEndedIterator for $iter<'a, A, D>
+    where D: Dimension
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (len, stride) = (self.core.inner_len, self.core.inner_stride);
+        self.core.next_back().map(|ptr| unsafe { $view::new_(ptr, len, stride as Ix) })
+    }
+}
+
+impl<'a, A, D> ExactSizeIterator for $iter<'a, A, D>
+    where D: Dimension
+{}
+    }
+}
+
+lanes_impl!(Lanes, LanesIter, ArrayView);
+lanes_impl!(LanesMut, LanesIterMut, ArrayViewMut);
+
+impl<'a, A, D> Clone for Lanes<'a, A, D>
+    where D: Clone
+{
+    fn clone(&self) -> Self {
+        Lanes {
+            base: self.base.clone(),
+            inner_len: self.inner_len,
+            inner_stride: self.inner_stride,
+        }
+    }
+}
+
+impl<'a, A, D> Clone for LanesIter<'a, A, D>
+    where D: Clone
+{
+    fn clone(&self) -> Self {
+        LanesIter {
+            core: LanesCore {
+                ptr: self.core.ptr,
+                dim: self.core.dim.clone(),
+                strides: self.core.strides.clone(),
+                front: self.core.front,
+                back: self.core.back,
+                inner_len: self.core.inner_len,
+                inner_stride: self.core.inner_stride,
+            },
+            life: PhantomData,
+        }
+    }
+}
+
+unsafe impl<'a, A, D> Send for LanesIter<'a, A, D> where A: Sync, D: Send { }
+unsafe impl<'a, A, D> Sync for LanesIter<'a, A, D> where A: Sync, D: Sync { }
+unsafe impl<'a, A, D> Send for LanesIterMut<'a, A, D> where A: Send, D: Send { }
+unsafe impl<'a, A, D> Sync for LanesIterMut<'a, A, D> where A: Sync, D: Sync { }
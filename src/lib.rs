@@ -17,6 +17,8 @@
 //!   An array where the data is owned uniquely.
 //! - [`RcArray`](type.RcArray.html):
 //!   An array where the data has shared ownership and is copy on write.
+//! - [`ArcArray`](type.ArcArray.html):
+//!   Like `RcArray`, but it can be sent to and shared between threads.
 //! - [`ArrayView`](type.ArrayView.html), [`ArrayViewMut`](type.ArrayViewMut.html):
 //!   Lightweight array views.
 //!
@@ -31,6 +33,8 @@
 //!   innermost dimension.
 //! - Array views can be used to slice and mutate any `[T]` data using
 //!   `ArrayView::from` and `ArrayViewMut::from`.
+//! - Arrays can be exchanged with numpy in the `.npy` and `.npz` formats,
+//!   see the [`npy`](npy/index.html) module.
 //!
 //! ## Crate Status
 //!
@@ -47,6 +51,7 @@
 //!   + The higher order functions like ``.map()``, ``.map_inplace()`` and
 //!     ``.zip_mut_with()`` are the most efficient ways to
 //!     perform single traversal and lock step traversal respectively.
+//!   + ``Zip`` generalizes lock step traversal to up to six arrays at once.
 //!   + ``.iter()`` is efficient for c-contiguous arrays.
 //!   + Can use BLAS in some operations (`dot` and `mat_mul`).
 //!
@@ -58,10 +63,21 @@
 //! - `rustc-serialize`
 //!   - Optional, compatible with Rust stable
 //!   - Enables serialization support
+//! - `serde`
+//!   - Optional, compatible with Rust stable
+//!   - Enables serialization support for serde 1.0
 //! - `blas`
 //!   - Optional and experimental, compatible with Rust stable
 //!   - Enable transparent BLAS support for matrix multiplication. Pluggable
 //!     backend via `blas-sys`.
+//! - `rayon`
+//!   - Optional, compatible with Rust stable
+//!   - Enables parallel iterators and parallelized methods, see the
+//!     [`parallel`](parallel/index.html) module.
+//! - `memmap`
+//!   - Optional, compatible with Rust stable
+//!   - Enables arrays backed by memory-mapped files, see the
+//!     [`mmap`](mmap/index.html) module.
 //!
 
 #[cfg(feature = "serde")]
@@ -72,12 +88,19 @@ extern crate rustc_serialize as serialize;
 #[cfg(feature="blas")]
 extern crate blas_sys;
 
+#[cfg(feature = "rayon")]
+extern crate rayon;
+
+#[cfg(feature = "memmap")]
+extern crate memmap2;
+
 extern crate matrixmultiply;
 
 extern crate itertools;
 extern crate num as libnum;
 
 use std::rc::Rc;
+use std::sync::Arc;
 use std::slice::{self, Iter, IterMut};
 use std::marker::PhantomData;
 
@@ -86,6 +109,8 @@ use itertools::ZipSlices;
 pub use dimension::{
     Dimension,
     RemoveAxis,
+    InsertAxis,
+    DimMax,
     Axis,
 };
 
@@ -93,6 +118,7 @@ pub use dimension::NdIndex;
 pub use indexes::Indexes;
 pub use error::{ShapeError, ErrorKind};
 pub use si::{Si, S};
+pub use shape_builder::{ShapeBuilder, Order};
 
 use iterators::Baseiter;
 pub use iterators::{
@@ -106,15 +132,31 @@ pub use iterators::{
 
 pub use arraytraits::AsArray;
 pub use linalg_traits::{LinalgScalar, NdFloat};
-pub use stacking::stack;
+pub use stacking::{stack, concatenate, stack_new_axis, split, array_split, tile, repeat};
+pub use zip::{Zip, NdProducer, IntoNdProducer, FoldWhile, Indices, indices};
+pub use lanes::{
+    Lanes,
+    LanesMut,
+    LanesIter,
+    LanesIterMut,
+};
+pub use windows::{
+    Windows,
+    ExactChunks,
+    ExactChunksMut,
+    WindowsIter,
+    ExactChunksIterMut,
+};
 
 mod arraytraits;
 #[cfg(feature = "serde")]
 mod arrayserialize;
 mod arrayformat;
+pub use arrayformat::{FormatOptions, FormatWith};
 mod data_traits;
 
 pub use data_traits::{
+    RawData,
     Data,
     DataMut,
     DataOwned,
@@ -129,18 +171,23 @@ pub use free_functions::*;
 
 mod indexes;
 mod iterators;
+mod lanes;
 mod linalg_traits;
 mod linspace;
 mod numeric_util;
 mod si;
 mod error;
 mod stacking;
+mod windows;
+mod zip;
 
 /// Implementation's prelude. Common types used everywhere.
 mod imp_prelude {
     pub use prelude::*;
     pub use {
         RemoveAxis,
+        InsertAxis,
+        RawData,
         Data,
         DataMut,
         DataOwned,
@@ -160,6 +207,19 @@ pub type Ix = usize;
 /// Array index type (signed)
 pub type Ixs = isize;
 
+/// Axis length that asks for the length of the axis to be inferred from
+/// the number of elements, when reshaping with
+/// [`.into_shape_with_order()`](struct.ArrayBase.html#method.into_shape_with_order)
+/// or [`.to_shape()`](struct.ArrayBase.html#method.to_shape).
+///
+/// ```
+/// use ndarray::{arr1, INFER};
+///
+/// let a = arr1(&[1, 2, 3, 4, 5, 6]);
+/// assert_eq!(a.to_shape((INFER, 2)).unwrap().shape(), &[3, 2]);
+/// ```
+pub const INFER: Ix = !0;
+
 /// An *N*-dimensional array.
 ///
 /// The array is a general container of elements. It cannot grow or shrink, but
@@ -187,6 +247,7 @@ pub type Ixs = isize;
 /// + [Broadcasting](#broadcasting)
 /// + [Methods](#methods)
 /// + [Methods for Array Views](#methods-for-array-views)
+/// + [Methods for Raw Array Views](#methods-for-raw-array-views)
 ///
 /// ## `OwnedArray` and `RcArray`
 ///
@@ -198,6 +259,10 @@ pub type Ixs = isize;
 /// [`view_mut()`](#method.view_mut) or [`get_mut()`](#method.get_mut),
 /// will break sharing and require a clone of the data (if it is not uniquely held).
 ///
+/// [`ArcArray`](type.ArcArray.html) works the same way, with atomic reference
+/// counting so that it can be sent to other threads; convert to it with
+/// [`into_shared_sync()`](#method.into_shared_sync).
+///
 /// Note that all `ArrayBase` variants can change their view (slicing) of the
 /// data freely, even when their data can’t be mutated.
 ///
@@ -355,8 +420,12 @@ pub type Ixs = isize;
 /// `+`, `-`, `*`, `/` and so on).
 ///
 /// - `&A @ &A` which produces a new `OwnedArray`
-/// - `B @ A` which consumes `B`, updates it with the result, and returns it
-/// - `B @ &A` which consumes `B`, updates it with the result, and returns it
+/// - `B @ A` which consumes `B` and returns the result, reusing the
+///   elements of `B` if it has the shape of the result
+/// - `B @ &A` which consumes `B` and returns the result, reusing the
+///   elements of `B` if it has the shape of the result
+/// - `C @ &A` which consumes `C`, updates it with the result, and returns
+///   it; `A` must broadcast to the shape of `C` if `C` is an `ArrayViewMut`
 /// - `C @= &A` which performs an arithmetic operation in place
 ///
 /// The trait [`ScalarOperand`](trait.ScalarOperand.html) marks types that can be used in arithmetic
@@ -391,8 +460,31 @@ pub type Ixs = isize;
 /// );
 /// ```
 ///
+/// In `&A @ &A`, `B @ A` and `B @ &A`, *both* arrays are broadcast to
+/// their common shape, and the result has that shape and the dimension
+/// type given by [`DimMax`](trait.DimMax.html). If `B` is smaller than the
+/// result, its elements can not be reused, and the result is a new
+/// allocation of the same storage kind as `B`. An `ArrayViewMut` can not be
+/// reallocated, so in `C @ &A` and `C @= &A` only `A` is broadcast, to the
+/// shape of `C`. The methods `.try_add()`, `.try_sub()` etc. return an
+/// error instead of panicking if the shapes can not be broadcast together.
+///
+/// ```
+/// use ndarray::arr2;
+///
+/// let col = arr2(&[[1.],
+///                  [2.]]);
+/// let row = arr2(&[[10., 20., 30.]]);
+/// let sum = arr2(&[[11., 21., 31.],
+///                  [12., 22., 32.]]);
+///
+/// assert!(&col + &row == sum);
+/// // `col` is consumed and the result is a new 2 × 3 array
+/// assert!(col + &row == sum);
+/// ```
+///
 pub struct ArrayBase<S, D>
-    where S: Data
+    where S: RawData
 {
     /// Rc data when used as view, Uniquely held data when being mutated
     data: S,
@@ -409,6 +501,14 @@ pub struct ArrayBase<S, D>
 /// can act as both an owner as the data as well as a lightweight view.
 pub type RcArray<A, D> = ArrayBase<Rc<Vec<A>>, D>;
 
+/// Array where the data is atomically reference counted and copy on write,
+/// like [`RcArray`](type.RcArray.html), but it can be sent to and shared
+/// between threads (if the element type allows).
+///
+/// Mutating the elements of an `ArcArray` requires `A: Clone`, since the
+/// data is copied if it is shared.
+pub type ArcArray<A, D> = ArrayBase<Arc<Vec<A>>, D>;
+
 /// Array where the data is owned uniquely.
 pub type OwnedArray<A, D> = ArrayBase<Vec<A>, D>;
 
@@ -435,6 +535,48 @@ pub type ArrayView<'a, A, D> = ArrayBase<ViewRepr<&'a A>, D>;
 /// [ab]: struct.ArrayBase.html
 pub type ArrayViewMut<'a, A, D> = ArrayBase<ViewRepr<&'a mut A>, D>;
 
+/// A read-only array view without a lifetime.
+///
+/// A raw view holds a pointer, a shape and strides, which can be any
+/// values, including negative and zero strides. Unlike an
+/// [`ArrayView`](type.ArrayView.html), it is not guaranteed to point to
+/// valid elements, so the elements can only be accessed by converting it
+/// into a view with the unsafe method
+/// [`.deref_into_view()`](struct.ArrayBase.html#method.deref_into_view).
+///
+/// See also [**Methods for Raw Array Views**](struct.ArrayBase.html#methods-for-raw-array-views).
+pub type RawArrayView<A, D> = ArrayBase<RawViewRepr<*const A>, D>;
+
+/// A read-write array view without a lifetime.
+///
+/// Like [`RawArrayView`](type.RawArrayView.html), for mutable elements.
+///
+/// See also [**Methods for Raw Array Views**](struct.ArrayBase.html#methods-for-raw-array-views).
+pub type RawArrayViewMut<A, D> = ArrayBase<RawViewRepr<*mut A>, D>;
+
+/// An array that is either borrowed as a read-only view, or owns its
+/// elements, like `std::borrow::Cow`.
+///
+/// It is returned by methods that avoid copying when they can, like
+/// [`.to_shape()`](struct.ArrayBase.html#method.to_shape).
+///
+/// See also [**Methods for Copy-on-write Arrays**](struct.ArrayBase.html#methods-for-copy-on-write-arrays).
+pub type CowArray<'a, A, D> = ArrayBase<CowRepr<'a, A>, D>;
+
+/// Raw array view’s representation.
+#[derive(Copy, Clone)]
+// This is just a marker type, to carry the pointer type.
+pub struct RawViewRepr<A> {
+    ptr: PhantomData<A>,
+}
+
+impl<A> RawViewRepr<A> {
+    #[inline(always)]
+    fn new() -> Self {
+        RawViewRepr { ptr: PhantomData }
+    }
+}
+
 /// Array view’s representation.
 #[derive(Copy, Clone)]
 // This is just a marker type, to carry the lifetime parameter.
@@ -449,6 +591,39 @@ impl<A> ViewRepr<A> {
     }
 }
 
+/// Copy-on-write array’s representation.
+pub enum CowRepr<'a, A: 'a> {
+    /// Borrowed elements.
+    View(ViewRepr<&'a A>),
+    /// Owned elements.
+    Owned(Vec<A>),
+}
+
+/// A contiguous array shape in C (row major) or F (column major) memory
+/// order.
+///
+/// Created from a dimension with the [`ShapeBuilder`](trait.ShapeBuilder.html)
+/// methods, for example `(3, 4).f()`.
+#[derive(Copy, Clone, Debug)]
+pub struct Shape<D> {
+    dim: D,
+    is_c: bool,
+}
+
+/// An array shape with C or F memory order, or with custom strides.
+///
+/// Created from a dimension or a [`Shape`](struct.Shape.html), or with
+/// [`ShapeBuilder::strides`](trait.ShapeBuilder.html#tymethod.strides), for
+/// example `(3, 4).strides((1, 3))`.
+#[derive(Copy, Clone, Debug)]
+pub struct StrideShape<D> {
+    dim: D,
+    strides: D,
+    custom: bool,
+}
+
+mod shape_builder;
+
 mod impl_clone;
 
 mod impl_constructors;
@@ -515,6 +690,14 @@ mod numeric;
 
 pub mod linalg;
 
+pub mod npy;
+
+#[cfg(feature = "rayon")]
+pub mod parallel;
+
+#[cfg(feature = "memmap")]
+pub mod mmap;
+
 mod impl_ops_inplace;
 
 mod impl_ops;
@@ -523,6 +706,11 @@ pub use impl_ops::ScalarOperand;
 // Array view methods
 mod impl_views;
 
+mod impl_cow;
+
+// Raw array view methods
+mod impl_raw_views;
+
 /// Private array view methods
 impl<'a, A, D> ArrayBase<ViewRepr<&'a A>, D>
     where D: Dimension,
@@ -0,0 +1,371 @@
+// Copyright 2016 bluss and ndarray developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Matrix decompositions (LU, QR, Cholesky) in pure Rust.
+
+use imp_prelude::*;
+use error::{from_kind, ErrorKind};
+use {NdFloat, Si, S, Ixs};
+use super::LinalgError;
+
+/// The LU decomposition with partial pivoting of a square matrix *A*:
+/// *P A = L U*.
+///
+/// *P* is a permutation matrix, *L* is lower triangular with unit diagonal
+/// and *U* is upper triangular.
+///
+/// Create it with [`.lu()`](../struct.ArrayBase.html#method.lu).
+#[derive(Clone, Debug)]
+pub struct LU<A> {
+    /// L (below the diagonal) and U (on and above the diagonal)
+    lu: OwnedArray<A, (Ix, Ix)>,
+    /// row `i` of *P A* is row `perm[i]` of *A*
+    perm: Vec<Ix>,
+    /// `true` if the number of row exchanges is odd
+    odd: bool,
+    singular: bool,
+}
+
+impl<A: NdFloat> LU<A> {
+    /// Return the lower triangular factor *L*, with unit diagonal.
+    pub fn l(&self) -> OwnedArray<A, (Ix, Ix)> {
+        let n = self.lu.rows();
+        let mut l = OwnedArray::zeros((n, n));
+        for ((i, j), elt) in l.indexed_iter_mut() {
+            if i > j {
+                *elt = self.lu[(i, j)];
+            } else if i == j {
+                *elt = A::one();
+            }
+        }
+        l
+    }
+
+    /// Return the upper triangular factor *U*.
+    pub fn u(&self) -> OwnedArray<A, (Ix, Ix)> {
+        let n = self.lu.rows();
+        let mut u = OwnedArray::zeros((n, n));
+        for ((i, j), elt) in u.indexed_iter_mut() {
+            if i <= j {
+                *elt = self.lu[(i, j)];
+            }
+        }
+        u
+    }
+
+    /// Return the row permutation: row `i` of *P A* is row
+    /// `.permutation()[i]` of *A*.
+    pub fn permutation(&self) -> &[Ix] {
+        &self.perm
+    }
+
+    /// Return the permutation matrix *P*.
+    pub fn p(&self) -> OwnedArray<A, (Ix, Ix)> {
+        let n = self.perm.len();
+        let mut p = OwnedArray::zeros((n, n));
+        for (i, &j) in self.perm.iter().enumerate() {
+            p[(i, j)] = A::one();
+        }
+        p
+    }
+
+    /// Return `true` if a pivot was zero to working precision.
+    pub fn is_singular(&self) -> bool {
+        self.singular
+    }
+
+    /// Return the determinant of *A*.
+    pub fn det(&self) -> A {
+        let det = self.lu.diag().fold(A::one(), |acc, &x| acc * x);
+        if self.odd { -det } else { det }
+    }
+
+    /// Solve *A x = b* for *x*.
+    ///
+    /// **Errors** if the shape of `b` does not match, or if *A* is singular.
+    pub fn solve<S2>(&self, b: &ArrayBase<S2, Ix>) -> Result<OwnedArray<A, Ix>, LinalgError>
+        where S2: Data<Elem=A>,
+    {
+        if b.len() != self.perm.len() {
+            return Err(from_kind(ErrorKind::IncompatibleShape).into());
+        }
+        if self.singular {
+            return Err(LinalgError::Singular);
+        }
+        let mut x = OwnedArray::from_iter(self.perm.iter().map(|&i| b[i]));
+        self.solve_permuted(x.view_mut());
+        Ok(x)
+    }
+
+    /// Return the inverse of *A*.
+    ///
+    /// **Errors** if *A* is singular.
+    pub fn inv(&self) -> Result<OwnedArray<A, (Ix, Ix)>, LinalgError> {
+        if self.singular {
+            return Err(LinalgError::Singular);
+        }
+        let n = self.perm.len();
+        let mut inv = OwnedArray::zeros((n, n));
+        for (j, mut column) in inv.axis_iter_mut(Axis(1)).enumerate() {
+            // column j of the permuted identity matrix
+            for (i, &p) in self.perm.iter().enumerate() {
+                if p == j {
+                    column[i] = A::one();
+                }
+            }
+            self.solve_permuted(column);
+        }
+        Ok(inv)
+    }
+
+    /// Solve *L U x = y* in place, where `x` holds *y* on entry.
+    fn solve_permuted(&self, mut x: ArrayViewMut<A, Ix>) {
+        let n = x.len();
+        let lu = &self.lu;
+        // forward substitution with L
+        for i in 0..n {
+            let mut sum = x[i];
+            for j in 0..i {
+                sum -= lu[(i, j)] * x[j];
+            }
+            x[i] = sum;
+        }
+        // back substitution with U
+        for i in (0..n).rev() {
+            let mut sum = x[i];
+            for j in i + 1..n {
+                sum -= lu[(i, j)] * x[j];
+            }
+            x[i] = sum / lu[(i, i)];
+        }
+    }
+}
+
+/// # Matrix decompositions
+///
+/// These methods are implemented in Rust without external dependencies,
+/// for element types `f32` and `f64`.
+impl<A, S> ArrayBase<S, (Ix, Ix)>
+    where S: Data<Elem=A>,
+          A: NdFloat,
+{
+    /// Compute the LU decomposition with partial pivoting.
+    ///
+    /// A pivot that is zero to working precision (relative to the largest
+    /// element of the matrix) marks the decomposition as singular; it can
+    /// then still be used for `.det()`, but not to solve or invert.
+    ///
+    /// **Errors** if the matrix is not square.
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    ///
+    /// let a = arr2(&[[1f64, 2.],
+    ///                [3., 4.]]);
+    /// let lu = a.lu().unwrap();
+    /// assert!(lu.p().dot(&a).all_close(&lu.l().dot(&lu.u()), 1e-12));
+    /// assert!((lu.det() - -2.).abs() < 1e-12);
+    /// ```
+    pub fn lu(&self) -> Result<LU<A>, LinalgError> {
+        let n = try!(self.square_size());
+        let mut lu = self.to_owned();
+        let mut perm: Vec<Ix> = (0..n).collect();
+        let mut odd = false;
+        let mut singular = false;
+        let max = lu.fold(A::zero(), |acc, x| acc.max(x.abs()));
+        let tolerance = max * A::epsilon() * A::from(n).unwrap();
+        for k in 0..n {
+            // find the pivot row
+            let mut p = k;
+            for i in k + 1..n {
+                if lu[(i, k)].abs() > lu[(p, k)].abs() {
+                    p = i;
+                }
+            }
+            if p != k {
+                for j in 0..n {
+                    lu.swap((k, j), (p, j));
+                }
+                perm.swap(k, p);
+                odd = !odd;
+            }
+            let pivot = lu[(k, k)];
+            if !(pivot.abs() > tolerance) {
+                singular = true;
+                continue;
+            }
+            for i in k + 1..n {
+                let factor = lu[(i, k)] / pivot;
+                lu[(i, k)] = factor;
+                for j in k + 1..n {
+                    let x = lu[(k, j)];
+                    lu[(i, j)] -= factor * x;
+                }
+            }
+        }
+        Ok(LU {
+            lu: lu,
+            perm: perm,
+            odd: odd,
+            singular: singular,
+        })
+    }
+
+    /// Compute the QR decomposition *A = Q R*, using Householder reflections.
+    ///
+    /// If the matrix is *M* × *N* and *K* = min(*M*, *N*), return *Q*
+    /// with shape *M* × *K* and orthonormal columns, and *R* with shape
+    /// *K* × *N*, which is upper triangular.
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    ///
+    /// let a = arr2(&[[ 2., 3.],
+    ///                [-2., 1.],
+    ///                [ 1., 5.]]);
+    /// let (q, r) = a.qr();
+    /// assert_eq!(q.shape(), &[3, 2]);
+    /// assert_eq!(r[(1, 0)], 0.);
+    /// assert!(q.dot(&r).all_close(&a, 1e-12));
+    /// ```
+    pub fn qr(&self) -> (OwnedArray<A, (Ix, Ix)>, OwnedArray<A, (Ix, Ix)>) {
+        let (m, n) = self.dim();
+        let k = if m < n { m } else { n };
+        let two = A::one() + A::one();
+        let mut r = self.to_owned();
+        let mut q = OwnedArray::<A, _>::eye(m);
+        for j in 0..k {
+            // the householder vector v that reflects r[j.., j] onto the axis
+            let mut v = r.column(j).slice(&[Si(j as Ixs, None, 1)]).to_owned();
+            let norm = v.dot(&v).sqrt();
+            if norm == A::zero() {
+                continue;
+            }
+            let alpha = if v[0] > A::zero() { -norm } else { norm };
+            v[0] -= alpha;
+            let vnorm = v.dot(&v).sqrt();
+            if vnorm == A::zero() {
+                continue;
+            }
+            v.mapv_inplace(|x| x / vnorm);
+
+            // r[j.., j..] -= 2 v (vᵀ r[j.., j..])
+            for mut column in r.slice_mut(&[Si(j as Ixs, None, 1), Si(j as Ixs, None, 1)])
+                               .axis_iter_mut(Axis(1)) {
+                let d = two * v.dot(&column);
+                column.zip_mut_with(&v, |x, &y| *x -= d * y);
+            }
+            // q[.., j..] -= 2 (q[.., j..] v) vᵀ
+            for mut row in q.slice_mut(&[S, Si(j as Ixs, None, 1)])
+                            .axis_iter_mut(Axis(0)) {
+                let d = two * v.dot(&row);
+                row.zip_mut_with(&v, |x, &y| *x -= d * y);
+            }
+            for i in j + 1..m {
+                r[(i, j)] = A::zero();
+            }
+        }
+        let q = q.slice(&[S, Si(0, Some(k as Ixs), 1)]).to_owned();
+        let r = r.slice(&[Si(0, Some(k as Ixs), 1), S]).to_owned();
+        (q, r)
+    }
+
+    /// Compute the Cholesky decomposition *A = L Lᵀ* of a symmetric positive
+    /// definite matrix, and return the lower triangular factor *L*.
+    ///
+    /// Only the lower triangle of the matrix is read.
+    ///
+    /// **Errors** if the matrix is not square or not positive definite.
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    ///
+    /// let a = arr2(&[[4., 2.],
+    ///                [2., 10.]]);
+    /// let l = a.cholesky().unwrap();
+    /// assert_eq!(l, arr2(&[[2., 0.],
+    ///                      [1., 3.]]));
+    /// assert!(arr2(&[[1., 2.], [2., 1.]]).cholesky().is_err());
+    /// ```
+    pub fn cholesky(&self) -> Result<OwnedArray<A, (Ix, Ix)>, LinalgError> {
+        let n = try!(self.square_size());
+        let mut l = OwnedArray::<A, _>::zeros((n, n));
+        for j in 0..n {
+            let mut d = self[(j, j)];
+            for k in 0..j {
+                d -= l[(j, k)] * l[(j, k)];
+            }
+            if !(d > A::zero()) {
+                return Err(LinalgError::NotPositiveDefinite);
+            }
+            let ljj = d.sqrt();
+            l[(j, j)] = ljj;
+            for i in j + 1..n {
+                let mut sum = self[(i, j)];
+                for k in 0..j {
+                    sum -= l[(i, k)] * l[(j, k)];
+                }
+                l[(i, j)] = sum / ljj;
+            }
+        }
+        Ok(l)
+    }
+
+    /// Solve the linear system *A x = b* for *x*, using the LU
+    /// decomposition.
+    ///
+    /// **Errors** if the matrix is not square, if the shape of `b` does not
+    /// match, or if the matrix is singular.
+    ///
+    /// ```
+    /// use ndarray::{arr1, arr2};
+    ///
+    /// let a = arr2(&[[3., 1.],
+    ///                [1., 2.]]);
+    /// let x = a.solve(&arr1(&[9., 8.])).unwrap();
+    /// assert!(x.all_close(&arr1(&[2., 3.]), 1e-12));
+    /// ```
+    pub fn solve<S2>(&self, b: &ArrayBase<S2, Ix>) -> Result<OwnedArray<A, Ix>, LinalgError>
+        where S2: Data<Elem=A>,
+    {
+        try!(self.lu()).solve(b)
+    }
+
+    /// Return the inverse of the matrix, using the LU decomposition.
+    ///
+    /// **Errors** if the matrix is not square, or if it is singular.
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    ///
+    /// let a = arr2(&[[4., 7.],
+    ///                [2., 6.]]);
+    /// let inv = a.inv().unwrap();
+    /// assert!(inv.all_close(&arr2(&[[ 0.6, -0.7],
+    ///                               [-0.2,  0.4]]), 1e-12));
+    /// assert!(arr2(&[[1., 2.], [2., 4.]]).inv().is_err());
+    /// ```
+    pub fn inv(&self) -> Result<OwnedArray<A, (Ix, Ix)>, LinalgError> {
+        try!(self.lu()).inv()
+    }
+
+    /// Return the determinant of the matrix, using the LU decomposition.
+    ///
+    /// **Errors** if the matrix is not square.
+    pub fn det(&self) -> Result<A, LinalgError> {
+        Ok(try!(self.lu()).det())
+    }
+
+    fn square_size(&self) -> Result<Ix, LinalgError> {
+        let (rows, cols) = self.dim();
+        if rows != cols {
+            return Err(LinalgError::NotSquare { rows: rows, cols: cols });
+        }
+        Ok(rows)
+    }
+}
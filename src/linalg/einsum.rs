@@ -0,0 +1,320 @@
+// Copyright 2016 bluss and ndarray developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use imp_prelude::*;
+use error::{from_kind, in_operation, with_detail, ErrorKind, ShapeError};
+use LinalgScalar;
+use super::general_mat_mul;
+use super::impl_linalg::permuted_into_shape;
+
+/// Evaluate the Einstein summation convention on the operands.
+///
+/// `subscripts` labels the axes of each operand with one letter per axis,
+/// with the terms separated by commas, for example `"ij,jk->ik"` for matrix
+/// multiplication or `"bij,bjk->bik"` for batched matrix multiplication.
+/// Axes with the same label have their elements multiplied together, and
+/// labels missing from the output (after `->`) are summed over. A label
+/// repeated within one term takes the diagonal, as in `"ii->i"`.
+///
+/// Without `->`, the output has the labels that appear exactly once, in
+/// alphabetical order: `"ij,jk"` is the same as `"ij,jk->ik"`.
+///
+/// The operands are contracted in pairs from left to right. Each pair is
+/// rearranged into a (batched) matrix product, computed with
+/// [`general_mat_mul`](fn.general_mat_mul.html).
+///
+/// **Errors** if the subscripts are malformed, if the number of terms or
+/// the number of labels in a term does not match the operands, or if axes
+/// with the same label have different lengths. The error’s
+/// [`detail()`](../struct.ShapeError.html#method.detail) names the term at
+/// fault.
+///
+/// ```
+/// use ndarray::{arr1, arr2, Ix};
+/// use ndarray::linalg::einsum;
+///
+/// let a = arr2(&[[1., 2.],
+///                [3., 4.]]).into_shape(vec![2, 2]).unwrap();
+/// let b = arr2(&[[5., 6.],
+///                [7., 8.]]).into_shape(vec![2, 2]).unwrap();
+///
+/// let ab = einsum("ij,jk->ik", &[a.view(), b.view()]).unwrap();
+/// assert_eq!(ab.into_shape((2, 2)).unwrap(), arr2(&[[19., 22.],
+///                                                   [43., 50.]]));
+///
+/// let trace = einsum("ii", &[a.view()]).unwrap();
+/// assert_eq!(trace.shape(), &[] as &[Ix]);
+/// assert_eq!(trace[&[][..]], 5.);
+///
+/// let col_sums = einsum("ij->j", &[a.view()]).unwrap();
+/// assert_eq!(col_sums.into_shape(2).unwrap(), arr1(&[4., 6.]));
+/// ```
+pub fn einsum<A>(subscripts: &str, operands: &[ArrayView<A, Vec<Ix>>])
+    -> Result<OwnedArray<A, Vec<Ix>>, ShapeError>
+    where A: LinalgScalar,
+{
+    let (inputs, output) = try!(parse_subscripts(subscripts));
+    if inputs.len() != operands.len() || inputs.is_empty() {
+        return Err(einsum_error(ErrorKind::IncompatibleShape,
+                                format!("{} terms for {} operands",
+                                        inputs.len(), operands.len())));
+    }
+    // check the number of labels and the lengths of all axes with each label
+    let mut lengths: Vec<(u8, Ix)> = Vec::new();
+    for (i, (labels, op)) in inputs.iter().zip(operands).enumerate() {
+        if labels.len() != op.ndim() {
+            return Err(einsum_error(ErrorKind::IncompatibleShape,
+                                    format!("term {} `{}` has {} labels for {} axes",
+                                            i, show(labels), labels.len(), op.ndim())));
+        }
+        for (&label, &len) in labels.iter().zip(op.shape()) {
+            match lengths.iter().find(|&&(l, _)| l == label) {
+                Some(&(_, len2)) if len2 != len => {
+                    return Err(einsum_error(ErrorKind::IncompatibleShape,
+                                            format!("label `{}` in term {} has length {}, \
+                                                     but {} elsewhere",
+                                                    label as char, i, len, len2)));
+                }
+                Some(_) => {}
+                None => lengths.push((label, len)),
+            }
+        }
+    }
+    let output = match output {
+        Some(output) => {
+            for (i, label) in output.iter().enumerate() {
+                if output[..i].contains(label) || !inputs.iter().any(|t| t.contains(label)) {
+                    return Err(einsum_error(ErrorKind::IncompatibleShape,
+                                            format!("output label `{}` is repeated or \
+                                                     missing from the inputs",
+                                                    *label as char)));
+                }
+            }
+            output
+        }
+        None => implicit_output(&inputs),
+    };
+
+    let mut operands = operands.iter().zip(inputs).map(|(op, labels)| diagonal(op.view(), labels));
+    let mut acc = operands.next().unwrap();
+    let mut rest: Vec<Operand<A>> = operands.collect();
+    rest.reverse();
+    while let Some(next) = rest.pop() {
+        // labels needed after this pair is contracted
+        let mut keep = output.clone();
+        for op in &rest {
+            keep.extend(op.labels.iter().cloned());
+        }
+        acc = contract_pair(acc, next, &keep);
+    }
+    let acc = sum_labels(acc, &output);
+    let order: Vec<Ix> = output.iter()
+                               .map(|l| acc.labels.iter().position(|x| x == l).unwrap())
+                               .collect();
+    let view = acc.array.view();
+    let shape: Vec<Ix> = order.iter().map(|&i| view.shape()[i]).collect();
+    let result = match permuted_into_shape(&view, &order, shape) {
+        Ok(view) => view.to_owned(),
+        Err(owned) => owned,
+    };
+    Ok(result)
+}
+
+/// An operand with a label for each axis; the labels are distinct.
+struct Operand<'a, A: 'a> {
+    labels: Vec<u8>,
+    array: CowView<'a, A>,
+}
+
+enum CowView<'a, A: 'a> {
+    View(ArrayView<'a, A, Vec<Ix>>),
+    Owned(OwnedArray<A, Vec<Ix>>),
+}
+
+impl<'a, A> CowView<'a, A> {
+    fn view(&self) -> ArrayView<A, Vec<Ix>> {
+        match *self {
+            CowView::View(ref v) => v.view(),
+            CowView::Owned(ref a) => a.view(),
+        }
+    }
+}
+
+/// Parse the terms of `subscripts`, and the output term if it is explicit.
+fn parse_subscripts(subscripts: &str) -> Result<(Vec<Vec<u8>>, Option<Vec<u8>>), ShapeError> {
+    let subscripts: Vec<u8> = subscripts.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    let mut parts = subscripts.split(|&b| b == b'>');
+    let lhs = parts.next().unwrap();
+    let (lhs, output) = match parts.next() {
+        None => (lhs, None),
+        Some(output) => {
+            if parts.next().is_some() || lhs.last() != Some(&b'-') {
+                return Err(einsum_error(ErrorKind::Unsupported,
+                                        "expected a single `->` before the output".to_string()));
+            }
+            (&lhs[..lhs.len() - 1], Some(output.to_vec()))
+        }
+    };
+    let inputs: Vec<Vec<u8>> = lhs.split(|&b| b == b',').map(|t| t.to_vec()).collect();
+    let valid = |term: &Vec<u8>| term.iter().all(|b| b.is_ascii_alphabetic());
+    if let Some(i) = inputs.iter().position(|t| !valid(t)) {
+        return Err(einsum_error(ErrorKind::Unsupported,
+                                format!("term {} `{}` has labels that are not letters",
+                                        i, show(&inputs[i]))));
+    }
+    if let Some(ref output) = output {
+        if !valid(output) {
+            return Err(einsum_error(ErrorKind::Unsupported,
+                                    format!("output `{}` has labels that are not letters",
+                                            show(output))));
+        }
+    }
+    Ok((inputs, output))
+}
+
+fn einsum_error(kind: ErrorKind, detail: String) -> ShapeError {
+    with_detail(in_operation(from_kind(kind), "einsum"), detail)
+}
+
+fn show(labels: &[u8]) -> String {
+    String::from_utf8_lossy(labels).into_owned()
+}
+
+/// Return the labels that appear exactly once in `inputs`, sorted.
+fn implicit_output(inputs: &[Vec<u8>]) -> Vec<u8> {
+    let mut output: Vec<u8> = Vec::new();
+    for term in inputs {
+        for label in term {
+            let count = inputs.iter().flat_map(|t| t).filter(|&l| l == label).count();
+            if count == 1 {
+                output.push(*label);
+            }
+        }
+    }
+    output.sort();
+    output
+}
+
+/// Take the diagonal of the axes of `a` with repeated labels.
+fn diagonal<A>(a: ArrayView<A, Vec<Ix>>, labels: Vec<u8>) -> Operand<A> {
+    let mut unique = Vec::new();
+    let mut dim = Vec::new();
+    let mut strides = Vec::new();
+    for (i, &label) in labels.iter().enumerate() {
+        match unique.iter().position(|&l| l == label) {
+            Some(j) => {
+                // the lengths are equal; step along both axes at once
+                strides[j] = (strides[j] as Ixs + a.strides()[i]) as Ix;
+            }
+            None => {
+                unique.push(label);
+                dim.push(a.shape()[i]);
+                strides.push(a.strides()[i] as Ix);
+            }
+        }
+    }
+    let array = if unique.len() == labels.len() {
+        a
+    } else {
+        unsafe {
+            ArrayView::new_(a.ptr, dim, strides)
+        }
+    };
+    Operand { labels: unique, array: CowView::View(array) }
+}
+
+/// Sum over the axes of `a` whose labels are not in `keep`.
+fn sum_labels<'a, A>(a: Operand<'a, A>, keep: &[u8]) -> Operand<'a, A>
+    where A: LinalgScalar,
+{
+    if a.labels.iter().all(|l| keep.contains(l)) {
+        return a;
+    }
+    let mut labels = a.labels;
+    let mut array = a.array.view().to_owned();
+    let mut i = labels.len();
+    while i > 0 {
+        i -= 1;
+        if !keep.contains(&labels[i]) {
+            labels.remove(i);
+            array = if array.shape()[i] == 0 {
+                OwnedArray::zeros(array.dim().remove_axis(Axis(i)))
+            } else {
+                array.sum(Axis(i))
+            };
+        }
+    }
+    Operand { labels: labels, array: CowView::Owned(array) }
+}
+
+/// Contract the operands `x` and `y` into one, keeping the axes with labels
+/// in `keep`.
+fn contract_pair<'a, A>(x: Operand<'a, A>, y: Operand<'a, A>, keep: &[u8]) -> Operand<'a, A>
+    where A: LinalgScalar,
+{
+    // first sum over the labels that only one operand has and that are
+    // not needed later
+    let mut x_keep = keep.to_vec();
+    x_keep.extend(y.labels.iter().cloned());
+    let x = sum_labels(x, &x_keep);
+    let mut y_keep = keep.to_vec();
+    y_keep.extend(x.labels.iter().cloned());
+    let y = sum_labels(y, &y_keep);
+
+    let (xv, yv) = (x.array.view(), y.array.view());
+    let len = |label: &u8| {
+        match x.labels.iter().position(|l| l == label) {
+            Some(i) => xv.shape()[i],
+            None => yv.shape()[y.labels.iter().position(|l| l == label).unwrap()],
+        }
+    };
+    let batch: Vec<u8> = x.labels.iter().filter(|l| y.labels.contains(l) && keep.contains(l))
+                                 .cloned().collect();
+    let summed: Vec<u8> = x.labels.iter().filter(|l| y.labels.contains(l) && !keep.contains(l))
+                                  .cloned().collect();
+    let x_free: Vec<u8> = x.labels.iter().filter(|l| !y.labels.contains(l)).cloned().collect();
+    let y_free: Vec<u8> = y.labels.iter().filter(|l| !x.labels.contains(l)).cloned().collect();
+    let size = |labels: &[u8]| labels.iter().fold(1, |acc, l| acc * len(l));
+    let (b, m, k, n) = (size(&batch), size(&x_free), size(&summed), size(&y_free));
+
+    // x as b × m × k and y as b × k × n
+    let position = |labels: &[u8], l: &u8| labels.iter().position(|x| x == l).unwrap();
+    let x_order: Vec<Ix> = batch.iter().chain(&x_free).chain(&summed)
+                                .map(|l| position(&x.labels, l)).collect();
+    let y_order: Vec<Ix> = batch.iter().chain(&summed).chain(&y_free)
+                                .map(|l| position(&y.labels, l)).collect();
+    let x_owned;
+    let y_owned;
+    let x3 = match permuted_into_shape(&xv, &x_order, (b, m, k)) {
+        Ok(view) => view,
+        Err(owned) => {
+            x_owned = owned;
+            x_owned.view()
+        }
+    };
+    let y3 = match permuted_into_shape(&yv, &y_order, (b, k, n)) {
+        Ok(view) => view,
+        Err(owned) => {
+            y_owned = owned;
+            y_owned.view()
+        }
+    };
+    let mut c = OwnedArray::<A, _>::zeros((b, m, n));
+    if m * n != 0 {
+        for i in 0..b {
+            general_mat_mul(A::one(), &x3.subview(Axis(0), i), &y3.subview(Axis(0), i),
+                            A::zero(), &mut c.subview_mut(Axis(0), i));
+        }
+    }
+    let labels: Vec<u8> = batch.iter().chain(&x_free).chain(&y_free).cloned().collect();
+    let shape: Vec<Ix> = labels.iter().map(|l| len(l)).collect();
+    Operand {
+        labels: labels,
+        array: CowView::Owned(c.into_shape(shape).unwrap()),
+    }
+}
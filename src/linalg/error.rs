@@ -0,0 +1,73 @@
+// Copyright 2016 bluss and ndarray developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+use std::fmt;
+use std::error::Error;
+
+use error::ShapeError;
+use Ix;
+
+/// An error from a matrix decomposition or a method built on one.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LinalgError {
+    /// the matrix must be square, but has the shape `rows` × `cols`
+    NotSquare {
+        /// number of rows
+        rows: Ix,
+        /// number of columns
+        cols: Ix,
+    },
+    /// the matrix is singular (to working precision)
+    Singular,
+    /// the matrix is not positive definite
+    NotPositiveDefinite,
+    /// the shapes of the operands are incompatible
+    Shape(ShapeError),
+}
+
+impl LinalgError {
+    fn message(&self) -> &'static str {
+        match *self {
+            LinalgError::NotSquare { .. } => "the matrix is not square",
+            LinalgError::Singular => "the matrix is singular",
+            LinalgError::NotPositiveDefinite => "the matrix is not positive definite",
+            LinalgError::Shape(_) => "incompatible shapes",
+        }
+    }
+}
+
+impl From<ShapeError> for LinalgError {
+    fn from(error: ShapeError) -> Self {
+        LinalgError::Shape(error)
+    }
+}
+
+impl Error for LinalgError {
+    fn description(&self) -> &str {
+        self.message()
+    }
+
+    fn source(&self) -> Option<&(Error + 'static)> {
+        match *self {
+            LinalgError::Shape(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LinalgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LinalgError::NotSquare { rows, cols } => {
+                write!(f, "LinalgError: {} (shape {} × {})",
+                       self.message(), rows, cols)
+            }
+            LinalgError::Shape(ref e) => write!(f, "LinalgError: {}", e),
+            _ => write!(f, "LinalgError: {}", self.message()),
+        }
+    }
+}
@@ -7,16 +7,21 @@
 // except according to those terms.
 
 use libnum::Zero;
-use itertools::free::enumerate;
 
 use imp_prelude::*;
 use numeric_util;
 
 use {
     LinalgScalar,
+    DimMax,
+    Indexes,
+    Ixs,
 };
+use dimension::co_broadcast;
+use error::{self, ErrorKind, ShapeError};
 
 use std::any::{Any, TypeId};
+use std::mem::MaybeUninit;
 
 #[cfg(feature="blas")]
 use std::cmp;
@@ -57,6 +62,30 @@ impl<A, S> ArrayBase<S, Ix>
         self.dot_impl(rhs)
     }
 
+    /// Compute the dot product of one-dimensional arrays, like
+    /// [`.dot()`](#method.dot).
+    ///
+    /// **Errors** with `IncompatibleShape` if the arrays are not of the
+    /// same length.
+    ///
+    /// ```
+    /// use ndarray::arr1;
+    ///
+    /// let a = arr1(&[1., 2.]);
+    /// assert_eq!(a.try_dot(&arr1(&[3., 4.])), Ok(11.));
+    /// assert!(a.try_dot(&arr1(&[3., 4., 5.])).is_err());
+    /// ```
+    pub fn try_dot<S2>(&self, rhs: &ArrayBase<S2, Ix>) -> Result<A, ShapeError>
+        where S2: Data<Elem=A>,
+              A: LinalgScalar,
+    {
+        if self.len() != rhs.len() {
+            return Err(error::in_operation(error::incompatible_shapes(&self.dim, &rhs.dim),
+                                           "dot"));
+        }
+        Ok(self.dot_impl(rhs))
+    }
+
     fn dot_generic<S2>(&self, rhs: &ArrayBase<S2, Ix>) -> A
         where S2: Data<Elem=A>,
               A: LinalgScalar,
@@ -156,6 +185,15 @@ pub trait Dot<Rhs> {
     fn dot(&self, rhs: &Rhs) -> Self::Output;
 }
 
+/// Matrix Multiplication that returns an error for incompatible shapes
+///
+/// For two-dimensional arrays, the try_dot method is like the dot method of
+/// [`Dot`](trait.Dot.html), but returns an error instead of panicking if the
+/// shapes are incompatible.
+pub trait TryDot<Rhs> : Dot<Rhs> {
+    fn try_dot(&self, rhs: &Rhs) -> Result<Self::Output, ShapeError>;
+}
+
 impl<A, S> ArrayBase<S, (Ix, Ix)>
     where S: Data<Elem=A>,
 {
@@ -189,6 +227,28 @@ impl<A, S> ArrayBase<S, (Ix, Ix)>
         Dot::dot(self, rhs)
     }
 
+    /// Perform matrix multiplication of rectangular arrays `self` and `rhs`,
+    /// like [`.dot()`](#method.dot-1).
+    ///
+    /// **Errors** with `IncompatibleShape` if the shapes are incompatible,
+    /// or with `RangeLimited` if the result is larger than is possible to
+    /// represent.
+    ///
+    /// ```
+    /// use ndarray::{arr1, arr2, ErrorKind};
+    ///
+    /// let a = arr2(&[[1., 2.],
+    ///                [0., 1.]]);
+    /// assert_eq!(a.try_dot(&arr1(&[1., 1.])).unwrap(), arr1(&[3., 1.]));
+    /// let err = a.try_dot(&arr2(&[[1., 2., 3.]])).unwrap_err();
+    /// assert_eq!(err.kind(), ErrorKind::IncompatibleShape);
+    /// ```
+    pub fn try_dot<Rhs>(&self, rhs: &Rhs) -> Result<<Self as Dot<Rhs>>::Output, ShapeError>
+        where Self: TryDot<Rhs>
+    {
+        TryDot::try_dot(self, rhs)
+    }
+
     #[cfg_attr(has_deprecated, deprecated(note="Use .dot() instead."))]
     pub fn mat_mul<S2>(&self, rhs: &ArrayBase<S2, (Ix, Ix)>) -> OwnedArray<A, (Ix, Ix)>
         where A: LinalgScalar,
@@ -214,30 +274,49 @@ impl<A, S, S2> Dot<ArrayBase<S2, (Ix, Ix)>> for ArrayBase<S, (Ix, Ix)>
     type Output = OwnedArray<A, (Ix, Ix)>;
     fn dot(&self, b: &ArrayBase<S2, (Ix, Ix)>)
         -> OwnedArray<A, (Ix, Ix)>
+    {
+        match self.try_dot(b) {
+            Ok(c) => c,
+            Err(_) => {
+                let ((m, k), (k2, n)) = (self.dim(), b.dim());
+                dot_shape_error(m, k, k2, n)
+            }
+        }
+    }
+}
+
+impl<A, S, S2> TryDot<ArrayBase<S2, (Ix, Ix)>> for ArrayBase<S, (Ix, Ix)>
+    where S: Data<Elem=A>,
+          S2: Data<Elem=A>,
+          A: LinalgScalar,
+{
+    fn try_dot(&self, b: &ArrayBase<S2, (Ix, Ix)>)
+        -> Result<OwnedArray<A, (Ix, Ix)>, ShapeError>
     {
         let a = self.view();
         let b = b.view();
         let ((m, k), (k2, n)) = (a.dim(), b.dim());
-        if k != k2 || m.checked_mul(n).is_none() {
-            return dot_shape_error(m, k, k2, n);
+        if k != k2 {
+            return Err(error::in_operation(error::incompatible_shapes(&a.dim, &b.dim), "dot"));
+        }
+        if m.checked_mul(n).is_none() {
+            return Err(error::in_operation(error::from_kind(ErrorKind::RangeLimited), "dot"));
         }
 
         let lhs_s0 = a.strides()[0];
         let rhs_s0 = b.strides()[0];
         let column_major = lhs_s0 == 1 && rhs_s0 == 1;
-        // A is Copy so this is safe
-        let mut v = Vec::with_capacity(m * n);
-        let mut c;
+        let mut c = if !column_major {
+            OwnedArray::<A, _>::uninit((m, n))
+        } else {
+            OwnedArray::<A, _>::uninit_f((m, n))
+        };
+        // with beta zero, the elements of c are written without being read
         unsafe {
-            v.set_len(m * n);
-            if !column_major {
-                c = OwnedArray::from_vec_dim_unchecked((m, n), v);
-            } else {
-                c = OwnedArray::from_vec_dim_unchecked_f((m, n), v);
-            }
+            mat_mul_impl(A::one(), &a, &b, A::zero(),
+                         &mut c.raw_view_mut().cast::<A>().deref_into_view_mut());
+            Ok(c.assume_init())
         }
-        mat_mul_impl(A::one(), &a, &b, A::zero(), &mut c.view_mut());
-        c
     }
 }
 
@@ -274,26 +353,41 @@ impl<A, S, S2> Dot<ArrayBase<S2, Ix>> for ArrayBase<S, (Ix, Ix)>
 {
     type Output = OwnedArray<A, Ix>;
     fn dot(&self, rhs: &ArrayBase<S2, Ix>) -> OwnedArray<A, Ix>
+    {
+        match self.try_dot(rhs) {
+            Ok(c) => c,
+            Err(_) => {
+                let ((m, a), n) = (self.dim(), rhs.dim());
+                dot_shape_error(m, a, n, 1)
+            }
+        }
+    }
+}
+
+impl<A, S, S2> TryDot<ArrayBase<S2, Ix>> for ArrayBase<S, (Ix, Ix)>
+    where S: Data<Elem=A>,
+          S2: Data<Elem=A>,
+          A: LinalgScalar,
+{
+    fn try_dot(&self, rhs: &ArrayBase<S2, Ix>) -> Result<OwnedArray<A, Ix>, ShapeError>
     {
         let ((m, a), n) = (self.dim(), rhs.dim());
         if a != n {
-            return dot_shape_error(m, a, n, 1);
+            return Err(error::in_operation(error::incompatible_shapes(&self.dim, &rhs.dim),
+                                           "dot"));
         }
 
-        // Avoid initializing the memory in vec -- set it during iteration
-        let mut res_elems = Vec::<A>::with_capacity(m as usize);
-        unsafe {
-            res_elems.set_len(m as usize);
-        }
-        for (i, rr) in enumerate(&mut res_elems) {
+        // Avoid initializing the memory -- set it during iteration
+        let mut res = OwnedArray::<A, _>::uninit(m);
+        for (i, rr) in res.indexed_iter_mut() {
             unsafe {
-                *rr = (0..a).fold(A::zero(),
+                *rr = MaybeUninit::new((0..a).fold(A::zero(),
                     move |s, k| s + *self.uget((i, k)) * *rhs.uget(k)
-                );
+                ));
             }
         }
         unsafe {
-            ArrayBase::from_vec_dim_unchecked(m, res_elems)
+            Ok(res.assume_init())
         }
     }
 }
@@ -319,6 +413,118 @@ impl<A, S, D> ArrayBase<S, D>
     }
 }
 
+impl<A, S, D> ArrayBase<S, D>
+    where S: Data<Elem=A>,
+          D: Dimension,
+{
+    /// Perform batched matrix multiplication of `self` and `rhs`.
+    ///
+    /// Both arrays must have at least two axes. The last two axes of each
+    /// array are the matrices, and the leading axes are batch axes: if
+    /// `self` has shape *(…, M, K)* and `rhs` has shape *(…, K, N)*, the
+    /// result has shape *(…, M, N)*, where the batch shape is that of the
+    /// two batch shapes broadcast together.
+    ///
+    /// Each matrix product is computed like `.dot()`, using BLAS or
+    /// matrixmultiply.
+    ///
+    /// **Panics** if an array has fewer than two axes, or if the shapes
+    /// are incompatible.
+    ///
+    /// ```
+    /// use ndarray::{arr2, arr3};
+    ///
+    /// let a = arr3(&[[[1., 0.],
+    ///                 [0., 1.]],
+    ///                [[2., 0.],
+    ///                 [0., 2.]]]);
+    /// let b = arr2(&[[1., 2.],
+    ///                [3., 4.]]);
+    /// assert_eq!(a.matmul(&b), arr3(&[[[1., 2.],
+    ///                                  [3., 4.]],
+    ///                                 [[2., 4.],
+    ///                                  [6., 8.]]]));
+    /// ```
+    pub fn matmul<S2, E>(&self, rhs: &ArrayBase<S2, E>)
+        -> OwnedArray<A, <D as DimMax<E>>::Output>
+        where S2: Data<Elem=A>,
+              E: Dimension,
+              D: DimMax<E>,
+              A: LinalgScalar,
+    {
+        let (lshape, rshape) = (self.shape(), rhs.shape());
+        let (nd1, nd2) = (lshape.len(), rshape.len());
+        if nd1 < 2 || nd2 < 2 {
+            panic!("ndarray: matmul requires arrays with at least two axes, got {:?} and {:?}",
+                   lshape, rshape);
+        }
+        let ((m, k), (k2, n)) = ((lshape[nd1 - 2], lshape[nd1 - 1]),
+                                 (rshape[nd2 - 2], rshape[nd2 - 1]));
+        if k != k2 {
+            dot_shape_error(m, k, k2, n);
+        }
+        let batch1 = lshape[..nd1 - 2].to_vec();
+        let batch2 = rshape[..nd2 - 2].to_vec();
+        let batch: Vec<Ix> = match co_broadcast(&batch1, &batch2) {
+            Ok(batch) => batch,
+            Err(_) => panic!("ndarray: batch shapes {:?} and {:?} can not be broadcast together",
+                             batch1, batch2),
+        };
+        let nb = batch.len();
+        let mut out_dim = <D as DimMax<E>>::Output::zero_index_with_ndim(nb + 2);
+        {
+            let out_shape = out_dim.slice_mut();
+            out_shape[..nb].copy_from_slice(&batch);
+            out_shape[nb] = m;
+            out_shape[nb + 1] = n;
+        }
+        if out_dim.size_checked().is_none() {
+            panic!("ndarray: shape {:?} overflows type range", out_dim.slice());
+        }
+        // with beta zero, the elements of c are written without being read
+        let mut c = OwnedArray::<A, _>::uninit(out_dim);
+        let lstrides = batch_strides(self.strides(), &batch1, nb);
+        let rstrides = batch_strides(rhs.strides(), &batch2, nb);
+        let cstrides = c.strides().to_vec();
+        let (ls, rs) = (self.strides(), rhs.strides());
+        let lhs_mat_strides = (ls[nd1 - 2] as Ix, ls[nd1 - 1] as Ix);
+        let rhs_mat_strides = (rs[nd2 - 2] as Ix, rs[nd2 - 1] as Ix);
+        let c_mat_strides = (cstrides[nb] as Ix, cstrides[nb + 1] as Ix);
+        let cp = c.as_mut_ptr() as *mut A;
+        if m * n == 0 {
+            return unsafe { c.assume_init() };
+        }
+        for index in Indexes::new(batch.clone()) {
+            let offset = |strides: &[Ixs]| {
+                index.iter().zip(strides).fold(0, |acc, (&i, &s)| acc + i as Ixs * s)
+            };
+            unsafe {
+                let a = ArrayView::new_(self.ptr.offset(offset(&lstrides)),
+                                        (m, k), lhs_mat_strides);
+                let b = ArrayView::new_(rhs.ptr.offset(offset(&rstrides)),
+                                        (k, n), rhs_mat_strides);
+                let mut cv = ArrayViewMut::new_(cp.offset(offset(&cstrides[..nb])),
+                                                (m, n), c_mat_strides);
+                mat_mul_impl(A::one(), &a, &b, A::zero(), &mut cv);
+            }
+        }
+        unsafe { c.assume_init() }
+    }
+}
+
+/// Return the strides that step through the batch axes `batch_shape` of an
+/// array with `strides`, broadcast to `nb` batch axes.
+fn batch_strides(strides: &[Ixs], batch_shape: &[Ix], nb: usize) -> Vec<Ixs> {
+    let mut res = vec![0; nb];
+    let skip = nb - batch_shape.len();
+    for (i, (&len, &s)) in batch_shape.iter().zip(strides).enumerate() {
+        if len != 1 {
+            res[skip + i] = s;
+        }
+    }
+    res
+}
+
 // mat_mul_impl uses ArrayView arguments to send all array kinds into
 // the same instantiated implementation.
 #[cfg(not(feature="blas"))]
@@ -339,6 +545,22 @@ fn mat_mul_impl<A>(alpha: A,
         !(same_type::<A, f32>() || same_type::<A, f64>()) {
         return mat_mul_general(alpha, lhs, rhs, beta, c);
     }
+    // BLAS can't express negative strides; copy such operands to standard
+    // layout, which is cheap compared with the product at these sizes.
+    if has_negative_stride(lhs) {
+        let lhs = to_standard_layout(lhs);
+        return mat_mul_impl(alpha, &lhs.view(), rhs, beta, c);
+    }
+    if has_negative_stride(rhs) {
+        let rhs = to_standard_layout(rhs);
+        return mat_mul_impl(alpha, lhs, &rhs.view(), beta, c);
+    }
+    if has_negative_stride(c) {
+        let mut c_ = to_standard_layout(&c.view());
+        mat_mul_impl(alpha, lhs, rhs, beta, &mut c_.view_mut());
+        c.assign(&c_);
+        return;
+    }
     {
         // Use `c` for c-order and `f` for an f-order matrix
         // We can handle c * c, f * f generally and
@@ -566,6 +788,7 @@ fn blas_row_major_2d<A, S>(a: &ArrayBase<S, (Ix, Ix)>) -> bool
     if s1 != 1 {
         return false;
     }
+    // negatively strided operands are copied before we get here
     if s0 < 1 || s1 < 1 {
         return false;
     }
@@ -582,3 +805,149 @@ fn blas_row_major_2d<A, S>(a: &ArrayBase<S, (Ix, Ix)>) -> bool
     }
     true
 }
+
+#[cfg(feature="blas")]
+fn has_negative_stride<S>(a: &ArrayBase<S, (Ix, Ix)>) -> bool
+    where S: Data,
+{
+    a.strides().iter().any(|&s| s < 0)
+}
+
+/// Copy `a` into a new array in standard layout.
+#[cfg(feature="blas")]
+fn to_standard_layout<A>(a: &ArrayView<A, (Ix, Ix)>) -> OwnedArray<A, (Ix, Ix)>
+    where A: Clone,
+{
+    let v = a.iter().cloned().collect();
+    unsafe {
+        OwnedArray::from_vec_dim_unchecked(a.dim(), v)
+    }
+}
+
+/// Compute the tensor dot product of `a` and `b`, summing over the pairs of
+/// axes in `axes`.
+///
+/// Each pair `(i, j)` in `axes` contracts axis `i` of `a` with axis `j` of
+/// `b`; the lengths of the two axes must be equal. The result has the
+/// remaining (free) axes of `a` followed by the remaining axes of `b`,
+/// each in their original order.
+///
+/// The free and contracted axes are gathered into two matrices, which are
+/// multiplied like `.dot()`, using BLAS or matrixmultiply. An operand is
+/// copied if its axes can not be gathered without it.
+///
+/// **Panics** if an axis is out of bounds or appears twice, or if the
+/// lengths of a pair of axes differ.
+///
+/// ```
+/// use ndarray::{arr2, arr3, Axis};
+/// use ndarray::linalg::tensordot;
+///
+/// let a = arr3(&[[[1., 2.], [3., 4.]],
+///                [[5., 6.], [7., 8.]]]);
+/// let b = arr2(&[[1., 0.],
+///                [0., 1.]]);
+/// // contract the last axis of `a` with the first of `b`
+/// let c = tensordot(&a, &b, &[(Axis(2), Axis(0))]);
+/// assert_eq!(c.into_shape((2, 2, 2)).unwrap(), a);
+///
+/// // contract the first two axes of both: sum of a[i, j, k] * a[i, j, l]
+/// let d = tensordot(&a, &a, &[(Axis(0), Axis(0)), (Axis(1), Axis(1))]);
+/// assert_eq!(d.into_shape((2, 2)).unwrap(), arr2(&[[84., 100.],
+///                                                [100., 120.]]));
+/// ```
+pub fn tensordot<A, S1, S2, D1, D2>(a: &ArrayBase<S1, D1>,
+                                    b: &ArrayBase<S2, D2>,
+                                    axes: &[(Axis, Axis)])
+    -> OwnedArray<A, Vec<Ix>>
+    where S1: Data<Elem=A>,
+          S2: Data<Elem=A>,
+          D1: Dimension,
+          D2: Dimension,
+          A: LinalgScalar,
+{
+    let a_axes: Vec<Ix> = axes.iter().map(|&(ax, _)| ax.axis()).collect();
+    let b_axes: Vec<Ix> = axes.iter().map(|&(_, ax)| ax.axis()).collect();
+    check_tensordot_axes(a.shape(), &a_axes);
+    check_tensordot_axes(b.shape(), &b_axes);
+    for &(i, j) in axes {
+        let (la, lb) = (a.shape()[i.axis()], b.shape()[j.axis()]);
+        if la != lb {
+            panic!("ndarray: tensordot: axis {} of length {} can not be contracted \
+                    with axis {} of length {}", i.axis(), la, j.axis(), lb);
+        }
+    }
+    let a_free: Vec<Ix> = (0..a.ndim()).filter(|i| !a_axes.contains(i)).collect();
+    let b_free: Vec<Ix> = (0..b.ndim()).filter(|i| !b_axes.contains(i)).collect();
+    let k = a_axes.iter().fold(1, |acc, &i| acc * a.shape()[i]);
+    let m = a_free.iter().fold(1, |acc, &i| acc * a.shape()[i]);
+    let n = b_free.iter().fold(1, |acc, &i| acc * b.shape()[i]);
+
+    // a with its free axes first, b with its contracted axes first
+    let a_order: Vec<Ix> = a_free.iter().chain(&a_axes).cloned().collect();
+    let b_order: Vec<Ix> = b_axes.iter().chain(&b_free).cloned().collect();
+    let a_owned;
+    let b_owned;
+    let a_mat = match permuted_into_shape(a, &a_order, (m, k)) {
+        Ok(view) => view,
+        Err(owned) => {
+            a_owned = owned;
+            a_owned.view()
+        }
+    };
+    let b_mat = match permuted_into_shape(b, &b_order, (k, n)) {
+        Ok(view) => view,
+        Err(owned) => {
+            b_owned = owned;
+            b_owned.view()
+        }
+    };
+    let mut c = OwnedArray::<A, _>::uninit((m, n));
+    if m * n != 0 {
+        unsafe {
+            mat_mul_impl(A::one(), &a_mat, &b_mat, A::zero(),
+                         &mut c.raw_view_mut().cast::<A>().deref_into_view_mut());
+        }
+    }
+    let c = unsafe { c.assume_init() };
+    let shape: Vec<Ix> = a_free.iter().map(|&i| a.shape()[i])
+                               .chain(b_free.iter().map(|&i| b.shape()[i]))
+                               .collect();
+    c.into_shape(shape).unwrap()
+}
+
+fn check_tensordot_axes(shape: &[Ix], axes: &[Ix]) {
+    for (i, &axis) in axes.iter().enumerate() {
+        if axis >= shape.len() {
+            panic!("ndarray: tensordot: axis {} is out of bounds for shape {:?}",
+                   axis, shape);
+        }
+        if axes[..i].contains(&axis) {
+            panic!("ndarray: tensordot: axis {} is contracted more than once", axis);
+        }
+    }
+}
+
+/// Return `a` with its axes in the order `order`, reshaped to `shape`,
+/// as a view; or, if that is not possible without a copy, as a new array.
+pub fn permuted_into_shape<'a, A, S, D, E>(a: &'a ArrayBase<S, D>, order: &[Ix], shape: E)
+    -> Result<ArrayView<'a, A, E>, OwnedArray<A, E>>
+    where S: Data<Elem=A>,
+          D: Dimension,
+          E: Dimension,
+          A: Copy,
+{
+    let dim: Vec<Ix> = order.iter().map(|&i| a.shape()[i]).collect();
+    let strides: Vec<Ix> = order.iter().map(|&i| a.strides()[i] as Ix).collect();
+    let permuted = unsafe {
+        ArrayView::new_(a.ptr, dim, strides)
+    };
+    if permuted.is_standard_layout() {
+        let strides = shape.default_strides();
+        unsafe {
+            return Ok(ArrayView::new_(a.ptr, shape, strides));
+        }
+    }
+    let v = permuted.iter().cloned().collect();
+    Err(OwnedArray::from_vec_dim(shape, v).unwrap())
+}
@@ -0,0 +1,330 @@
+// Copyright 2016 bluss and ndarray developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Hermitian eigenvalue and singular value decompositions, using
+//! Jacobi rotations.
+
+use std::cmp::Ordering;
+use libnum::{Float, NumCast, Zero, One};
+
+use imp_prelude::*;
+use super::{ComplexField, LinalgError};
+
+/// The maximum number of sweeps over all pairs of rows and columns.
+///
+/// Jacobi methods converge quadratically, so this is never reached in
+/// practice; it only guarantees termination.
+const MAX_SWEEPS: usize = 64;
+
+/// # Eigenvalue and singular value decompositions
+///
+/// These methods are implemented in Rust without external dependencies,
+/// for element types `f32`, `f64`, `Complex<f32>` and `Complex<f64>`.
+impl<A, S> ArrayBase<S, (Ix, Ix)>
+    where S: Data<Elem=A>,
+          A: ComplexField,
+{
+    /// Compute the eigenvalues and eigenvectors of a symmetric (or, for
+    /// complex elements, hermitian) matrix, using the cyclic Jacobi method.
+    ///
+    /// Return the real eigenvalues in ascending order, and a unitary matrix
+    /// whose columns are the corresponding eigenvectors, so that
+    /// *A = V diag(w) Vᴴ*.
+    ///
+    /// Only the lower triangle of the matrix is read.
+    ///
+    /// **Errors** if the matrix is not square.
+    ///
+    /// ```
+    /// use ndarray::{arr1, arr2};
+    ///
+    /// let a = arr2(&[[2., 1.],
+    ///                [1., 2.]]);
+    /// let (w, v) = a.eigh().unwrap();
+    /// assert!(w.all_close(&arr1(&[1., 3.]), 1e-12));
+    /// assert!(a.dot(&v.column(1)).all_close(&(&v.column(1) * 3.), 1e-12));
+    /// ```
+    pub fn eigh(&self) -> Result<(OwnedArray<A::Real, Ix>, OwnedArray<A, (Ix, Ix)>),
+                                 LinalgError>
+    {
+        let (rows, cols) = self.dim();
+        if rows != cols {
+            return Err(LinalgError::NotSquare { rows: rows, cols: cols });
+        }
+        let n = rows;
+        // fill in the upper triangle from the lower
+        let mut a = OwnedArray::<A, _>::zeros((n, n));
+        for ((i, j), elt) in a.indexed_iter_mut() {
+            *elt = if i > j {
+                self[(i, j)]
+            } else if i == j {
+                A::from_real(self[(i, i)].re())
+            } else {
+                self[(j, i)].conj()
+            };
+        }
+        let mut v = OwnedArray::<A, _>::eye(n);
+        let eps = A::Real::epsilon();
+        let hundred: A::Real = NumCast::from(100).unwrap();
+
+        for _ in 0..MAX_SWEEPS {
+            let mut off = A::Real::zero();
+            let mut total = A::Real::zero();
+            for ((i, j), &x) in a.indexed_iter() {
+                let x2 = x.abs() * x.abs();
+                total = total + x2;
+                if i != j {
+                    off = off + x2;
+                }
+            }
+            if !(off > eps * eps * total) {
+                break;
+            }
+            for p in 0..n {
+                for q in p + 1..n {
+                    let g = a[(p, q)];
+                    let ag = g.abs();
+                    let app = a[(p, p)].re();
+                    let aqq = a[(q, q)].re();
+                    // an element too small to affect the diagonal is set to zero
+                    if ag == A::Real::zero() ||
+                        (app.abs() + hundred * ag == app.abs() &&
+                         aqq.abs() + hundred * ag == aqq.abs())
+                    {
+                        a[(p, q)] = A::zero();
+                        a[(q, p)] = A::zero();
+                        continue;
+                    }
+                    let rot = Rotation::new(g, ag, app, aqq);
+                    rot.apply_right(&mut a, p, q);
+                    rot.apply_left_adjoint(&mut a, p, q);
+                    rot.apply_right(&mut v, p, q);
+                    a[(p, q)] = A::zero();
+                    a[(q, p)] = A::zero();
+                    a[(p, p)] = A::from_real(a[(p, p)].re());
+                    a[(q, q)] = A::from_real(a[(q, q)].re());
+                }
+            }
+        }
+
+        let values = a.diag().map(|x| x.re());
+        let order = sorted_indices(&values, |x, y| x.partial_cmp(y));
+        let w = OwnedArray::from_iter(order.iter().map(|&i| values[i]));
+        let v = v.select(Axis(1), &order);
+        Ok((w, v))
+    }
+
+    /// Compute the thin singular value decomposition *A = U diag(s) Vᴴ*,
+    /// using the one-sided Jacobi method.
+    ///
+    /// If the matrix is *M* × *N* and *K* = min(*M*, *N*), return *U* with
+    /// shape *M* × *K*, the singular values *s* with length *K* in
+    /// descending order, and *Vᴴ* with shape *K* × *N*. The columns of *U*
+    /// and the rows of *Vᴴ* are orthonormal.
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    ///
+    /// let a = arr2(&[[3f64, 0.],
+    ///                [4., 5.],
+    ///                [0., 0.]]);
+    /// let (u, s, vt) = a.svd();
+    /// assert_eq!(u.shape(), &[3, 2]);
+    /// assert!((s[0] - 45f64.sqrt()).abs() < 1e-12);
+    /// assert!((s[1] - 5f64.sqrt()).abs() < 1e-12);
+    /// assert!((&u * &s).dot(&vt).all_close(&a, 1e-12));
+    /// ```
+    pub fn svd(&self)
+        -> (OwnedArray<A, (Ix, Ix)>, OwnedArray<A::Real, Ix>, OwnedArray<A, (Ix, Ix)>)
+    {
+        self.svd_impl(false)
+    }
+
+    /// Compute the full singular value decomposition *A = U Σ Vᴴ*.
+    ///
+    /// This is like [`.svd()`](#method.svd), except that *U* is
+    /// *M* × *M* and *Vᴴ* is *N* × *N*, both unitary; *Σ* is the
+    /// *M* × *N* matrix with the singular values on its diagonal.
+    pub fn svd_full(&self)
+        -> (OwnedArray<A, (Ix, Ix)>, OwnedArray<A::Real, Ix>, OwnedArray<A, (Ix, Ix)>)
+    {
+        self.svd_impl(true)
+    }
+
+    fn svd_impl(&self, full: bool)
+        -> (OwnedArray<A, (Ix, Ix)>, OwnedArray<A::Real, Ix>, OwnedArray<A, (Ix, Ix)>)
+    {
+        let (m, n) = self.dim();
+        if m < n {
+            // decompose Aᴴ = U S Vᴴ instead, then A = V S Uᴴ
+            let (u, s, vt) = adjoint(self).svd_impl(full);
+            return (adjoint(&vt), s, adjoint(&u));
+        }
+        let mut u = self.to_owned();
+        let mut v = OwnedArray::<A, _>::eye(n);
+        let eps = A::Real::epsilon();
+
+        for _ in 0..MAX_SWEEPS {
+            let mut rotated = false;
+            for p in 0..n {
+                for q in p + 1..n {
+                    let alpha = norm_sq(u.column(p));
+                    let beta = norm_sq(u.column(q));
+                    let gamma = dotc(u.column(p), u.column(q));
+                    let ag = gamma.abs();
+                    if ag == A::Real::zero() || ag <= eps * (alpha * beta).sqrt() {
+                        continue;
+                    }
+                    rotated = true;
+                    let rot = Rotation::new(gamma, ag, alpha, beta);
+                    rot.apply_right(&mut u, p, q);
+                    rot.apply_right(&mut v, p, q);
+                }
+            }
+            if !rotated {
+                break;
+            }
+        }
+
+        let norms = OwnedArray::from_iter((0..n).map(|j| norm_sq(u.column(j)).sqrt()));
+        let order = sorted_indices(&norms, |x, y| y.partial_cmp(x));
+        let s = OwnedArray::from_iter(order.iter().map(|&j| norms[j]));
+        let u = u.select(Axis(1), &order);
+        let v = v.select(Axis(1), &order);
+
+        // normalize the columns of u; complete it where singular values are zero
+        let ucols = if full { m } else { n };
+        let mut u_out = OwnedArray::zeros((m, ucols));
+        let mut valid = vec![false; ucols];
+        for j in 0..n {
+            if s[j] > A::Real::zero() {
+                let sj = A::from_real(s[j]);
+                u_out.column_mut(j).zip_mut_with(&u.column(j), |x, &y| *x = y / sj);
+                valid[j] = true;
+            }
+        }
+        complete_orthonormal(&mut u_out, &mut valid);
+        (u_out, s, adjoint(&v))
+    }
+}
+
+/// A unitary rotation *U = D P* of rows or columns `p` and `q`, where
+/// *D* = diag(1, ē) removes the phase *e* of the off-diagonal element and
+/// *P* is the real Jacobi rotation [[c, s], [-s, c]] that zeroes it.
+struct Rotation<A: ComplexField> {
+    c: A,
+    s: A,
+    /// the phase e (|e| = 1)
+    e: A,
+}
+
+impl<A: ComplexField> Rotation<A> {
+    /// Compute the rotation that diagonalizes the hermitian 2 × 2 matrix
+    /// [[app, g], [ḡ, aqq]], where `ag` is |g| > 0.
+    fn new(g: A, ag: A::Real, app: A::Real, aqq: A::Real) -> Self {
+        let one = A::Real::one();
+        let two = one + one;
+        let theta = (aqq - app) / (two * ag);
+        let t = theta.signum() / (theta.abs() + (theta * theta + one).sqrt());
+        let c = one / (t * t + one).sqrt();
+        let s = t * c;
+        Rotation {
+            c: A::from_real(c),
+            s: A::from_real(s),
+            e: g / A::from_real(ag),
+        }
+    }
+
+    /// Replace `m` by `m U`.
+    fn apply_right(&self, m: &mut OwnedArray<A, (Ix, Ix)>, p: Ix, q: Ix) {
+        let (c, s, ec) = (self.c, self.s, self.e.conj());
+        for mut row in m.outer_iter_mut() {
+            let (x, y) = (row[p], row[q]);
+            row[p] = x * c - y * ec * s;
+            row[q] = x * s + y * ec * c;
+        }
+    }
+
+    /// Replace `m` by `Uᴴ m`.
+    fn apply_left_adjoint(&self, m: &mut OwnedArray<A, (Ix, Ix)>, p: Ix, q: Ix) {
+        let (c, s, e) = (self.c, self.s, self.e);
+        for mut column in m.axis_iter_mut(Axis(1)) {
+            let (x, y) = (column[p], column[q]);
+            column[p] = x * c - y * e * s;
+            column[q] = x * s + y * e * c;
+        }
+    }
+}
+
+/// Return the conjugate transpose of `m`.
+fn adjoint<A, S>(m: &ArrayBase<S, (Ix, Ix)>) -> OwnedArray<A, (Ix, Ix)>
+    where S: Data<Elem=A>,
+          A: ComplexField,
+{
+    m.t().map(|x| x.conj())
+}
+
+/// Return *xᴴ y*.
+fn dotc<A: ComplexField>(x: ArrayView<A, Ix>, y: ArrayView<A, Ix>) -> A {
+    x.iter().zip(y).fold(A::zero(), |acc, (&a, &b)| acc + a.conj() * b)
+}
+
+/// Return the squared euclidean norm of `x`.
+fn norm_sq<A: ComplexField>(x: ArrayView<A, Ix>) -> A::Real {
+    x.fold(A::Real::zero(), |acc, &a| acc + (a.conj() * a).re())
+}
+
+/// Return the indices of `xs`, sorted by `cmp` on their values.
+fn sorted_indices<T, F>(xs: &OwnedArray<T, Ix>, cmp: F) -> Vec<Ix>
+    where F: Fn(&T, &T) -> Option<Ordering>,
+{
+    let mut order: Vec<Ix> = (0..xs.len()).collect();
+    order.sort_by(|&i, &j| cmp(&xs[i], &xs[j]).unwrap_or(Ordering::Equal));
+    order
+}
+
+/// Replace each column `j` of `q` where `valid[j]` is false by a unit vector
+/// orthogonal to all the valid columns.
+///
+/// The valid columns must be orthonormal.
+fn complete_orthonormal<A: ComplexField>(q: &mut OwnedArray<A, (Ix, Ix)>,
+                                         valid: &mut [bool])
+{
+    let m = q.rows();
+    for j in 0..valid.len() {
+        if valid[j] {
+            continue;
+        }
+        // project each standard basis vector onto the orthogonal
+        // complement, and keep the one with the largest remainder
+        let mut best = OwnedArray::zeros(m);
+        let mut best_norm = A::Real::zero();
+        for i in 0..m {
+            let mut x = OwnedArray::<A, _>::zeros(m);
+            x[i] = A::one();
+            // orthogonalize twice for numerical stability
+            for _ in 0..2 {
+                for k in 0..valid.len() {
+                    if valid[k] {
+                        let qk = q.column(k);
+                        let d = dotc(qk, x.view());
+                        x.zip_mut_with(&qk, |x, &y| *x = *x - y * d);
+                    }
+                }
+            }
+            let norm = norm_sq(x.view()).sqrt();
+            if norm > best_norm {
+                best = x;
+                best_norm = norm;
+            }
+        }
+        let norm = A::from_real(best_norm);
+        q.column_mut(j).zip_mut_with(&best, |x, &y| *x = y / norm);
+        valid[j] = true;
+    }
+}
@@ -9,7 +9,17 @@
 
 //! Linear algebra.
 
-pub use self::impl_linalg::Dot;
+pub use self::impl_linalg::{Dot, TryDot};
 pub use self::impl_linalg::general_mat_mul;
+pub use self::impl_linalg::tensordot;
+pub use self::einsum::einsum;
+pub use self::decomp::LU;
+pub use self::error::LinalgError;
+pub use self::scalar::ComplexField;
 
 mod impl_linalg;
+mod decomp;
+mod einsum;
+mod error;
+mod jacobi;
+mod scalar;
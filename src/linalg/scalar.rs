@@ -0,0 +1,64 @@
+// Copyright 2016 bluss and ndarray developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+use libnum::Complex;
+
+use {LinalgScalar, NdFloat};
+
+/// Real or complex floating-point elements: `f32`, `f64`, `Complex<f32>`
+/// and `Complex<f64>`.
+///
+/// This is the element trait of the eigenvalue and singular value
+/// decompositions.
+pub trait ComplexField : LinalgScalar {
+    /// The real type of the magnitude: `f32` or `f64`.
+    type Real: NdFloat;
+
+    /// Return the complex conjugate (the value itself for real types).
+    fn conj(self) -> Self;
+
+    /// Return the real part.
+    fn re(self) -> Self::Real;
+
+    /// Return the magnitude (absolute value).
+    fn abs(self) -> Self::Real;
+
+    /// Convert a real number into this type.
+    fn from_real(re: Self::Real) -> Self;
+
+}
+
+macro_rules! impl_real_field {
+    ($t:ty) => {
+        impl ComplexField for $t {
+            type Real = $t;
+            #[inline]
+            fn conj(self) -> $t { self }
+            #[inline]
+            fn re(self) -> $t { self }
+            #[inline]
+            fn abs(self) -> $t { self.abs() }
+            #[inline]
+            fn from_real(re: $t) -> $t { re }
+        }
+
+        impl ComplexField for Complex<$t> {
+            type Real = $t;
+            #[inline]
+            fn conj(self) -> Self { Complex::conj(&self) }
+            #[inline]
+            fn re(self) -> $t { self.re }
+            #[inline]
+            fn abs(self) -> $t { self.norm() }
+            #[inline]
+            fn from_real(re: $t) -> Self { Complex::new(re, 0.) }
+        }
+    }
+}
+
+impl_real_field!(f32);
+impl_real_field!(f64);
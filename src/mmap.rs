@@ -0,0 +1,204 @@
+// Copyright 2016 bluss and ndarray developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Arrays backed by memory-mapped files.
+//!
+//! ***Requires crate feature `memmap`***
+//!
+//! [`MmapArray`](type.MmapArray.html) maps a file read-only and
+//! [`MmapArrayMut`](type.MmapArrayMut.html) maps it read-write, so that
+//! writes to the array go to the file. The operating system reads the
+//! file in on demand, so the file can be larger than the memory.
+//!
+//! The arrays are created from the shape and strides of the elements in
+//! the file, or from an `.npy` file with `open_npy` and `open_npy_mut`.
+//! All the read-only methods of arrays work on them without copying, for
+//! example `.slice()`, `.axis_chunks_iter()` and `.fold_axis()`, and
+//! `MmapArrayMut` has the methods for mutating elements as well.
+//!
+//! Mapping a file is unsafe: the elements must be valid values of the
+//! element type, and the file must not be changed by anyone else while it
+//! is mapped.
+//!
+//! ```no_run
+//! use std::fs::File;
+//! use ndarray::{Axis, Ix};
+//! use ndarray::mmap::MmapArray;
+//!
+//! let file = File::open("data.npy").unwrap();
+//! let a = unsafe { MmapArray::<f64, (Ix, Ix)>::open_npy(&file).unwrap() };
+//! let col_sums = a.sum(Axis(0));
+//! ```
+
+use std::fs::File;
+use std::io;
+use std::marker::PhantomData;
+use std::mem;
+
+use memmap2::{Mmap, MmapMut, MmapOptions};
+
+use imp_prelude::*;
+use dimension;
+use error::{self, ErrorKind, ShapeError};
+use {DataMut, RawData};
+
+/// Array representation of a read-only memory-mapped file.
+pub struct MmapRepr<A> {
+    // Only held to keep the mapping alive while the array points into it.
+    #[allow(dead_code)]
+    map: Mmap,
+    marker: PhantomData<A>,
+}
+
+/// Array representation of a read-write memory-mapped file.
+pub struct MmapReprMut<A> {
+    map: MmapMut,
+    marker: PhantomData<A>,
+}
+
+/// An array backed by a read-only memory-mapped file.
+pub type MmapArray<A, D> = ArrayBase<MmapRepr<A>, D>;
+
+/// An array backed by a read-write memory-mapped file; writes to the
+/// elements change the file.
+pub type MmapArrayMut<A, D> = ArrayBase<MmapReprMut<A>, D>;
+
+unsafe impl<A> RawData for MmapRepr<A> {
+    type Elem = A;
+}
+
+unsafe impl<A> Data for MmapRepr<A> {
+    fn _data_slice(&self) -> &[A] {
+        &[]
+    }
+}
+
+unsafe impl<A> RawData for MmapReprMut<A> {
+    type Elem = A;
+}
+
+unsafe impl<A> Data for MmapReprMut<A> {
+    fn _data_slice(&self) -> &[A] {
+        &[]
+    }
+}
+
+unsafe impl<A> DataMut for MmapReprMut<A> { }
+
+/// Check that the elements at `offset` in a mapping of `len` bytes are
+/// aligned and in bounds, and return the offset in elements from the
+/// element at `offset` to the first element of the array.
+fn check_layout<A, D>(len: usize, offset: usize, dim: &D, strides: &D)
+    -> Result<isize, ShapeError>
+    where D: Dimension
+{
+    let size = mem::size_of::<A>();
+    if size == 0 || offset % mem::align_of::<A>() != 0 || offset > len {
+        return Err(error::from_kind(ErrorKind::Unsupported));
+    }
+    try!(dimension::can_index_slice_len((len - offset) / size, dim, strides));
+    Ok(-dimension::offset_from_ptr_to_memory(dim, strides))
+}
+
+fn invalid_input(err: ShapeError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, err)
+}
+
+impl<A, D> ArrayBase<MmapRepr<A>, D>
+    where D: Dimension
+{
+    /// Map `file` read-only, and create an array of the elements starting
+    /// `offset` bytes into the file, in standard layout with shape `dim`.
+    ///
+    /// **Errors** if mapping the file fails, or with an error of kind
+    /// `InvalidInput` if the elements are not aligned or do not fit in the
+    /// file.
+    ///
+    /// Unsafe because the file must contain valid values of `A` and must
+    /// not be modified while it is mapped.
+    pub unsafe fn map_file(file: &File, offset: usize, dim: D) -> io::Result<Self> {
+        let strides = dim.default_strides();
+        Self::map_file_with_strides(file, offset, dim, strides)
+    }
+
+    /// Map `file` read-only, and create an array of the elements starting
+    /// `offset` bytes into the file, with shape `dim` and element strides
+    /// `strides`. With negative strides, `offset` is the position of the
+    /// element with the lowest address.
+    ///
+    /// **Errors** if mapping the file fails, or with an error of kind
+    /// `InvalidInput` if the elements are not aligned, do not fit in the
+    /// file, or if the strides are not supported (as in
+    /// `from_vec_dim_stride`).
+    ///
+    /// Unsafe because the file must contain valid values of `A` and must
+    /// not be modified while it is mapped.
+    pub unsafe fn map_file_with_strides(file: &File, offset: usize, dim: D, strides: D)
+        -> io::Result<Self>
+    {
+        let map = try!(MmapOptions::new().map(file));
+        let first = try!(check_layout::<A, D>(map.len(), offset, &dim, &strides)
+                             .map_err(invalid_input));
+        let ptr = (map.as_ptr().offset(offset as isize) as *mut A).offset(first);
+        Ok(ArrayBase {
+            data: MmapRepr { map: map, marker: PhantomData },
+            ptr: ptr,
+            dim: dim,
+            strides: strides,
+        })
+    }
+}
+
+impl<A, D> ArrayBase<MmapReprMut<A>, D>
+    where D: Dimension
+{
+    /// Map `file` read-write, and create an array of the elements starting
+    /// `offset` bytes into the file, in standard layout with shape `dim`.
+    /// The file must be opened for reading and writing.
+    ///
+    /// **Errors** like [`MmapArray::map_file`](type.MmapArray.html).
+    ///
+    /// Unsafe because the file must contain valid values of `A` and must
+    /// not be accessed by anyone else while it is mapped.
+    pub unsafe fn map_file_mut(file: &File, offset: usize, dim: D) -> io::Result<Self> {
+        let strides = dim.default_strides();
+        Self::map_file_mut_with_strides(file, offset, dim, strides)
+    }
+
+    /// Map `file` read-write, and create an array of the elements starting
+    /// `offset` bytes into the file, with shape `dim` and element strides
+    /// `strides`. The file must be opened for reading and writing.
+    ///
+    /// **Errors** like
+    /// [`MmapArray::map_file_with_strides`](type.MmapArray.html).
+    ///
+    /// Unsafe because the file must contain valid values of `A` and must
+    /// not be accessed by anyone else while it is mapped.
+    pub unsafe fn map_file_mut_with_strides(file: &File, offset: usize, dim: D, strides: D)
+        -> io::Result<Self>
+    {
+        let mut map = try!(MmapOptions::new().map_mut(file));
+        let first = try!(check_layout::<A, D>(map.len(), offset, &dim, &strides)
+                             .map_err(invalid_input));
+        let ptr = (map.as_mut_ptr().offset(offset as isize) as *mut A).offset(first);
+        Ok(ArrayBase {
+            data: MmapReprMut { map: map, marker: PhantomData },
+            ptr: ptr,
+            dim: dim,
+            strides: strides,
+        })
+    }
+
+    /// Write the changes to the elements back to the file, and wait until
+    /// it is done.
+    ///
+    /// **Errors** if writing to the file fails.
+    pub fn flush(&self) -> io::Result<()> {
+        self.data.map.flush()
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright 2016 bluss and ndarray developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+use std::mem;
+
+use libnum::Complex;
+
+/// Element types that can be stored in `.npy` files: the primitive
+/// integer and floating point types, `bool`, `Complex<f32>` and
+/// `Complex<f64>`.
+///
+/// ***This trait's methods are internal to the crate.***
+pub trait NpyElement : Copy {
+    /// The numpy type code, without byte order: for example `"f8"`.
+    #[doc(hidden)]
+    fn type_code() -> &'static str;
+
+    /// The size in bytes of each scalar component; the byte order
+    /// only matters if it is larger than one.
+    #[doc(hidden)]
+    fn component_size() -> usize;
+
+    /// Append the little endian representation of `self` to `buf`.
+    #[doc(hidden)]
+    fn write_le(&self, buf: &mut Vec<u8>);
+
+    /// Read an element from the first `mem::size_of::<Self>()` bytes
+    /// of `bytes`.
+    #[doc(hidden)]
+    fn read(bytes: &[u8], big_endian: bool) -> Self;
+}
+
+macro_rules! npy_int {
+    ($t:ty, $code:expr) => {
+        impl NpyElement for $t {
+            fn type_code() -> &'static str { $code }
+            fn component_size() -> usize { mem::size_of::<$t>() }
+            fn write_le(&self, buf: &mut Vec<u8>) {
+                buf.extend_from_slice(&self.to_le_bytes());
+            }
+            fn read(bytes: &[u8], big_endian: bool) -> Self {
+                let mut b = [0; mem::size_of::<$t>()];
+                b.copy_from_slice(&bytes[..mem::size_of::<$t>()]);
+                if big_endian { <$t>::from_be_bytes(b) } else { <$t>::from_le_bytes(b) }
+            }
+        }
+    }
+}
+
+npy_int!(i8, "i1");
+npy_int!(u8, "u1");
+npy_int!(i16, "i2");
+npy_int!(u16, "u2");
+npy_int!(i32, "i4");
+npy_int!(u32, "u4");
+npy_int!(i64, "i8");
+npy_int!(u64, "u8");
+
+macro_rules! npy_float {
+    ($t:ty, $bits:ty, $code:expr, $ccode:expr) => {
+        impl NpyElement for $t {
+            fn type_code() -> &'static str { $code }
+            fn component_size() -> usize { mem::size_of::<$t>() }
+            fn write_le(&self, buf: &mut Vec<u8>) {
+                self.to_bits().write_le(buf)
+            }
+            fn read(bytes: &[u8], big_endian: bool) -> Self {
+                <$t>::from_bits(<$bits>::read(bytes, big_endian))
+            }
+        }
+
+        impl NpyElement for Complex<$t> {
+            fn type_code() -> &'static str { $ccode }
+            fn component_size() -> usize { mem::size_of::<$t>() }
+            fn write_le(&self, buf: &mut Vec<u8>) {
+                self.re.write_le(buf);
+                self.im.write_le(buf);
+            }
+            fn read(bytes: &[u8], big_endian: bool) -> Self {
+                let size = mem::size_of::<$t>();
+                Complex::new(<$t>::read(bytes, big_endian),
+                             <$t>::read(&bytes[size..], big_endian))
+            }
+        }
+    }
+}
+
+npy_float!(f32, u32, "f4", "c8");
+npy_float!(f64, u64, "f8", "c16");
+
+impl NpyElement for bool {
+    fn type_code() -> &'static str { "b1" }
+    fn component_size() -> usize { 1 }
+    fn write_le(&self, buf: &mut Vec<u8>) {
+        buf.push(*self as u8);
+    }
+    fn read(bytes: &[u8], _big_endian: bool) -> Self {
+        bytes[0] != 0
+    }
+}
@@ -0,0 +1,255 @@
+// Copyright 2016 bluss and ndarray developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The `.npy` header: a magic string, a version, and a Python dict literal
+//! describing the array.
+
+use std::io::{self, Read, Write};
+use std::iter::Peekable;
+use std::str::Chars;
+
+use Ix;
+use super::ReadNpyError;
+
+const MAGIC: &'static [u8] = b"\x93NUMPY";
+
+/// The total length of the preamble and header is padded to a multiple
+/// of this.
+const HEADER_ALIGN: usize = 64;
+
+/// The longest header we read, like numpy's default limit; the length
+/// comes from the file, so it must be checked before allocating.
+const MAX_HEADER_LEN: usize = 10000;
+
+/// The deepest nesting of tuples and dicts we parse; headers are untrusted,
+/// and the parser recurses once per level.
+const MAX_NESTING: usize = 16;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Header {
+    pub descr: String,
+    pub fortran_order: bool,
+    pub shape: Vec<Ix>,
+}
+
+impl Header {
+    pub fn write<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let shape: Vec<String> = self.shape.iter().map(|x| x.to_string()).collect();
+        let shape = if shape.len() == 1 {
+            format!("({},)", shape[0])
+        } else {
+            format!("({})", shape.join(", "))
+        };
+        let mut dict = format!("{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}",
+                               self.descr,
+                               if self.fortran_order { "True" } else { "False" },
+                               shape);
+        // version 1.0 has a two byte header length, 2.0 a four byte one
+        let (version, len_size) = if dict.len() + MAGIC.len() + 4 + HEADER_ALIGN < 1 << 16 {
+            (1, 2)
+        } else {
+            (2, 4)
+        };
+        let preamble = MAGIC.len() + 2 + len_size;
+        let padding = HEADER_ALIGN - (preamble + dict.len() + 1) % HEADER_ALIGN;
+        for _ in 0..padding % HEADER_ALIGN {
+            dict.push(' ');
+        }
+        dict.push('\n');
+        try!(w.write_all(MAGIC));
+        try!(w.write_all(&[version, 0]));
+        let len = dict.len() as u32;
+        if len_size == 2 {
+            try!(w.write_all(&(len as u16).to_le_bytes()));
+        } else {
+            try!(w.write_all(&len.to_le_bytes()));
+        }
+        w.write_all(dict.as_bytes())
+    }
+
+    pub fn read<R: Read>(r: &mut R) -> Result<Header, ReadNpyError> {
+        let mut preamble = [0; 8];
+        try!(r.read_exact(&mut preamble));
+        if &preamble[..6] != MAGIC {
+            return Err(ReadNpyError::ParseHeader("missing magic string".to_string()));
+        }
+        let len = match (preamble[6], preamble[7]) {
+            (1, 0) => {
+                let mut len = [0; 2];
+                try!(r.read_exact(&mut len));
+                u16::from_le_bytes(len) as usize
+            }
+            (2, 0) | (3, 0) => {
+                let mut len = [0; 4];
+                try!(r.read_exact(&mut len));
+                u32::from_le_bytes(len) as usize
+            }
+            (major, minor) => return Err(ReadNpyError::Version(major, minor)),
+        };
+        if len > MAX_HEADER_LEN {
+            return Err(ReadNpyError::ParseHeader(format!("header length {} is longer than {}",
+                                                         len, MAX_HEADER_LEN)));
+        }
+        let mut dict = vec![0; len];
+        try!(r.read_exact(&mut dict));
+        let dict = match String::from_utf8(dict) {
+            Ok(dict) => dict,
+            Err(_) => return Err(ReadNpyError::ParseHeader("header is not text".to_string())),
+        };
+        Header::from_dict(&dict)
+    }
+
+    fn from_dict(dict: &str) -> Result<Header, ReadNpyError> {
+        let mut chars = dict.chars().peekable();
+        let value = try!(parse_value(&mut chars, 0));
+        skip_whitespace(&mut chars);
+        if chars.next().is_some() {
+            return Err(parse_error("trailing characters"));
+        }
+        let items = match value {
+            PyValue::Dict(items) => items,
+            _ => return Err(parse_error("expected a dict")),
+        };
+        let mut descr = None;
+        let mut fortran_order = None;
+        let mut shape = None;
+        for (key, value) in items {
+            match (&key[..], value) {
+                ("descr", PyValue::Str(s)) => descr = Some(s),
+                ("descr", _) => {
+                    return Err(ReadNpyError::WrongDescriptor(
+                        "structured data types are not supported".to_string()));
+                }
+                ("fortran_order", PyValue::Bool(b)) => fortran_order = Some(b),
+                ("shape", PyValue::Tuple(values)) => {
+                    let mut dims = Vec::new();
+                    for value in values {
+                        match value {
+                            PyValue::Int(n) => dims.push(n),
+                            _ => return Err(parse_error("expected integer in shape")),
+                        }
+                    }
+                    shape = Some(dims);
+                }
+                _ => return Err(parse_error("unexpected key or value")),
+            }
+        }
+        match (descr, fortran_order, shape) {
+            (Some(descr), Some(fortran_order), Some(shape)) => {
+                Ok(Header {
+                    descr: descr,
+                    fortran_order: fortran_order,
+                    shape: shape,
+                })
+            }
+            _ => Err(parse_error("missing key")),
+        }
+    }
+}
+
+fn parse_error(msg: &str) -> ReadNpyError {
+    ReadNpyError::ParseHeader(msg.to_string())
+}
+
+/// The subset of Python literals used in `.npy` headers.
+enum PyValue {
+    Str(String),
+    Bool(bool),
+    Int(Ix),
+    Tuple(Vec<PyValue>),
+    Dict(Vec<(String, PyValue)>),
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.peek().map_or(false, |c| c.is_whitespace()) {
+        chars.next();
+    }
+}
+
+/// Parse values separated by commas (with an optional trailing comma)
+/// until `end`.
+fn parse_sequence<F>(chars: &mut Peekable<Chars>, end: char, mut f: F)
+    -> Result<(), ReadNpyError>
+    where F: FnMut(&mut Peekable<Chars>) -> Result<(), ReadNpyError>,
+{
+    loop {
+        skip_whitespace(chars);
+        if chars.peek() == Some(&end) {
+            chars.next();
+            return Ok(());
+        }
+        try!(f(chars));
+        skip_whitespace(chars);
+        match chars.next() {
+            Some(',') => {}
+            Some(c) if c == end => return Ok(()),
+            _ => return Err(parse_error("expected ',' or end of sequence")),
+        }
+    }
+}
+
+/// Parse a value nested in `depth` tuples or dicts.
+fn parse_value(chars: &mut Peekable<Chars>, depth: usize) -> Result<PyValue, ReadNpyError> {
+    skip_whitespace(chars);
+    if depth > MAX_NESTING && chars.peek().map_or(false, |&c| c == '(' || c == '{') {
+        return Err(parse_error("values are nested too deeply"));
+    }
+    match chars.next() {
+        Some(quote @ '\'') | Some(quote @ '"') => {
+            let mut s = String::new();
+            loop {
+                match chars.next() {
+                    Some(c) if c == quote => return Ok(PyValue::Str(s)),
+                    Some('\\') => return Err(parse_error("escapes are not supported")),
+                    Some(c) => s.push(c),
+                    None => return Err(parse_error("unterminated string")),
+                }
+            }
+        }
+        Some('(') => {
+            let mut values = Vec::new();
+            try!(parse_sequence(chars, ')', |chars| {
+                values.push(try!(parse_value(chars, depth + 1)));
+                Ok(())
+            }));
+            Ok(PyValue::Tuple(values))
+        }
+        Some('{') => {
+            let mut items = Vec::new();
+            try!(parse_sequence(chars, '}', |chars| {
+                let key = match try!(parse_value(chars, depth + 1)) {
+                    PyValue::Str(key) => key,
+                    _ => return Err(parse_error("expected string key")),
+                };
+                skip_whitespace(chars);
+                if chars.next() != Some(':') {
+                    return Err(parse_error("expected ':'"));
+                }
+                items.push((key, try!(parse_value(chars, depth + 1))));
+                Ok(())
+            }));
+            Ok(PyValue::Dict(items))
+        }
+        Some(c) if c.is_alphanumeric() => {
+            let mut word = c.to_string();
+            while chars.peek().map_or(false, |c| c.is_alphanumeric()) {
+                word.push(chars.next().unwrap());
+            }
+            match &word[..] {
+                "True" => Ok(PyValue::Bool(true)),
+                "False" => Ok(PyValue::Bool(false)),
+                // Python 2 writes long integers with a suffix
+                _ => match word.trim_end_matches('L').parse() {
+                    Ok(n) => Ok(PyValue::Int(n)),
+                    Err(_) => Err(parse_error("unexpected word")),
+                },
+            }
+        }
+        _ => Err(parse_error("unexpected character")),
+    }
+}
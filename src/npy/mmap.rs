@@ -0,0 +1,79 @@
+// Copyright 2016 bluss and ndarray developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+use std::fs::File;
+use std::io::{Seek, SeekFrom};
+
+use imp_prelude::*;
+use mmap::{MmapRepr, MmapReprMut};
+use super::{read_header, data_size, NpyElement, ReadNpyError};
+
+/// Read the `.npy` header at the start of `file`, and return the offset,
+/// shape and strides of the elements.
+fn npy_layout<A, D>(file: &File) -> Result<(usize, D, D), ReadNpyError>
+    where A: NpyElement,
+          D: Dimension,
+{
+    let mut r = file;
+    try!(r.seek(SeekFrom::Start(0)));
+    let (dim, fortran_order, big_endian) = try!(read_header::<_, A, D>(&mut r));
+    if big_endian != cfg!(target_endian = "big") && A::component_size() > 1 {
+        let order = if big_endian { "big endian" } else { "little endian" };
+        return Err(ReadNpyError::WrongEndianness(order.to_string()));
+    }
+    let offset = try!(r.seek(SeekFrom::Current(0)));
+    let nbytes = try!(data_size::<A, D>(&dim));
+    if try!(file.metadata()).len() < offset + nbytes as u64 {
+        return Err(ReadNpyError::MissingData);
+    }
+    let strides = if fortran_order {
+        dim.fortran_strides()
+    } else {
+        dim.default_strides()
+    };
+    Ok((offset as usize, dim, strides))
+}
+
+impl<A, D> ArrayBase<MmapRepr<A>, D>
+    where A: NpyElement,
+          D: Dimension,
+{
+    /// **Requires crate feature `"memmap"`**<br>
+    /// Map the `.npy` file `file` read-only, and create an array of its
+    /// elements.
+    ///
+    /// **Errors** like [`read_npy`](npy/fn.read_npy.html), or if the
+    /// elements are not in native byte order.
+    ///
+    /// Unsafe because the file must contain valid values of `A` (`bool`
+    /// elements must be 0 or 1) and must not be modified while it is
+    /// mapped.
+    pub unsafe fn open_npy(file: &File) -> Result<Self, ReadNpyError> {
+        let (offset, dim, strides) = try!(npy_layout::<A, D>(file));
+        Ok(try!(Self::map_file_with_strides(file, offset, dim, strides)))
+    }
+}
+
+impl<A, D> ArrayBase<MmapReprMut<A>, D>
+    where A: NpyElement,
+          D: Dimension,
+{
+    /// **Requires crate feature `"memmap"`**<br>
+    /// Map the `.npy` file `file` read-write, and create an array of its
+    /// elements. The file must be opened for reading and writing.
+    ///
+    /// **Errors** like [`read_npy`](npy/fn.read_npy.html), or if the
+    /// elements are not in native byte order.
+    ///
+    /// Unsafe because the file must contain valid values of `A` (`bool`
+    /// elements must be 0 or 1) and must not be accessed by anyone else
+    /// while it is mapped.
+    pub unsafe fn open_npy_mut(file: &File) -> Result<Self, ReadNpyError> {
+        let (offset, dim, strides) = try!(npy_layout::<A, D>(file));
+        Ok(try!(Self::map_file_mut_with_strides(file, offset, dim, strides)))
+    }
+}
@@ -0,0 +1,274 @@
+// Copyright 2016 bluss and ndarray developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Reading and writing arrays in numpy's `.npy` and `.npz` formats.
+//!
+//! An `.npy` file holds one array: a short text header with the element
+//! type, the memory order and the shape, followed by the raw elements.
+//! An `.npz` file is a zip archive of `.npy` files; archives written by
+//! `numpy.savez` can be read, but not the compressed archives written by
+//! `numpy.savez_compressed`.
+//!
+//! The supported element types are those implementing
+//! [`NpyElement`](trait.NpyElement.html).
+//!
+//! ```
+//! use ndarray::{arr2, OwnedArray, Ix};
+//! use ndarray::npy::{read_npy, write_npy};
+//!
+//! let a = arr2(&[[1., 2., 3.],
+//!                [4., 5., 6.]]);
+//! let mut file = Vec::new();
+//! write_npy(&mut file, &a).unwrap();
+//!
+//! let b: OwnedArray<f64, (Ix, Ix)> = read_npy(&file[..]).unwrap();
+//! assert_eq!(a, b);
+//!
+//! // without a fixed dimension
+//! let c: OwnedArray<f64, Vec<Ix>> = read_npy(&file[..]).unwrap();
+//! assert_eq!(c.shape(), &[2, 3]);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::mem;
+
+use imp_prelude::*;
+use error::{self, ErrorKind, ShapeError};
+
+pub use self::element::NpyElement;
+pub use self::npz::NpzReader;
+
+use self::header::Header;
+
+mod element;
+mod header;
+#[cfg(feature = "memmap")]
+mod mmap;
+mod npz;
+
+/// Write `array` to `w` in the `.npy` format.
+///
+/// The elements are written in C order if the array is in standard layout
+/// or if it has no particular layout, and in Fortran order if the array is
+/// in Fortran layout; either way the array reads back the same.
+///
+/// **Errors** if writing to `w` fails.
+pub fn write_npy<W, A, S, D>(mut w: W, array: &ArrayBase<S, D>) -> io::Result<()>
+    where W: Write,
+          A: NpyElement,
+          S: Data<Elem=A>,
+          D: Dimension,
+{
+    let byte_order = if A::component_size() == 1 { "|" } else { "<" };
+    let transposed = array.view().reversed_axes();
+    let fortran_order = !array.is_standard_layout() && transposed.is_standard_layout();
+    let header = Header {
+        descr: format!("{}{}", byte_order, A::type_code()),
+        fortran_order: fortran_order,
+        shape: array.shape().to_vec(),
+    };
+    try!(header.write(&mut w));
+
+    const CHUNK: usize = 1 << 13;
+    let mut buf = Vec::with_capacity(CHUNK + mem::size_of::<A>());
+    let mut write_elements = |view: ArrayView<A, D>| -> io::Result<()> {
+        for elt in view.iter() {
+            elt.write_le(&mut buf);
+            if buf.len() >= CHUNK {
+                try!(w.write_all(&buf));
+                buf.clear();
+            }
+        }
+        w.write_all(&buf)
+    };
+    if fortran_order {
+        write_elements(transposed)
+    } else {
+        write_elements(array.view())
+    }
+}
+
+/// Read an array in the `.npy` format from `r`.
+///
+/// The dimension type `D` can be fixed, like `(Ix, Ix)`, or `Vec<Ix>` to
+/// accept any number of axes. Little and big endian files are both read.
+///
+/// **Errors** if reading from `r` fails, if the header is malformed, or if
+/// the element type or the number of axes do not match `A` and `D`.
+pub fn read_npy<R, A, D>(mut r: R) -> Result<OwnedArray<A, D>, ReadNpyError>
+    where R: Read,
+          A: NpyElement,
+          D: Dimension,
+{
+    let (dim, fortran_order, big_endian) = try!(read_header::<_, A, D>(&mut r));
+    let size = mem::size_of::<A>();
+    let nbytes = try!(data_size::<A, D>(&dim));
+    let mut bytes = Vec::new();
+    try!(r.take(nbytes as u64).read_to_end(&mut bytes));
+    if bytes.len() != nbytes {
+        return Err(ReadNpyError::MissingData);
+    }
+    let v: Vec<A> = bytes.chunks(size).map(|b| A::read(b, big_endian)).collect();
+    let array = if fortran_order {
+        OwnedArray::from_vec_dim_f(dim, v)
+    } else {
+        OwnedArray::from_vec_dim(dim, v)
+    };
+    Ok(try!(array))
+}
+
+/// Read the header and return the shape, whether it is in Fortran order,
+/// and whether the elements are big endian.
+fn read_header<R, A, D>(r: &mut R) -> Result<(D, bool, bool), ReadNpyError>
+    where R: Read,
+          A: NpyElement,
+          D: Dimension,
+{
+    let header = try!(Header::read(r));
+    let big_endian = try!(check_descr::<A>(&header.descr));
+    let ndim = header.shape.len();
+    if let Some(expected) = D::fixed_ndim() {
+        if expected != ndim {
+            return Err(ReadNpyError::WrongNdim { expected: expected, found: ndim });
+        }
+    }
+    let mut dim = D::zero_index_with_ndim(ndim);
+    dim.slice_mut().copy_from_slice(&header.shape);
+    Ok((dim, header.fortran_order, big_endian))
+}
+
+/// Return the size in bytes of the elements of an array with shape `dim`.
+fn data_size<A, D: Dimension>(dim: &D) -> Result<usize, ReadNpyError> {
+    match dim.size_checked().and_then(|len| len.checked_mul(mem::size_of::<A>())) {
+        Some(nbytes) => Ok(nbytes),
+        None => Err(ReadNpyError::Shape(error::from_kind(ErrorKind::RangeLimited))),
+    }
+}
+
+/// Check that the descriptor `descr` is for `A`, and return `true` if
+/// it is big endian.
+fn check_descr<A: NpyElement>(descr: &str) -> Result<bool, ReadNpyError> {
+    let (order, code) = match descr.chars().next() {
+        Some(c @ '<') | Some(c @ '>') | Some(c @ '=') | Some(c @ '|') => (c, &descr[1..]),
+        _ => return Err(ReadNpyError::WrongDescriptor(descr.to_string())),
+    };
+    if code != A::type_code() {
+        return Err(ReadNpyError::WrongDescriptor(descr.to_string()));
+    }
+    match order {
+        '>' => Ok(true),
+        '=' => Ok(cfg!(target_endian = "big")),
+        '|' if A::component_size() > 1 => {
+            Err(ReadNpyError::WrongEndianness(descr.to_string()))
+        }
+        _ => Ok(false),
+    }
+}
+
+/// An error from reading an `.npy` or `.npz` file.
+#[derive(Debug)]
+pub enum ReadNpyError {
+    /// reading from the underlying reader failed
+    Io(io::Error),
+    /// the `.npy` header is malformed
+    ParseHeader(String),
+    /// the `.npy` format version is not supported
+    Version(u8, u8),
+    /// the element type in the file does not match the array's
+    WrongDescriptor(String),
+    /// the byte order in the file is missing or not supported
+    WrongEndianness(String),
+    /// the number of axes in the file does not match the array's
+    WrongNdim {
+        /// number of axes of the dimension type
+        expected: usize,
+        /// number of axes in the file
+        found: usize,
+    },
+    /// the shape in the file is invalid
+    Shape(ShapeError),
+    /// the file ends before all elements are read
+    MissingData,
+    /// the `.npz` archive is malformed
+    ParseArchive(String),
+    /// the `.npz` archive has no array with this name
+    MissingArray(String),
+    /// the `.npz` archive entry with this name is compressed
+    Compressed(String),
+}
+
+impl ReadNpyError {
+    fn message(&self) -> &'static str {
+        match *self {
+            ReadNpyError::Io(_) => "I/O error",
+            ReadNpyError::ParseHeader(_) => "malformed header",
+            ReadNpyError::Version(..) => "unsupported format version",
+            ReadNpyError::WrongDescriptor(_) => "wrong element type",
+            ReadNpyError::WrongEndianness(_) => "unsupported byte order",
+            ReadNpyError::WrongNdim { .. } => "wrong number of axes",
+            ReadNpyError::Shape(_) => "invalid shape",
+            ReadNpyError::MissingData => "not enough data for the shape",
+            ReadNpyError::ParseArchive(_) => "malformed archive",
+            ReadNpyError::MissingArray(_) => "no array with this name",
+            ReadNpyError::Compressed(_) => "compressed archives are not supported",
+        }
+    }
+}
+
+impl From<io::Error> for ReadNpyError {
+    fn from(error: io::Error) -> Self {
+        ReadNpyError::Io(error)
+    }
+}
+
+impl From<ShapeError> for ReadNpyError {
+    fn from(error: ShapeError) -> Self {
+        ReadNpyError::Shape(error)
+    }
+}
+
+impl Error for ReadNpyError {
+    fn description(&self) -> &str {
+        self.message()
+    }
+
+    fn source(&self) -> Option<&(Error + 'static)> {
+        match *self {
+            ReadNpyError::Io(ref e) => Some(e),
+            ReadNpyError::Shape(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ReadNpyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ReadNpyError::Io(ref e) => write!(f, "ReadNpyError: {}", e),
+            ReadNpyError::Shape(ref e) => write!(f, "ReadNpyError: {}", e),
+            ReadNpyError::Version(major, minor) => {
+                write!(f, "ReadNpyError: {} ({}.{})", self.message(), major, minor)
+            }
+            ReadNpyError::WrongNdim { expected, found } => {
+                write!(f, "ReadNpyError: {} (expected {}, found {})",
+                       self.message(), expected, found)
+            }
+            ReadNpyError::ParseHeader(ref s) |
+            ReadNpyError::WrongDescriptor(ref s) |
+            ReadNpyError::WrongEndianness(ref s) |
+            ReadNpyError::ParseArchive(ref s) |
+            ReadNpyError::MissingArray(ref s) |
+            ReadNpyError::Compressed(ref s) => {
+                write!(f, "ReadNpyError: {} ({})", self.message(), s)
+            }
+            ReadNpyError::MissingData => write!(f, "ReadNpyError: {}", self.message()),
+        }
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright 2016 bluss and ndarray developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+use std::io::{Read, Seek, SeekFrom};
+
+use imp_prelude::*;
+use super::{read_npy, NpyElement, ReadNpyError};
+
+const END_OF_CENTRAL_DIR: u32 = 0x06054b50;
+const ZIP64_END_LOCATOR: u32 = 0x07064b50;
+const ZIP64_END_OF_CENTRAL_DIR: u32 = 0x06064b50;
+const CENTRAL_DIR_ENTRY: u32 = 0x02014b50;
+const LOCAL_HEADER: u32 = 0x04034b50;
+const ZIP64_EXTRA: u16 = 0x0001;
+
+/// The end of central directory record is 22 bytes, followed by a comment
+/// of at most 64 KiB.
+const MAX_END_SEARCH: u64 = 22 + 0xFFFF;
+
+/// A reader for the arrays in an `.npz` archive, as written by
+/// `numpy.savez`.
+///
+/// ```
+/// use std::fs::File;
+/// use ndarray::{OwnedArray, Ix};
+/// use ndarray::npy::{NpzReader, ReadNpyError};
+///
+/// fn read_weights(path: &str) -> Result<OwnedArray<f64, (Ix, Ix)>, ReadNpyError> {
+///     let mut npz = try!(NpzReader::new(try!(File::open(path))));
+///     npz.by_name("weights")
+/// }
+/// ```
+pub struct NpzReader<R> {
+    reader: R,
+    entries: Vec<Entry>,
+}
+
+struct Entry {
+    name: String,
+    compressed: bool,
+    size: u64,
+    offset: u64,
+}
+
+impl<R: Read + Seek> NpzReader<R> {
+    /// Read the table of contents of the archive in `reader`.
+    ///
+    /// **Errors** if reading fails or if `reader` is not a zip archive.
+    pub fn new(mut reader: R) -> Result<Self, ReadNpyError> {
+        let (count, mut offset) = try!(find_central_dir(&mut reader));
+        let mut entries = Vec::new();
+        for _ in 0..count {
+            try!(reader.seek(SeekFrom::Start(offset)));
+            let mut fixed = [0; 46];
+            try!(reader.read_exact(&mut fixed));
+            if le32(&fixed[0..]) != CENTRAL_DIR_ENTRY {
+                return Err(archive_error("bad central directory entry"));
+            }
+            let name_len = le16(&fixed[28..]) as usize;
+            let extra_len = le16(&fixed[30..]) as usize;
+            let comment_len = le16(&fixed[32..]) as usize;
+            let mut var = vec![0; name_len + extra_len];
+            try!(reader.read_exact(&mut var));
+            let name = match String::from_utf8(var[..name_len].to_vec()) {
+                Ok(name) => name,
+                Err(_) => return Err(archive_error("entry name is not text")),
+            };
+            let mut entry = Entry {
+                name: name,
+                compressed: le16(&fixed[10..]) != 0,
+                size: le32(&fixed[24..]) as u64,
+                offset: le32(&fixed[42..]) as u64,
+            };
+            // sizes and offsets that do not fit are in the zip64 extra field
+            let compressed_size = le32(&fixed[20..]);
+            let mut extra = &var[name_len..];
+            while extra.len() >= 4 {
+                let id = le16(extra);
+                let len = le16(&extra[2..]) as usize;
+                let mut data = &extra[4..extra.len().min(4 + len)];
+                if id == ZIP64_EXTRA {
+                    for &(field, is_u64) in &[(0, entry.size == 0xFFFFFFFF),
+                                              (1, compressed_size == 0xFFFFFFFF),
+                                              (2, entry.offset == 0xFFFFFFFF)] {
+                        if !is_u64 {
+                            continue;
+                        }
+                        if data.len() < 8 {
+                            return Err(archive_error("bad zip64 extra field"));
+                        }
+                        match field {
+                            0 => entry.size = le64(data),
+                            2 => entry.offset = le64(data),
+                            _ => {}
+                        }
+                        data = &data[8..];
+                    }
+                }
+                extra = &extra[extra.len().min(4 + len)..];
+            }
+            entries.push(entry);
+            offset += (46 + name_len + extra_len + comment_len) as u64;
+        }
+        Ok(NpzReader {
+            reader: reader,
+            entries: entries,
+        })
+    }
+
+    /// Return the names of the arrays in the archive, without the `.npy`
+    /// extension.
+    pub fn names(&self) -> Vec<String> {
+        self.entries.iter().map(|e| e.name.trim_end_matches(".npy").to_string()).collect()
+    }
+
+    /// Return the number of arrays in the archive.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return `true` if the archive has no arrays.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Read the array called `name`; the `.npy` extension may be left out.
+    ///
+    /// **Errors** if there is no array called `name`, if it is compressed,
+    /// or for the same reasons as [`read_npy`](fn.read_npy.html).
+    pub fn by_name<A, D>(&mut self, name: &str) -> Result<OwnedArray<A, D>, ReadNpyError>
+        where A: NpyElement,
+              D: Dimension,
+    {
+        let (size, offset) = {
+            let entry = match self.entries.iter().find(|e| {
+                e.name == name || e.name.trim_end_matches(".npy") == name
+            }) {
+                Some(entry) => entry,
+                None => return Err(ReadNpyError::MissingArray(name.to_string())),
+            };
+            if entry.compressed {
+                return Err(ReadNpyError::Compressed(entry.name.clone()));
+            }
+            (entry.size, entry.offset)
+        };
+        try!(self.reader.seek(SeekFrom::Start(offset)));
+        let mut local = [0; 30];
+        try!(self.reader.read_exact(&mut local));
+        if le32(&local) != LOCAL_HEADER {
+            return Err(archive_error("bad local file header"));
+        }
+        let skip = le16(&local[26..]) as i64 + le16(&local[28..]) as i64;
+        try!(self.reader.seek(SeekFrom::Current(skip)));
+        read_npy((&mut self.reader).take(size))
+    }
+}
+
+/// Return the number of entries and the offset of the central directory.
+fn find_central_dir<R: Read + Seek>(reader: &mut R) -> Result<(u64, u64), ReadNpyError> {
+    let len = try!(reader.seek(SeekFrom::End(0)));
+    let start = len.saturating_sub(MAX_END_SEARCH);
+    try!(reader.seek(SeekFrom::Start(start)));
+    let mut tail = Vec::new();
+    try!(reader.read_to_end(&mut tail));
+    let last = tail.len().saturating_sub(21);
+    let pos = match (0..last).rev().find(|&i| le32(&tail[i..]) == END_OF_CENTRAL_DIR) {
+        Some(pos) => pos,
+        None => return Err(archive_error("end of central directory not found")),
+    };
+    let end = &tail[pos..];
+    let count = le16(&end[10..]) as u64;
+    let offset = le32(&end[16..]) as u64;
+    if count != 0xFFFF && offset != 0xFFFFFFFF {
+        return Ok((count, offset));
+    }
+    // zip64: the locator is right before the end of central directory
+    if pos < 20 || le32(&tail[pos - 20..]) != ZIP64_END_LOCATOR {
+        return Err(archive_error("zip64 end of central directory not found"));
+    }
+    try!(reader.seek(SeekFrom::Start(le64(&tail[pos - 12..]))));
+    let mut end64 = [0; 56];
+    try!(reader.read_exact(&mut end64));
+    if le32(&end64) != ZIP64_END_OF_CENTRAL_DIR {
+        return Err(archive_error("bad zip64 end of central directory"));
+    }
+    Ok((le64(&end64[32..]), le64(&end64[48..])))
+}
+
+fn archive_error(msg: &str) -> ReadNpyError {
+    ReadNpyError::ParseArchive(msg.to_string())
+}
+
+fn le16(b: &[u8]) -> u16 {
+    b[0] as u16 | (b[1] as u16) << 8
+}
+
+fn le32(b: &[u8]) -> u32 {
+    le16(b) as u32 | (le16(&b[2..]) as u32) << 16
+}
+
+fn le64(b: &[u8]) -> u64 {
+    le32(b) as u64 | (le32(&b[4..]) as u64) << 32
+}
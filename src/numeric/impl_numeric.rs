@@ -6,8 +6,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use std::ops::Add;
-use libnum::{self, Zero, Float};
+use std::ops::{Add, Mul};
+use libnum::{self, Zero, One, Float};
 use itertools::free::enumerate;
 
 use imp_prelude::*;
@@ -105,12 +105,315 @@ impl<A, S, D> ArrayBase<S, D>
               D: RemoveAxis,
     {
         let n = self.shape().axis(axis);
-        let sum = self.sum(axis);
+        let mut sum = self.sum(axis);
         let mut cnt = A::one();
         for _ in 1..n {
             cnt = cnt + A::one();
         }
-        sum / &aview0(&cnt)
+        sum.mapv_inplace(|x| x / cnt);
+        sum
+    }
+
+    /// Return the product of all elements in the array.
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    ///
+    /// let a = arr2(&[[1., 2.],
+    ///                [3., 4.]]);
+    /// assert_eq!(a.scalar_product(), 24.);
+    /// ```
+    pub fn scalar_product(&self) -> A
+        where A: Clone + Mul<Output=A> + libnum::One,
+    {
+        if let Some(slc) = self.as_slice_memory_order() {
+            return numeric_util::unrolled_product(slc);
+        }
+        let mut prod = A::one();
+        for row in self.inner_iter() {
+            if let Some(slc) = row.as_slice() {
+                prod = prod * numeric_util::unrolled_product(slc);
+            } else {
+                prod = row.iter().fold(prod, |acc, elt| acc * elt.clone());
+            }
+        }
+        prod
+    }
+
+    /// Return product along `axis`.
+    ///
+    /// The product of an axis of length zero is one.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// ```
+    /// use ndarray::{aview1, arr2, Axis};
+    ///
+    /// let a = arr2(&[[1., 2.],
+    ///                [3., 4.]]);
+    /// assert!(
+    ///     a.product(Axis(0)) == aview1(&[3., 8.]) &&
+    ///     a.product(Axis(1)) == aview1(&[2., 12.])
+    /// );
+    /// ```
+    pub fn product(&self, axis: Axis) -> OwnedArray<A, <D as RemoveAxis>::Smaller>
+        where A: Clone + One + Mul<Output=A>,
+              D: RemoveAxis,
+    {
+        if self.shape().axis(axis) == 0 {
+            return OwnedArray::from_elem(self.dim.remove_axis(axis), A::one());
+        }
+        self.map_lanes(axis, |lane| lane.scalar_product())
+    }
+
+    /// Return the smallest element in the array, or `None` if the array
+    /// is empty.
+    ///
+    /// If any element is NaN (unordered with itself), the result is NaN.
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    /// use std::f64;
+    ///
+    /// let a = arr2(&[[3., 1.],
+    ///                [4., 2.]]);
+    /// assert_eq!(a.scalar_min(), Some(1.));
+    ///
+    /// let b = arr2(&[[3., f64::NAN]]);
+    /// assert!(b.scalar_min().unwrap().is_nan());
+    /// ```
+    pub fn scalar_min(&self) -> Option<A>
+        where A: Clone + PartialOrd,
+    {
+        self.fold_nonempty(min_nan)
+    }
+
+    /// Return the largest element in the array, or `None` if the array
+    /// is empty.
+    ///
+    /// If any element is NaN (unordered with itself), the result is NaN.
+    pub fn scalar_max(&self) -> Option<A>
+        where A: Clone + PartialOrd,
+    {
+        self.fold_nonempty(max_nan)
+    }
+
+    /// Return the smallest elements along `axis`.
+    ///
+    /// If any element of a lane is NaN, the result for that lane is NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds or has length zero.
+    ///
+    /// ```
+    /// use ndarray::{aview1, arr2, Axis};
+    ///
+    /// let a = arr2(&[[3., 1.],
+    ///                [4., 2.]]);
+    /// assert!(
+    ///     a.min(Axis(0)) == aview1(&[3., 1.]) &&
+    ///     a.max(Axis(1)) == aview1(&[3., 4.])
+    /// );
+    /// ```
+    pub fn min(&self, axis: Axis) -> OwnedArray<A, <D as RemoveAxis>::Smaller>
+        where A: Clone + PartialOrd,
+              D: RemoveAxis,
+    {
+        self.map_lanes(axis, |lane| lane.scalar_min().unwrap())
+    }
+
+    /// Return the largest elements along `axis`.
+    ///
+    /// If any element of a lane is NaN, the result for that lane is NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds or has length zero.
+    pub fn max(&self, axis: Axis) -> OwnedArray<A, <D as RemoveAxis>::Smaller>
+        where A: Clone + PartialOrd,
+              D: RemoveAxis,
+    {
+        self.map_lanes(axis, |lane| lane.scalar_max().unwrap())
+    }
+
+    /// Return the index of the smallest element in the array, or `None` if
+    /// the array is empty.
+    ///
+    /// If there are several smallest elements, the index of the first one
+    /// in logical order is returned. If any element is NaN, the index of the
+    /// first NaN is returned.
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    ///
+    /// let a = arr2(&[[3., 1.],
+    ///                [4., 1.]]);
+    /// assert_eq!(a.scalar_argmin(), Some((0, 1)));
+    /// assert_eq!(a.scalar_argmax(), Some((1, 0)));
+    /// ```
+    pub fn scalar_argmin(&self) -> Option<D>
+        where A: PartialOrd,
+    {
+        self.arg_best(|x, best| x < best)
+    }
+
+    /// Return the index of the largest element in the array, or `None` if
+    /// the array is empty.
+    ///
+    /// If there are several largest elements, the index of the first one
+    /// in logical order is returned. If any element is NaN, the index of the
+    /// first NaN is returned.
+    pub fn scalar_argmax(&self) -> Option<D>
+        where A: PartialOrd,
+    {
+        self.arg_best(|x, best| x > best)
+    }
+
+    /// Return the indices of the smallest elements along `axis`.
+    ///
+    /// See [`.scalar_argmin()`](#method.scalar_argmin) for how ties and NaN
+    /// are handled.
+    ///
+    /// **Panics** if `axis` is out of bounds or has length zero.
+    ///
+    /// ```
+    /// use ndarray::{aview1, arr2, Axis};
+    ///
+    /// let a = arr2(&[[3., 1.],
+    ///                [4., 2.]]);
+    /// assert!(
+    ///     a.argmin(Axis(0)) == aview1(&[0, 0]) &&
+    ///     a.argmax(Axis(1)) == aview1(&[0, 0])
+    /// );
+    /// ```
+    pub fn argmin(&self, axis: Axis) -> OwnedArray<Ix, <D as RemoveAxis>::Smaller>
+        where A: PartialOrd,
+              D: RemoveAxis,
+    {
+        self.map_lanes(axis, |lane| lane.scalar_argmin().unwrap())
+    }
+
+    /// Return the indices of the largest elements along `axis`.
+    ///
+    /// See [`.scalar_argmax()`](#method.scalar_argmax) for how ties and NaN
+    /// are handled.
+    ///
+    /// **Panics** if `axis` is out of bounds or has length zero.
+    pub fn argmax(&self, axis: Axis) -> OwnedArray<Ix, <D as RemoveAxis>::Smaller>
+        where A: PartialOrd,
+              D: RemoveAxis,
+    {
+        self.map_lanes(axis, |lane| lane.scalar_argmax().unwrap())
+    }
+
+    /// Return the variance of all elements in the array.
+    ///
+    /// The variance is computed as *Σ (x - mean)² / (n - ddof)*, where *n* is
+    /// the number of elements: use `ddof = 0.` for the population variance
+    /// and `ddof = 1.` for the sample variance.
+    ///
+    /// The result is NaN if `ddof` is greater than or equal to *n*, or if
+    /// any element is NaN.
+    ///
+    /// ```
+    /// use ndarray::arr1;
+    ///
+    /// let a = arr1(&[1., 2., 3., 4f64]);
+    /// assert_eq!(a.scalar_var(0.), 1.25);
+    /// assert!(a.scalar_var(4.).is_nan());
+    /// ```
+    pub fn scalar_var(&self, ddof: A) -> A
+        where A: Float,
+    {
+        let n = A::from(self.len()).unwrap();
+        let dof = n - ddof;
+        if !(dof > A::zero()) {
+            return A::nan();
+        }
+        let mean = self.scalar_sum() / n;
+        let sum_sq = self.fold(A::zero(), |acc, &x| {
+            let d = x - mean;
+            acc + d * d
+        });
+        sum_sq / dof
+    }
+
+    /// Return the standard deviation of all elements in the array: the square
+    /// root of [`.scalar_var(ddof)`](#method.scalar_var).
+    pub fn scalar_std(&self, ddof: A) -> A
+        where A: Float,
+    {
+        self.scalar_var(ddof).sqrt()
+    }
+
+    /// Return the variance along `axis`.
+    ///
+    /// See [`.scalar_var()`](#method.scalar_var) for the meaning of `ddof`.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// ```
+    /// use ndarray::{aview1, arr2, Axis};
+    ///
+    /// let a = arr2(&[[1., 2.],
+    ///                [3., 6.]]);
+    /// assert!(
+    ///     a.var(Axis(0), 0.) == aview1(&[1., 4.]) &&
+    ///     a.var(Axis(1), 1.) == aview1(&[0.5, 4.5])
+    /// );
+    /// ```
+    pub fn var(&self, axis: Axis, ddof: A) -> OwnedArray<A, <D as RemoveAxis>::Smaller>
+        where A: Float,
+              D: RemoveAxis,
+    {
+        if self.shape().axis(axis) == 0 {
+            return OwnedArray::from_elem(self.dim.remove_axis(axis), A::nan());
+        }
+        self.map_lanes(axis, |lane| lane.scalar_var(ddof))
+    }
+
+    /// Return the standard deviation along `axis`.
+    ///
+    /// See [`.scalar_var()`](#method.scalar_var) for the meaning of `ddof`.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    pub fn std(&self, axis: Axis, ddof: A) -> OwnedArray<A, <D as RemoveAxis>::Smaller>
+        where A: Float,
+              D: RemoveAxis,
+    {
+        self.var(axis, ddof).mapv_into(A::sqrt)
+    }
+
+    /// Return the cumulative sum along `axis`.
+    ///
+    /// The result has the same shape as `self`.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// ```
+    /// use ndarray::{arr2, Axis};
+    ///
+    /// let a = arr2(&[[1., 2.],
+    ///                [3., 4.]]);
+    /// assert_eq!(a.cumsum(Axis(0)), arr2(&[[1., 2.],
+    ///                                      [4., 6.]]));
+    /// assert_eq!(a.cumprod(Axis(1)), arr2(&[[1., 2.],
+    ///                                       [3., 12.]]));
+    /// ```
+    pub fn cumsum(&self, axis: Axis) -> OwnedArray<A, D>
+        where A: Clone + Add<Output=A>,
+              D: RemoveAxis,
+    {
+        self.accumulate_axis(axis, |acc, x| acc.clone() + x.clone())
+    }
+
+    /// Return the cumulative product along `axis`.
+    ///
+    /// The result has the same shape as `self`.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    pub fn cumprod(&self, axis: Axis) -> OwnedArray<A, D>
+        where A: Clone + Mul<Output=A>,
+              D: RemoveAxis,
+    {
+        self.accumulate_axis(axis, |acc, x| acc.clone() * x.clone())
     }
 
     /// Return `true` if the arrays' elementwise differences are all within
@@ -129,3 +432,114 @@ impl<A, S, D> ArrayBase<S, D>
     }
 }
 
+/// Private numerical helper methods
+impl<A, S, D> ArrayBase<S, D>
+    where S: Data<Elem=A>,
+          D: Dimension,
+{
+    /// Call `f` with each 1-dimensional lane along `axis` and collect the
+    /// results into an array with `axis` removed.
+    ///
+    /// **Panics** if `axis` is out of bounds or has length zero.
+    fn map_lanes<'a, B, F>(&'a self, axis: Axis, f: F)
+        -> OwnedArray<B, <D as RemoveAxis>::Smaller>
+        where D: RemoveAxis,
+              F: FnMut(ArrayView<'a, A, Ix>) -> B,
+              A: 'a,
+    {
+        assert!(self.shape().axis(axis) > 0,
+                "ndarray: reduction along an axis of length zero");
+        self.map_axis(axis, f)
+    }
+
+    /// Fold all elements with `f`, using the unrolled kernel where the
+    /// elements are contiguous; return `None` if the array is empty.
+    fn fold_nonempty<F>(&self, f: F) -> Option<A>
+        where A: Clone,
+              F: Fn(A, A) -> A,
+    {
+        if let Some(slc) = self.as_slice_memory_order() {
+            if slc.is_empty() {
+                return None;
+            }
+            return Some(numeric_util::unrolled_fold(slc, || slc[0].clone(), &f));
+        }
+        let mut acc = None;
+        for row in self.inner_iter() {
+            let row_acc = if let Some(slc) = row.as_slice() {
+                if slc.is_empty() {
+                    continue;
+                }
+                numeric_util::unrolled_fold(slc, || slc[0].clone(), &f)
+            } else {
+                let mut iter = row.iter().cloned();
+                match iter.next() {
+                    None => continue,
+                    Some(first) => iter.fold(first, &f),
+                }
+            };
+            acc = Some(match acc {
+                None => row_acc,
+                Some(acc) => f(acc, row_acc),
+            });
+        }
+        acc
+    }
+
+    /// Return the index of the first element `x` for which there is no
+    /// earlier `best` with `!is_better(x, best)`, or of the first NaN.
+    fn arg_best<F>(&self, is_better: F) -> Option<D>
+        where A: PartialOrd,
+              F: Fn(&A, &A) -> bool,
+    {
+        let mut best: Option<(D, &A)> = None;
+        for (index, elt) in self.indexed_iter() {
+            if is_nan(elt) {
+                return Some(index);
+            }
+            best = match best {
+                Some((_, b)) if !is_better(elt, b) => best,
+                _ => Some((index, elt)),
+            };
+        }
+        best.map(|(index, _)| index)
+    }
+
+    /// Return an array where each element is the accumulation with `f`
+    /// of the elements before it along `axis`, and itself.
+    fn accumulate_axis<F>(&self, axis: Axis, mut f: F) -> OwnedArray<A, D>
+        where A: Clone,
+              D: RemoveAxis,
+              F: FnMut(&A, &A) -> A,
+    {
+        let mut res = self.to_owned();
+        {
+            let mut lanes = res.axis_iter_mut(axis);
+            if let Some(mut prev) = lanes.next() {
+                for mut current in lanes {
+                    current.zip_mut_with(&prev, |x, acc| *x = f(acc, x));
+                    prev = current;
+                }
+            }
+        }
+        res
+    }
+}
+
+/// Return `true` if `x` is unordered with itself (a floating point NaN).
+#[inline]
+fn is_nan<A: PartialOrd>(x: &A) -> bool {
+    x.partial_cmp(x).is_none()
+}
+
+/// The smaller of `a` and `b`, or the one that is NaN.
+#[inline]
+fn min_nan<A: PartialOrd>(a: A, b: A) -> A {
+    if is_nan(&a) || !(is_nan(&b) || b < a) { a } else { b }
+}
+
+/// The larger of `a` and `b`, or the one that is NaN.
+#[inline]
+fn max_nan<A: PartialOrd>(a: A, b: A) -> A {
+    if is_nan(&a) || !(is_nan(&b) || b > a) { a } else { b }
+}
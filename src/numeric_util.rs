@@ -10,6 +10,7 @@ use libnum;
 use std::cmp;
 use std::ops::{
     Add,
+    Mul,
 };
 
 use LinalgScalar;
@@ -51,6 +52,57 @@ pub fn unrolled_sum<A>(mut xs: &[A]) -> A
     sum
 }
 
+/// Fold the values in `xs` with `f`, starting each of the eight
+/// partial accumulators from `init()`.
+///
+/// `f` must be associative and commutative, and `init()` must be a value
+/// that it is fine to fold in more than once (an identity element, or one
+/// of the values in `xs` for idempotent operations like min and max).
+pub fn unrolled_fold<A, I, F>(mut xs: &[A], init: I, f: F) -> A
+    where A: Clone,
+          I: Fn() -> A,
+          F: Fn(A, A) -> A,
+{
+    // eightfold unrolled so that floating point can be vectorized
+    // (even with strict floating point accuracy semantics)
+    let mut acc = init();
+    let (mut p0, mut p1, mut p2, mut p3,
+         mut p4, mut p5, mut p6, mut p7) =
+        (init(), init(), init(), init(),
+         init(), init(), init(), init());
+    while xs.len() >= 8 {
+        p0 = f(p0, xs[0].clone());
+        p1 = f(p1, xs[1].clone());
+        p2 = f(p2, xs[2].clone());
+        p3 = f(p3, xs[3].clone());
+        p4 = f(p4, xs[4].clone());
+        p5 = f(p5, xs[5].clone());
+        p6 = f(p6, xs[6].clone());
+        p7 = f(p7, xs[7].clone());
+
+        xs = &xs[8..];
+    }
+    acc = f(acc.clone(), f(p0, p4));
+    acc = f(acc.clone(), f(p1, p5));
+    acc = f(acc.clone(), f(p2, p6));
+    acc = f(acc.clone(), f(p3, p7));
+
+    // make it clear to the optimizer that this loop is short
+    // and can not be autovectorized.
+    for i in 0..xs.len() {
+        if i >= 7 { break; }
+        acc = f(acc.clone(), xs[i].clone())
+    }
+    acc
+}
+
+/// Compute the product of the values in `xs`
+pub fn unrolled_product<A>(xs: &[A]) -> A
+    where A: Clone + Mul<Output=A> + libnum::One,
+{
+    unrolled_fold(xs, A::one, A::mul)
+}
+
 /// Compute the dot product.
 ///
 /// `xs` and `ys` must be the same length
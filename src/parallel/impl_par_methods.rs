@@ -0,0 +1,195 @@
+// Copyright 2016 bluss and ndarray developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use rayon;
+use rayon::prelude::*;
+
+use imp_prelude::*;
+use {
+    AxisIter,
+    AxisIterMut,
+};
+use super::Parallel;
+
+/// The minimum number of elements in a piece of work before
+/// `par_fold_axis` stops splitting it further.
+const FOLD_AXIS_SPLIT_LIMIT: usize = 1 << 12;
+
+/// # Parallel methods
+///
+/// These methods require crate feature `rayon`.
+impl<A, S, D> ArrayBase<S, D>
+    where S: Data<Elem=A>,
+          D: Dimension,
+{
+    /// Return a parallel iterator of references to the elements of the array.
+    ///
+    /// Elements are visited in arbitrary order.
+    ///
+    /// ```
+    /// use ndarray::OwnedArray;
+    /// use ndarray::parallel::prelude::*;
+    ///
+    /// let a = OwnedArray::from_elem((64, 64), 1.);
+    /// let sum: f64 = a.par_iter().sum();
+    /// assert_eq!(sum, 64. * 64.);
+    /// ```
+    pub fn par_iter(&self) -> Parallel<ArrayView<A, D>>
+        where A: Sync,
+    {
+        self.view().into_par_iter()
+    }
+
+    /// Return a parallel iterator of mutable references to the elements of
+    /// the array.
+    ///
+    /// Elements are visited in arbitrary order.
+    pub fn par_iter_mut(&mut self) -> Parallel<ArrayViewMut<A, D>>
+        where S: DataMut,
+              A: Send,
+    {
+        self.view_mut().into_par_iter()
+    }
+
+    /// Modify the array in place by calling `f` by mutable reference on each
+    /// element, in parallel.
+    ///
+    /// Elements are visited in arbitrary order.
+    ///
+    /// ```
+    /// use ndarray::OwnedArray;
+    ///
+    /// let mut a = OwnedArray::from_elem((64, 64), 1.);
+    /// a.par_map_inplace(|x| *x *= 2.);
+    /// assert!(a.iter().all(|&x| x == 2.));
+    /// ```
+    pub fn par_map_inplace<F>(&mut self, f: F)
+        where S: DataMut,
+              F: Fn(&mut A) + Sync + Send,
+              A: Send,
+    {
+        if let Some(slc) = self.as_slice_memory_order_mut() {
+            slc.par_iter_mut().for_each(f);
+            return;
+        }
+        self.par_iter_mut().for_each(f)
+    }
+
+    /// Modify the array in place by calling `f` by **v**alue on each
+    /// element, in parallel. The array is updated with the new values.
+    ///
+    /// Elements are visited in arbitrary order.
+    pub fn par_mapv_inplace<F>(&mut self, f: F)
+        where S: DataMut,
+              F: Fn(A) -> A + Sync + Send,
+              A: Clone + Send,
+    {
+        self.par_map_inplace(move |x| *x = f(x.clone()))
+    }
+
+    /// Return a parallel iterator that yields each subview along `axis`.
+    ///
+    /// See [`.axis_iter()`](#method.axis_iter) for more information.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// ```
+    /// use ndarray::{OwnedArray, Axis};
+    /// use ndarray::parallel::prelude::*;
+    ///
+    /// let a = OwnedArray::<f64, _>::from_elem((128, 32), 1.);
+    /// let row_sums: Vec<f64> = a.par_axis_iter(Axis(0))
+    ///                           .map(|row| row.scalar_sum())
+    ///                           .collect();
+    /// assert_eq!(row_sums, vec![32.; 128]);
+    /// ```
+    pub fn par_axis_iter(&self, axis: Axis) -> Parallel<AxisIter<A, D::Smaller>>
+        where D: RemoveAxis,
+              A: Sync,
+    {
+        self.axis_iter(axis).into_par_iter()
+    }
+
+    /// Return a parallel iterator that yields each mutable subview
+    /// along `axis`.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    pub fn par_axis_iter_mut(&mut self, axis: Axis)
+        -> Parallel<AxisIterMut<A, D::Smaller>>
+        where S: DataMut,
+              D: RemoveAxis,
+              A: Send,
+    {
+        self.axis_iter_mut(axis).into_par_iter()
+    }
+
+    /// Fold along an axis, in parallel.
+    ///
+    /// The result is the same as [`.fold_axis()`](#method.fold_axis);
+    /// the work is divided between threads by splitting the array along
+    /// the other axes, so each lane along `axis` is still folded
+    /// in order.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// ```
+    /// use ndarray::{OwnedArray, Axis};
+    ///
+    /// let a = OwnedArray::<f64, _>::from_elem((100, 200), 1.);
+    /// let sums = a.par_fold_axis(Axis(0), 0., |acc, &x| acc + x);
+    /// assert_eq!(sums, a.fold_axis(Axis(0), 0., |acc, &x| acc + x));
+    /// ```
+    pub fn par_fold_axis<B, F>(&self, axis: Axis, init: B, fold: F)
+        -> OwnedArray<B, D::Smaller>
+        where D: RemoveAxis,
+              F: Fn(&B, &A) -> B + Sync,
+              B: Clone + Send + Sync,
+              A: Sync,
+    {
+        let mut res = OwnedArray::from_elem(self.dim().remove_axis(axis), init);
+        par_fold_axis_into(res.view_mut(), self.view(), axis, &fold);
+        res
+    }
+}
+
+/// Fold `source` along `axis` into `res`, splitting both along one of the
+/// other axes while there is enough work to go around.
+fn par_fold_axis_into<A, B, D, F>(mut res: ArrayViewMut<B, D::Smaller>,
+                                  source: ArrayView<A, D>,
+                                  axis: Axis,
+                                  fold: &F)
+    where D: RemoveAxis,
+          F: Fn(&B, &A) -> B + Sync,
+          B: Send + Sync,
+          A: Sync,
+{
+    let split_axis = if source.len() >= FOLD_AXIS_SPLIT_LIMIT {
+        // the longest axis of the result
+        res.shape().iter().enumerate()
+           .max_by_key(|&(_, &len)| len)
+           .and_then(|(i, &len)| if len > 1 { Some(i) } else { None })
+    } else {
+        None
+    };
+    match split_axis {
+        None => {
+            for subview in source.axis_iter(axis) {
+                res.zip_mut_with(&subview, |x, y| *x = fold(x, y));
+            }
+        }
+        Some(i) => {
+            // the corresponding axis in `source`
+            let source_axis = if i >= axis.axis() { i + 1 } else { i };
+            let mid = res.shape()[i] / 2;
+            let (r1, r2) = res.split_at(Axis(i), mid);
+            let (s1, s2) = source.split_at(Axis(source_axis), mid);
+            rayon::join(move || par_fold_axis_into(r1, s1, axis, fold),
+                        move || par_fold_axis_into(r2, s2, axis, fold));
+        }
+    }
+}
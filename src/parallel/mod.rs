@@ -0,0 +1,56 @@
+// Copyright 2016 bluss and ndarray developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Parallelization features for ndarray.
+//!
+//! ***Requires crate feature `rayon`***
+//!
+//! The array methods `.par_iter()`, `.par_iter_mut()`,
+//! `.par_map_inplace()`, `.par_axis_iter()` and `.par_fold_axis()` use
+//! [rayon](https://docs.rs/rayon) to divide the work between threads.
+//! The arrays are split in two along an axis, using the same splitting
+//! as `ArrayView::split_at` and `AxisIter::split_at`, until rayon
+//! decides the pieces are small enough.
+//!
+//! Array views (`ArrayView`, `ArrayViewMut`), references to arrays,
+//! the axis iterators (`AxisIter`, `AxisIterMut`) and the window and
+//! chunk producers (`Windows`, `ExactChunks`, `ExactChunksMut`) implement
+//! rayon’s `IntoParallelIterator`. The parallel iterators over the axis
+//! iterators are indexed (`IndexedParallelIterator`).
+//!
+//! Import the rayon traits through `ndarray::parallel::prelude`.
+//!
+//! ```
+//! use ndarray::OwnedArray;
+//! use ndarray::Axis;
+//! use ndarray::parallel::prelude::*;
+//!
+//! let mut a = OwnedArray::<f64, _>::zeros((128, 128));
+//! a.par_iter_mut().for_each(|x| *x = 1.);
+//!
+//! let row_sums: Vec<f64> = a.par_axis_iter(Axis(0))
+//!                           .map(|row| row.scalar_sum())
+//!                           .collect();
+//! assert_eq!(row_sums, vec![128.; 128]);
+//! ```
+
+pub use self::par::Parallel;
+
+/// Into- traits for creating parallelized iterators (re-exported from rayon).
+pub mod prelude {
+    pub use rayon::prelude::{
+        ParallelIterator,
+        IndexedParallelIterator,
+        IntoParallelIterator,
+        IntoParallelRefIterator,
+        IntoParallelRefMutIterator,
+    };
+}
+
+mod par;
+mod impl_par_methods;
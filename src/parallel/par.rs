@@ -0,0 +1,284 @@
+// Copyright 2016 bluss and ndarray developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use rayon::iter::{
+    ParallelIterator,
+    IndexedParallelIterator,
+    IntoParallelIterator,
+};
+use rayon::iter::plumbing::{
+    bridge,
+    bridge_unindexed,
+    Consumer,
+    Folder,
+    Producer,
+    ProducerCallback,
+    UnindexedConsumer,
+    UnindexedProducer,
+};
+
+use imp_prelude::*;
+use {
+    AxisIter,
+    AxisIterMut,
+    Windows,
+    ExactChunks,
+    ExactChunksMut,
+    NdProducer,
+};
+
+/// Parallel iterator wrapper.
+///
+/// Created by the `par_` methods of `ArrayBase`, for example
+/// [`.par_iter()`](../struct.ArrayBase.html#method.par_iter), or by
+/// `.into_par_iter()` on an array view or axis iterator.
+#[derive(Copy, Clone, Debug)]
+pub struct Parallel<I> {
+    iter: I,
+}
+
+/// Requires crate feature `rayon`.
+impl<'a, A: 'a, S, D> IntoParallelIterator for &'a ArrayBase<S, D>
+    where S: Data<Elem=A>,
+          D: Dimension,
+          A: Sync,
+{
+    type Item = &'a A;
+    type Iter = Parallel<ArrayView<'a, A, D>>;
+    fn into_par_iter(self) -> Self::Iter {
+        self.view().into_par_iter()
+    }
+}
+
+/// Requires crate feature `rayon`.
+impl<'a, A: 'a, S, D> IntoParallelIterator for &'a mut ArrayBase<S, D>
+    where S: DataMut<Elem=A>,
+          D: Dimension,
+          A: Send,
+{
+    type Item = &'a mut A;
+    type Iter = Parallel<ArrayViewMut<'a, A, D>>;
+    fn into_par_iter(self) -> Self::Iter {
+        self.view_mut().into_par_iter()
+    }
+}
+
+/// Return the axis to split an array along: the axis with the largest
+/// stride among the axes that have more than one element, so that the two
+/// halves stay as contiguous as possible.
+fn split_axis<D: Dimension>(dim: &D, strides: &[Ixs]) -> Option<Axis> {
+    dim.slice().iter().zip(strides).enumerate()
+       .filter(|&(_, (&len, _))| len > 1)
+       .max_by_key(|&(_, (_, &s))| s.abs())
+       .map(|(i, _)| Axis(i))
+}
+
+/// An unindexed producer of the elements of an array view.
+struct ElementsProducer<V>(V);
+
+macro_rules! par_view_impl {
+    ($view:ident, [$($thread_bounds:tt)*]) => {
+/// Requires crate feature `rayon`.
+impl<'a, A, D> IntoParallelIterator for $view<'a, A, D>
+    where D: Dimension,
+          A: $($thread_bounds)*,
+{
+    type Item = <Self as IntoIterator>::Item;
+    type Iter = Parallel<Self>;
+    fn into_par_iter(self) -> Self::Iter {
+        Parallel {
+            iter: self,
+        }
+    }
+}
+
+impl<'a, A, D> ParallelIterator for Parallel<$view<'a, A, D>>
+    where D: Dimension,
+          A: $($thread_bounds)*,
+{
+    type Item = <$view<'a, A, D> as IntoIterator>::Item;
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+        where C: UnindexedConsumer<Self::Item>
+    {
+        bridge_unindexed(ElementsProducer(self.iter), consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+impl<'a, A, D> UnindexedProducer for ElementsProducer<$view<'a, A, D>>
+    where D: Dimension,
+          A: $($thread_bounds)*,
+{
+    type Item = <$view<'a, A, D> as IntoIterator>::Item;
+    fn split(self) -> (Self, Option<Self>) {
+        let axis = split_axis(&self.0.dim, self.0.strides());
+        match axis {
+            Some(axis) => {
+                let mid = self.0.shape()[axis.axis()] / 2;
+                let (a, b) = self.0.split_at(axis, mid);
+                (ElementsProducer(a), Some(ElementsProducer(b)))
+            }
+            None => (self, None),
+        }
+    }
+
+    fn fold_with<F>(self, folder: F) -> F
+        where F: Folder<Self::Item>,
+    {
+        folder.consume_iter(self.0)
+    }
+}
+    }
+}
+
+par_view_impl!(ArrayView, [Sync]);
+par_view_impl!(ArrayViewMut, [Send]);
+
+/// An indexed producer of the subviews of an axis iterator.
+struct AxisProducer<I>(I);
+
+macro_rules! par_axis_iter_impl {
+    ($iter:ident, [$($thread_bounds:tt)*]) => {
+/// Requires crate feature `rayon`.
+impl<'a, A, D> IntoParallelIterator for $iter<'a, A, D>
+    where D: Dimension,
+          A: $($thread_bounds)*,
+{
+    type Item = <Self as Iterator>::Item;
+    type Iter = Parallel<Self>;
+    fn into_par_iter(self) -> Self::Iter {
+        Parallel {
+            iter: self,
+        }
+    }
+}
+
+impl<'a, A, D> ParallelIterator for Parallel<$iter<'a, A, D>>
+    where D: Dimension,
+          A: $($thread_bounds)*,
+{
+    type Item = <$iter<'a, A, D> as Iterator>::Item;
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+        where C: UnindexedConsumer<Self::Item>
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+impl<'a, A, D> IndexedParallelIterator for Parallel<$iter<'a, A, D>>
+    where D: Dimension,
+          A: $($thread_bounds)*,
+{
+    fn drive<C>(self, consumer: C) -> C::Result
+        where C: Consumer<Self::Item>
+    {
+        bridge(self, consumer)
+    }
+
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+
+    fn with_producer<Cb>(self, callback: Cb) -> Cb::Output
+        where Cb: ProducerCallback<Self::Item>
+    {
+        callback.callback(AxisProducer(self.iter))
+    }
+}
+
+impl<'a, A, D> Producer for AxisProducer<$iter<'a, A, D>>
+    where D: Dimension,
+          A: $($thread_bounds)*,
+{
+    type Item = <$iter<'a, A, D> as Iterator>::Item;
+    type IntoIter = $iter<'a, A, D>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (a, b) = self.0.split_at(index);
+        (AxisProducer(a), AxisProducer(b))
+    }
+}
+    }
+}
+
+par_axis_iter_impl!(AxisIter, [Sync]);
+par_axis_iter_impl!(AxisIterMut, [Send]);
+
+/// An unindexed producer of the windows or chunks of an array.
+struct WindowsProducer<P>(P);
+
+macro_rules! par_windows_impl {
+    ($producer:ident, [$($thread_bounds:tt)*]) => {
+/// Requires crate feature `rayon`.
+impl<'a, A, D> IntoParallelIterator for $producer<'a, A, D>
+    where D: Dimension,
+          A: $($thread_bounds)*,
+{
+    type Item = <Self as IntoIterator>::Item;
+    type Iter = Parallel<Self>;
+    fn into_par_iter(self) -> Self::Iter {
+        Parallel {
+            iter: self,
+        }
+    }
+}
+
+impl<'a, A, D> ParallelIterator for Parallel<$producer<'a, A, D>>
+    where D: Dimension,
+          A: $($thread_bounds)*,
+{
+    type Item = <$producer<'a, A, D> as IntoIterator>::Item;
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+        where C: UnindexedConsumer<Self::Item>
+    {
+        bridge_unindexed(WindowsProducer(self.iter), consumer)
+    }
+}
+
+impl<'a, A, D> UnindexedProducer for WindowsProducer<$producer<'a, A, D>>
+    where D: Dimension,
+          A: $($thread_bounds)*,
+{
+    type Item = <$producer<'a, A, D> as IntoIterator>::Item;
+    fn split(self) -> (Self, Option<Self>) {
+        let dim = self.0.raw_dim();
+        let strides: Vec<Ixs> = (0..dim.ndim())
+            .map(|i| NdProducer::stride_of(&self.0, Axis(i)))
+            .collect();
+        match split_axis(&dim, &strides) {
+            Some(axis) => {
+                let mid = dim.slice()[axis.axis()] / 2;
+                let (a, b) = self.0.split_at(axis, mid);
+                (WindowsProducer(a), Some(WindowsProducer(b)))
+            }
+            None => (self, None),
+        }
+    }
+
+    fn fold_with<F>(self, folder: F) -> F
+        where F: Folder<Self::Item>,
+    {
+        folder.consume_iter(self.0)
+    }
+}
+    }
+}
+
+par_windows_impl!(Windows, [Sync]);
+par_windows_impl!(ExactChunks, [Sync]);
+par_windows_impl!(ExactChunksMut, [Send]);
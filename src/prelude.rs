@@ -23,6 +23,7 @@ pub use {
     ArrayBase,
     OwnedArray,
     RcArray,
+    ArcArray,
     ArrayView,
     ArrayViewMut,
 };
@@ -31,6 +32,7 @@ pub use {
     Axis,
     Ix, Ixs,
     Dimension,
+    ShapeBuilder,
 };
 #[doc(no_inline)]
 pub use {
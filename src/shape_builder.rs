@@ -0,0 +1,137 @@
+// Copyright 2016 bluss and ndarray developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use {Dimension, Shape, StrideShape};
+
+/// The logical order of the elements of an array when it is reshaped:
+/// `C` (row major) order, where the last index varies the fastest, or `F`
+/// (column major) order, where the first index varies the fastest.
+///
+/// See [`.into_shape_with_order()`](struct.ArrayBase.html#method.into_shape_with_order).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Order {
+    /// Row major order.
+    C,
+    /// Column major order.
+    F,
+}
+
+/// A trait for `Shape` and `D where D: Dimension` that allows
+/// customizing the memory layout (strides) of an array shape.
+///
+/// This trait is used together with array constructor methods like
+/// `OwnedArray::from_vec_dim`, `OwnedArray::from_elem` and
+/// `OwnedArray::from_shape_fn`, which accept a shape in C order (the
+/// default), in F order, or (for the constructors that use existing
+/// data) with custom strides.
+///
+/// ```
+/// use ndarray::OwnedArray;
+/// use ndarray::ShapeBuilder;
+///
+/// let a = OwnedArray::<f64, _>::zeros((3, 4).f());
+/// assert_eq!(a.strides(), &[1, 3]);
+///
+/// // column major data, as used by Fortran and BLAS
+/// let b = OwnedArray::from_vec_dim((2, 3).f(), vec![1, 4, 2, 5, 3, 6]).unwrap();
+/// assert_eq!(b.row(0), ndarray::aview1(&[1, 2, 3]));
+///
+/// let c = OwnedArray::from_vec_dim((2, 2).strides((1, 4)), (0..6).collect()).unwrap();
+/// assert_eq!(c.row(1), ndarray::aview1(&[1, 5]));
+/// ```
+pub trait ShapeBuilder {
+    type Dim: Dimension;
+
+    /// Convert into a shape, in C order unless it already has F order.
+    fn into_shape(self) -> Shape<Self::Dim>;
+    /// Use F (column major) memory order.
+    fn f(self) -> Shape<Self::Dim>;
+    /// Use F memory order if `is_f` is true, and C order otherwise.
+    fn set_f(self, is_f: bool) -> Shape<Self::Dim>;
+    /// Use the element strides `strides`, like in
+    /// [`from_vec_dim_stride`](struct.ArrayBase.html#method.from_vec_dim_stride).
+    fn strides(self, strides: Self::Dim) -> StrideShape<Self::Dim>;
+}
+
+impl<D> ShapeBuilder for D
+    where D: Dimension
+{
+    type Dim = D;
+
+    fn into_shape(self) -> Shape<D> {
+        Shape {
+            dim: self,
+            is_c: true,
+        }
+    }
+
+    fn f(self) -> Shape<D> {
+        self.set_f(true)
+    }
+
+    fn set_f(self, is_f: bool) -> Shape<D> {
+        self.into_shape().set_f(is_f)
+    }
+
+    fn strides(self, strides: D) -> StrideShape<D> {
+        StrideShape {
+            dim: self,
+            strides: strides,
+            custom: true,
+        }
+    }
+}
+
+impl<D> ShapeBuilder for Shape<D>
+    where D: Dimension
+{
+    type Dim = D;
+
+    fn into_shape(self) -> Shape<D> {
+        self
+    }
+
+    fn f(self) -> Shape<D> {
+        self.set_f(true)
+    }
+
+    fn set_f(mut self, is_f: bool) -> Shape<D> {
+        self.is_c = !is_f;
+        self
+    }
+
+    fn strides(self, strides: D) -> StrideShape<D> {
+        self.dim.strides(strides)
+    }
+}
+
+impl<D> From<D> for Shape<D>
+    where D: Dimension
+{
+    fn from(dim: D) -> Shape<D> {
+        dim.into_shape()
+    }
+}
+
+impl<T> From<T> for StrideShape<T::Dim>
+    where T: ShapeBuilder
+{
+    fn from(value: T) -> StrideShape<T::Dim> {
+        let shape = value.into_shape();
+        let strides = if shape.is_c {
+            shape.dim.default_strides()
+        } else {
+            shape.dim.fortran_strides()
+        };
+        StrideShape {
+            dim: shape.dim,
+            strides: strides,
+            custom: false,
+        }
+    }
+}
@@ -6,10 +6,18 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use std::mem::MaybeUninit;
+
 use imp_prelude::*;
-use error::{ShapeError, ErrorKind, from_kind};
+use error::{ShapeError, ErrorKind, from_kind, in_operation, incompatible_shapes,
+            incompatible_shapes_along};
+use AsArray;
+
 /// Stack arrays along the given axis.
 ///
+/// This is the same as [`concatenate`](fn.concatenate.html): the arrays
+/// are joined along an existing axis.
+///
 /// ***Errors*** if the arrays have mismatching shapes, apart from along `axis`.
 /// (may be made more flexible in the future).<br>
 /// ***Errors*** if `arrays` is empty, if `axis` is out of bounds,
@@ -30,47 +38,330 @@ use error::{ShapeError, ErrorKind, from_kind};
 /// ```
 pub fn stack<'a, A, D>(axis: Axis, arrays: &[ArrayView<'a, A, D>])
     -> Result<OwnedArray<A, D>, ShapeError>
-    where A: Copy,
+    where A: Clone,
+          D: RemoveAxis
+{
+    concatenate(axis, arrays).map_err(|e| in_operation(e, "stack"))
+}
+
+/// Return the first axis other than `skip` where the shapes `a` and `b`
+/// differ, or `None` if they are equal apart from along `skip`.
+fn mismatching_axis<D>(a: &D, b: &D, skip: Option<Axis>) -> Option<Axis>
+    where D: Dimension
+{
+    a.slice().iter().zip(b.slice()).enumerate()
+     .position(|(i, (x, y))| Some(Axis(i)) != skip && x != y)
+     .map(Axis)
+}
+
+/// Concatenate arrays along an existing axis.
+///
+/// The result has the same axes as the arrays, and its length along `axis`
+/// is the sum of the lengths of the arrays along it.
+///
+/// The arrays are passed as views, so arrays of different storage kinds
+/// are joined by calling `.view()` on each of them; the
+/// [`concatenate!`](macro.concatenate.html) macro does this for its
+/// arguments.
+///
+/// ***Errors*** with `IncompatibleShape` if the arrays have mismatching
+/// shapes along any axis other than `axis`; the error is for the first such
+/// axis.<br>
+/// ***Errors*** if `arrays` is empty, if `axis` is out of bounds,
+/// if the result is larger than is possible to represent.
+///
+/// ```
+/// use ndarray::{arr2, Axis, concatenate};
+///
+/// let a = arr2(&[[2., 2.],
+///                [3., 3.]]);
+/// let b = arr2(&[[4.],
+///                [5.]]);
+/// assert!(
+///     concatenate(Axis(1), &[a.view(), b.view()])
+///     == Ok(arr2(&[[2., 2., 4.],
+///                  [3., 3., 5.]]))
+/// );
+/// ```
+pub fn concatenate<'a, A, D>(axis: Axis, arrays: &[ArrayView<'a, A, D>])
+    -> Result<OwnedArray<A, D>, ShapeError>
+    where A: Clone,
           D: RemoveAxis
 {
     if arrays.len() == 0 {
-        return Err(from_kind(ErrorKind::Unsupported));
+        return Err(in_operation(from_kind(ErrorKind::Unsupported), "concatenate"));
     }
 
     let mut res_dim = arrays[0].dim();
     if axis.axis() >= res_dim.ndim() {
-        return Err(from_kind(ErrorKind::OutOfBounds));
+        return Err(in_operation(from_kind(ErrorKind::OutOfBounds), "concatenate"));
     }
     let common_dim = res_dim.remove_axis(axis);
-    if arrays.iter().any(|a| a.dim().remove_axis(axis) != common_dim) {
-        return Err(from_kind(ErrorKind::IncompatibleShape));
+    for a in arrays {
+        if a.dim().remove_axis(axis) != common_dim {
+            let err = match mismatching_axis(&res_dim, &a.dim, Some(axis)) {
+                Some(ax) => incompatible_shapes_along(ax, &res_dim, &a.dim),
+                None => incompatible_shapes(&res_dim, &a.dim),
+            };
+            return Err(in_operation(err, "concatenate"));
+        }
     }
 
-    let stacked_dim = arrays.iter()
-                            .fold(0, |acc, a| acc + a.shape().axis(axis));
+    let mut stacked_dim: Ix = 0;
+    for a in arrays {
+        stacked_dim = match stacked_dim.checked_add(a.shape().axis(axis)) {
+            Some(len) => len,
+            None => return Err(in_operation(from_kind(ErrorKind::RangeLimited), "concatenate")),
+        };
+    }
     res_dim.set_axis(axis, stacked_dim);
 
-    // we can safely use uninitialized values here because they are Copy
-    // and we will only ever write to them
-    let size = res_dim.size();
-    let mut v = Vec::with_capacity(size);
-    unsafe {
-        v.set_len(size);
+    if res_dim.size_checked().is_none() {
+        return Err(in_operation(from_kind(ErrorKind::RangeLimited), "concatenate"));
     }
-    let mut res = try!(OwnedArray::from_vec_dim(res_dim, v));
+    // every element is written exactly once below
+    let mut res = OwnedArray::uninit(res_dim);
 
     {
         let mut assign_view = res.view_mut();
         for array in arrays {
             let len = array.shape().axis(axis);
             let (mut front, rest) = assign_view.split_at(axis, len);
-            front.assign(array);
+            front.zip_mut_with(array, |dst, src| *dst = MaybeUninit::new(src.clone()));
             assign_view = rest;
         }
     }
+    unsafe { Ok(res.assume_init()) }
+}
+
+/// Stack arrays along a new axis.
+///
+/// The arrays must all have the same shape; the result has one more axis,
+/// inserted before `axis`, along which its length is the number of arrays.
+///
+/// The arrays are passed as views, so arrays of different storage kinds
+/// are stacked by calling `.view()` on each of them; the
+/// [`stack_new_axis!`](macro.stack_new_axis.html) macro does this for its
+/// arguments.
+///
+/// ***Errors*** with `IncompatibleShape` if the arrays have mismatching
+/// shapes; the error is for the first axis where they differ.<br>
+/// ***Errors*** if `arrays` is empty, if `axis` is strictly greater than
+/// the number of axes of the arrays, if the result is larger than is
+/// possible to represent.
+///
+/// ```
+/// use ndarray::{arr1, arr2, Axis, stack_new_axis};
+///
+/// let a = arr1(&[2., 3.]);
+/// let b = arr1(&[4., 5.]);
+/// assert!(
+///     stack_new_axis(Axis(0), &[a.view(), b.view()])
+///     == Ok(arr2(&[[2., 3.],
+///                  [4., 5.]]))
+/// );
+/// assert!(
+///     stack_new_axis(Axis(1), &[a.view(), b.view()])
+///     == Ok(arr2(&[[2., 4.],
+///                  [3., 5.]]))
+/// );
+/// ```
+pub fn stack_new_axis<'a, A, D>(axis: Axis, arrays: &[ArrayView<'a, A, D>])
+    -> Result<OwnedArray<A, D::Larger>, ShapeError>
+    where A: Clone,
+          D: InsertAxis
+{
+    if arrays.len() == 0 {
+        return Err(in_operation(from_kind(ErrorKind::Unsupported), "stack_new_axis"));
+    }
+
+    let common_dim = arrays[0].dim();
+    if axis.axis() > common_dim.ndim() {
+        return Err(in_operation(from_kind(ErrorKind::OutOfBounds), "stack_new_axis"));
+    }
+    for a in arrays {
+        if a.dim != common_dim {
+            let err = match mismatching_axis(&common_dim, &a.dim, None) {
+                Some(ax) => incompatible_shapes_along(ax, &common_dim, &a.dim),
+                None => incompatible_shapes(&common_dim, &a.dim),
+            };
+            return Err(in_operation(err, "stack_new_axis"));
+        }
+    }
+
+    // each array as a view with a new axis of length one
+    let views = arrays.iter().map(|a| unsafe {
+        ArrayView::new_(a.ptr, a.dim.insert_axis(axis), a.strides.insert_axis(axis))
+    }).collect::<Vec<_>>();
+    concatenate(axis, &views).map_err(|e| in_operation(e, "stack_new_axis"))
+}
+
+/// Split an array into views along `axis`, at each of the `indices`.
+///
+/// The indices must be in increasing order; each view starts at an index
+/// and ends at the next, so that `n` indices give `n + 1` views, which may
+/// be empty.
+///
+/// ***Errors*** with `OutOfBounds` if `axis` is out of bounds, or if the
+/// indices are decreasing or greater than the length of `axis`.
+///
+/// ```
+/// use ndarray::{arr1, Axis, split};
+///
+/// let a = arr1(&[1, 2, 3, 4, 5]);
+/// let parts = split(&a, Axis(0), &[1, 3]).unwrap();
+/// assert_eq!(parts.len(), 3);
+/// assert_eq!(parts[0], arr1(&[1]));
+/// assert_eq!(parts[1], arr1(&[2, 3]));
+/// assert_eq!(parts[2], arr1(&[4, 5]));
+/// ```
+pub fn split<'a, A, D, V>(array: V, axis: Axis, indices: &[Ix])
+    -> Result<Vec<ArrayView<'a, A, D>>, ShapeError>
+    where V: AsArray<'a, A, D>,
+          A: 'a,
+          D: Dimension,
+{
+    let mut rest = array.into();
+    if axis.axis() >= rest.ndim() {
+        return Err(in_operation(from_kind(ErrorKind::OutOfBounds), "split"));
+    }
+    let mut res = Vec::with_capacity(indices.len() + 1);
+    let mut start = 0;
+    for &index in indices {
+        if index < start || index > start + rest.shape().axis(axis) {
+            return Err(in_operation(from_kind(ErrorKind::OutOfBounds), "split"));
+        }
+        let (front, back) = rest.split_at(axis, index - start);
+        res.push(front);
+        rest = back;
+        start = index;
+    }
+    res.push(rest);
     Ok(res)
 }
 
+/// Split an array into `n` views along `axis`, of nearly equal lengths.
+///
+/// If the length of `axis` is not divisible by `n`, the first views are
+/// one element longer than the rest. Views are empty if `n` is greater than
+/// the length.
+///
+/// ***Errors*** with `OutOfBounds` if `axis` is out of bounds, or with
+/// `Unsupported` if `n` is zero.
+///
+/// ```
+/// use ndarray::{arr1, Axis, array_split};
+///
+/// let a = arr1(&[1, 2, 3, 4, 5]);
+/// let parts = array_split(&a, Axis(0), 3).unwrap();
+/// assert_eq!(parts[0], arr1(&[1, 2]));
+/// assert_eq!(parts[1], arr1(&[3, 4]));
+/// assert_eq!(parts[2], arr1(&[5]));
+/// ```
+pub fn array_split<'a, A, D, V>(array: V, axis: Axis, n: usize)
+    -> Result<Vec<ArrayView<'a, A, D>>, ShapeError>
+    where V: AsArray<'a, A, D>,
+          A: 'a,
+          D: Dimension,
+{
+    let view = array.into();
+    if axis.axis() >= view.ndim() {
+        return Err(in_operation(from_kind(ErrorKind::OutOfBounds), "array_split"));
+    }
+    if n == 0 {
+        return Err(in_operation(from_kind(ErrorKind::Unsupported), "array_split"));
+    }
+    let len = view.shape().axis(axis);
+    let (each, extra) = (len / n, len % n);
+    let indices = (1..n).map(|i| i * each + ::std::cmp::min(i, extra))
+                        .collect::<Vec<_>>();
+    split(view, axis, &indices).map_err(|e| in_operation(e, "array_split"))
+}
+
+/// Repeat the whole array `reps[i]` times along each axis `i`.
+///
+/// ***Errors*** with `IncompatibleShape` if `reps` does not have the same
+/// number of axes as the array, or with `RangeLimited` if the result is
+/// larger than is possible to represent.
+///
+/// ```
+/// use ndarray::{arr2, tile};
+///
+/// let a = arr2(&[[1, 2]]);
+/// assert!(
+///     tile(&a, (2, 2))
+///     == Ok(arr2(&[[1, 2, 1, 2],
+///                  [1, 2, 1, 2]]))
+/// );
+/// ```
+pub fn tile<'a, A, D, V>(array: V, reps: D) -> Result<OwnedArray<A, D>, ShapeError>
+    where V: AsArray<'a, A, D>,
+          A: 'a,
+          A: Clone,
+          D: Dimension,
+{
+    let view = array.into();
+    if reps.ndim() != view.ndim() {
+        return Err(in_operation(incompatible_shapes(&view.dim, &reps), "tile"));
+    }
+    let mut res_dim = view.dim();
+    for (d, &r) in res_dim.slice_mut().iter_mut().zip(reps.slice()) {
+        *d = match d.checked_mul(r) {
+            Some(x) => x,
+            None => return Err(in_operation(from_kind(ErrorKind::RangeLimited), "tile")),
+        };
+    }
+    if res_dim.size_checked().is_none() {
+        return Err(in_operation(from_kind(ErrorKind::RangeLimited), "tile"));
+    }
+    Ok(OwnedArray::from_shape_fn(res_dim, |mut index| {
+        for (i, &d) in index.slice_mut().iter_mut().zip(view.shape()) {
+            *i %= d;
+        }
+        unsafe { view.uget(index).clone() }
+    }))
+}
+
+/// Repeat each element of the array `n` times along `axis`.
+///
+/// ***Errors*** with `OutOfBounds` if `axis` is out of bounds, or with
+/// `RangeLimited` if the result is larger than is possible to represent.
+///
+/// ```
+/// use ndarray::{arr2, Axis, repeat};
+///
+/// let a = arr2(&[[1, 2],
+///                [3, 4]]);
+/// assert!(
+///     repeat(&a, Axis(1), 2)
+///     == Ok(arr2(&[[1, 1, 2, 2],
+///                  [3, 3, 4, 4]]))
+/// );
+/// ```
+pub fn repeat<'a, A, D, V>(array: V, axis: Axis, n: usize)
+    -> Result<OwnedArray<A, D>, ShapeError>
+    where V: AsArray<'a, A, D>,
+          A: 'a,
+          A: Clone,
+          D: Dimension,
+{
+    let view = array.into();
+    if axis.axis() >= view.ndim() {
+        return Err(in_operation(from_kind(ErrorKind::OutOfBounds), "repeat"));
+    }
+    let mut res_dim = view.dim();
+    match view.shape().axis(axis).checked_mul(n) {
+        Some(len) => res_dim.set_axis(axis, len),
+        None => return Err(in_operation(from_kind(ErrorKind::RangeLimited), "repeat")),
+    }
+    if res_dim.size_checked().is_none() {
+        return Err(in_operation(from_kind(ErrorKind::RangeLimited), "repeat"));
+    }
+    Ok(OwnedArray::from_shape_fn(res_dim, |mut index| {
+        index.slice_mut()[axis.axis()] /= n;
+        unsafe { view.uget(index).clone() }
+    }))
+}
 
 /// Stack arrays along the given axis.
 ///
@@ -106,3 +397,67 @@ macro_rules! stack {
         $crate::stack($axis, &[ $($crate::ArrayView::from(&$array) ),* ]).unwrap()
     }
 }
+
+
+/// Concatenate arrays along an existing axis.
+///
+/// Uses the [`concatenate`][1] function, calling `ArrayView::from(&a)` on
+/// each argument `a`.
+///
+/// [1]: fn.concatenate.html
+///
+/// ***Panics*** if the `concatenate` function would return an error.
+///
+/// ```
+/// #[macro_use(concatenate)]
+/// extern crate ndarray;
+///
+/// use ndarray::{arr1, Axis};
+///
+/// # fn main() {
+///
+/// let a = arr1(&[2., 3.]);
+/// assert!(
+///     concatenate![Axis(0), a, &[4.]]
+///     == arr1(&[2., 3., 4.])
+/// );
+/// # }
+/// ```
+#[macro_export]
+macro_rules! concatenate {
+    ($axis:expr, $( $array:expr ),+ ) => {
+        $crate::concatenate($axis, &[ $($crate::ArrayView::from(&$array) ),* ]).unwrap()
+    }
+}
+
+/// Stack arrays along a new axis.
+///
+/// Uses the [`stack_new_axis`][1] function, calling `ArrayView::from(&a)`
+/// on each argument `a`.
+///
+/// [1]: fn.stack_new_axis.html
+///
+/// ***Panics*** if the `stack_new_axis` function would return an error.
+///
+/// ```
+/// #[macro_use(stack_new_axis)]
+/// extern crate ndarray;
+///
+/// use ndarray::{arr1, arr2, Axis};
+///
+/// # fn main() {
+///
+/// let a = arr1(&[2., 3.]);
+/// assert!(
+///     stack_new_axis![Axis(0), a, &[4., 5.]]
+///     == arr2(&[[2., 3.],
+///               [4., 5.]])
+/// );
+/// # }
+/// ```
+#[macro_export]
+macro_rules! stack_new_axis {
+    ($axis:expr, $( $array:expr ),+ ) => {
+        $crate::stack_new_axis($axis, &[ $($crate::ArrayView::from(&$array) ),* ]).unwrap()
+    }
+}
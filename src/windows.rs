@@ -0,0 +1,458 @@
+// Copyright 2016 bluss and ndarray developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Producers of N-dimensional windows and chunks of an array.
+
+use imp_prelude::*;
+use iterators::Baseiter;
+use zip::{Layout, NdProducer};
+
+/// A producer of the overlapping windows of an array.
+///
+/// Each window is an `ArrayView` with the window's shape; the windows are
+/// laid out in an array of their own, with one axis for each axis of the
+/// array, so that the producer can be used with [`Zip`](struct.Zip.html),
+/// or iterated over in logical order.
+///
+/// Iterator element type is `ArrayView<'a, A, D>`.
+///
+/// See [`.windows()`](struct.ArrayBase.html#method.windows) for more
+/// information.
+pub struct Windows<'a, A: 'a, D> {
+    /// the array the windows are taken from
+    source: ArrayView<'a, A, D>,
+    window: D,
+    step: D,
+    dilation: D,
+    /// the first element of each window
+    base: ArrayView<'a, A, D>,
+    /// the element strides of a window
+    window_strides: D,
+    /// whether this is a part of a producer split by `split_at`
+    split: bool,
+}
+
+/// Return the element strides `strides` multiplied by `factor`, axis by
+/// axis.
+fn scaled_strides<D: Dimension>(strides: &D, factor: &D) -> D {
+    let mut res = strides.clone();
+    for (s, &f) in res.slice_mut().iter_mut().zip(factor.slice()) {
+        *s = (*s as Ixs * f as Ixs) as Ix;
+    }
+    res
+}
+
+fn check_window_arg<D: Dimension>(name: &str, shape: &[Ix], arg: &D) {
+    if arg.ndim() != shape.len() {
+        panic!("ndarray: {} {:?} does not match the number of axes of an array of shape {:?}",
+               name, arg.slice(), shape);
+    }
+    if arg.slice().iter().any(|&x| x == 0) {
+        panic!("ndarray: {} {:?} has an axis of length zero", name, arg.slice());
+    }
+}
+
+impl<'a, A, D> Windows<'a, A, D>
+    where D: Dimension
+{
+    fn new(source: ArrayView<'a, A, D>, window: D, step: D, dilation: D) -> Self {
+        check_window_arg("window shape", source.shape(), &window);
+        check_window_arg("window stride", source.shape(), &step);
+        check_window_arg("window dilation", source.shape(), &dilation);
+        let mut count = source.dim.clone();
+        for (i, n) in count.slice_mut().iter_mut().enumerate() {
+            let span = dilation.slice()[i] * (window.slice()[i] - 1) + 1;
+            *n = if *n >= span {
+                (*n - span) / step.slice()[i] + 1
+            } else {
+                0
+            };
+        }
+        let base = unsafe {
+            ArrayView::new_(source.ptr, count, scaled_strides(&source.strides, &step))
+        };
+        let window_strides = scaled_strides(&source.strides, &dilation);
+        Windows {
+            source: source,
+            window: window,
+            step: step,
+            dilation: dilation,
+            base: base,
+            window_strides: window_strides,
+            split: false,
+        }
+    }
+
+    fn check_not_split(&self, method: &str) {
+        if self.split {
+            panic!("ndarray: the window {} must be set before splitting the producer",
+                   method);
+        }
+    }
+
+    /// Move the windows by `stride` elements along each axis, instead of
+    /// by one element.
+    ///
+    /// **Panics** if `stride` does not have one non-zero length per axis
+    /// of the array, or if the producer is the result of
+    /// [`.split_at()`](#method.split_at).
+    ///
+    /// ```
+    /// use ndarray::OwnedArray;
+    /// use ndarray::arr2;
+    ///
+    /// let a = OwnedArray::from_iter(0..12).into_shape((3, 4)).unwrap();
+    /// let corners: Vec<_> = a.windows((2, 2)).stride((1, 2))
+    ///                        .into_iter().map(|w| w[(0, 0)]).collect();
+    /// assert_eq!(corners, vec![0, 2, 4, 6]);
+    /// ```
+    pub fn stride(self, stride: D) -> Self {
+        self.check_not_split("stride");
+        Windows::new(self.source, self.window, stride, self.dilation)
+    }
+
+    /// Spread out the elements of each window, so that they are `dilation`
+    /// elements apart along each axis.
+    ///
+    /// **Panics** if `dilation` does not have one non-zero length per
+    /// axis of the array, or if the producer is the result of
+    /// [`.split_at()`](#method.split_at).
+    ///
+    /// ```
+    /// use ndarray::OwnedArray;
+    /// use ndarray::aview1;
+    ///
+    /// let a = OwnedArray::from_iter(0..6);
+    /// let mut windows = a.windows(2).dilation(3).into_iter();
+    /// assert_eq!(windows.next().unwrap(), aview1(&[0, 3]));
+    /// assert_eq!(windows.len(), 2);
+    /// ```
+    pub fn dilation(self, dilation: D) -> Self {
+        self.check_not_split("dilation");
+        Windows::new(self.source, self.window, self.step, dilation)
+    }
+
+    /// Return the number of windows along each axis.
+    pub fn raw_dim(&self) -> D {
+        self.base.dim.clone()
+    }
+
+    /// Split the producer along `axis` at window `index`, yielding two
+    /// disjoint sets of windows (the windows themselves may overlap).
+    ///
+    /// The stride and dilation of the windows can not be changed after
+    /// splitting.
+    ///
+    /// **Panics** if `axis` or `index` is out of bounds.
+    pub fn split_at(self, axis: Axis, index: Ix) -> (Self, Self) {
+        let (a, b) = self.base.split_at(axis, index);
+        let left = Windows {
+            source: self.source.clone(),
+            window: self.window.clone(),
+            step: self.step.clone(),
+            dilation: self.dilation.clone(),
+            base: a,
+            window_strides: self.window_strides.clone(),
+            split: true,
+        };
+        let right = Windows {
+            source: self.source,
+            window: self.window,
+            step: self.step,
+            dilation: self.dilation,
+            base: b,
+            window_strides: self.window_strides,
+            split: true,
+        };
+        (left, right)
+    }
+}
+
+impl<'a, A, D> Clone for Windows<'a, A, D>
+    where D: Clone
+{
+    fn clone(&self) -> Self {
+        Windows {
+            source: self.source.clone(),
+            window: self.window.clone(),
+            step: self.step.clone(),
+            dilation: self.dilation.clone(),
+            base: self.base.clone(),
+            window_strides: self.window_strides.clone(),
+            split: self.split,
+        }
+    }
+}
+
+/// A producer of the non-overlapping chunks of an array, which all have the
+/// same shape; elements at the end of an axis that do not fill a whole
+/// chunk are left out.
+///
+/// Iterator element type is `ArrayView<'a, A, D>`.
+///
+/// See [`.exact_chunks()`](struct.ArrayBase.html#method.exact_chunks) for
+/// more information.
+pub struct ExactChunks<'a, A: 'a, D> {
+    base: ArrayView<'a, A, D>,
+    chunk: D,
+    inner_strides: D,
+}
+
+/// A producer of the non-overlapping read-write chunks of an array, which
+/// all have the same shape.
+///
+/// Iterator element type is `ArrayViewMut<'a, A, D>`.
+///
+/// See [`.exact_chunks_mut()`](struct.ArrayBase.html#method.exact_chunks_mut)
+/// for more information.
+pub struct ExactChunksMut<'a, A: 'a, D> {
+    base: ArrayViewMut<'a, A, D>,
+    chunk: D,
+    inner_strides: D,
+}
+
+/// Return the number of chunks along each axis and their strides.
+fn chunk_layout<D: Dimension>(dim: &D, strides: &D, chunk: &D) -> (D, D) {
+    check_window_arg("chunk shape", dim.slice(), chunk);
+    let mut count = dim.clone();
+    for (n, &c) in count.slice_mut().iter_mut().zip(chunk.slice()) {
+        *n /= c;
+    }
+    (count, scaled_strides(strides, chunk))
+}
+
+macro_rules! exact_chunks_impl {
+    ($name:ident) => {
+impl<'a, A, D> $name<'a, A, D>
+    where D: Dimension
+{
+    /// Return the number of chunks along each axis.
+    pub fn raw_dim(&self) -> D {
+        self.base.dim.clone()
+    }
+
+    /// Split the producer along `axis` at chunk `index`, yielding two
+    /// disjoint sets of chunks.
+    ///
+    /// **Panics** if `axis` or `index` is out of bounds.
+    pub fn split_at(self, axis: Axis, index: Ix) -> (Self, Self) {
+        let (a, b) = self.base.split_at(axis, index);
+        let left = $name {
+            base: a,
+            chunk: self.chunk.clone(),
+            inner_strides: self.inner_strides.clone(),
+        };
+        let right = $name {
+            base: b,
+            chunk: self.chunk,
+            inner_strides: self.inner_strides,
+        };
+        (left, right)
+    }
+}
+    }
+}
+
+exact_chunks_impl!(ExactChunks);
+exact_chunks_impl!(ExactChunksMut);
+
+impl<'a, A, D> Clone for ExactChunks<'a, A, D>
+    where D: Clone
+{
+    fn clone(&self) -> Self {
+        ExactChunks {
+            base: self.base.clone(),
+            chunk: self.chunk.clone(),
+            inner_strides: self.inner_strides.clone(),
+        }
+    }
+}
+
+pub fn new_windows<A, D>(source: ArrayView<A, D>, window: D) -> Windows<A, D>
+    where D: Dimension
+{
+    let mut ones = window.clone();
+    for x in ones.slice_mut() {
+        *x = 1;
+    }
+    Windows::new(source, window, ones.clone(), ones)
+}
+
+pub fn new_exact_chunks<A, D>(v: ArrayView<A, D>, chunk: D) -> ExactChunks<A, D>
+    where D: Dimension
+{
+    let (count, strides) = chunk_layout(&v.dim, &v.strides, &chunk);
+    ExactChunks {
+        base: unsafe { ArrayView::new_(v.ptr, count, strides) },
+        inner_strides: v.strides,
+        chunk: chunk,
+    }
+}
+
+pub fn new_exact_chunks_mut<A, D>(v: ArrayViewMut<A, D>, chunk: D) -> ExactChunksMut<A, D>
+    where D: Dimension
+{
+    let (count, strides) = chunk_layout(&v.dim, &v.strides, &chunk);
+    ExactChunksMut {
+        base: unsafe { ArrayViewMut::new_(v.ptr, count, strides) },
+        inner_strides: v.strides,
+        chunk: chunk,
+    }
+}
+
+/// An iterator over the windows or chunks of an array.
+///
+/// Iterator element type is `ArrayView<'a, A, D>`.
+///
+/// See [`Windows`](struct.Windows.html) and
+/// [`ExactChunks`](struct.ExactChunks.html).
+pub struct WindowsIter<'a, A: 'a, D> {
+    iter: Baseiter<'a, A, D>,
+    inner_dim: D,
+    inner_strides: D,
+}
+
+/// An iterator over the read-write chunks of an array.
+///
+/// Iterator element type is `ArrayViewMut<'a, A, D>`.
+///
+/// See [`ExactChunksMut`](struct.ExactChunksMut.html).
+pub struct ExactChunksIterMut<'a, A: 'a, D> {
+    iter: Baseiter<'a, A, D>,
+    inner_dim: D,
+    inner_strides: D,
+}
+
+macro_rules! windows_iter_impl {
+    ($iter:ident, $view:ident) => {
+impl<'a, A, D> Iterator for $iter<'a, A, D>
+    where D: Dimension
+{
+    type Item = $view<'a, A, D>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|ptr| {
+            unsafe {
+                $view::new_(ptr, self.inner_dim.clone(), self.inner_strides.clone())
+            }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.iter.size_hint();
+        (len, Some(len))
+    }
+}
+
+impl<'a, A, D> ExactSizeIterator for $iter<'a, A, D>
+    where D: Dimension
+{}
+    }
+}
+
+windows_iter_impl!(WindowsIter, ArrayView);
+windows_iter_impl!(ExactChunksIterMut, ArrayViewMut);
+
+impl<'a, A, D> IntoIterator for Windows<'a, A, D>
+    where D: Dimension
+{
+    type Item = ArrayView<'a, A, D>;
+    type IntoIter = WindowsIter<'a, A, D>;
+    fn into_iter(self) -> Self::IntoIter {
+        WindowsIter {
+            iter: unsafe { Baseiter::new(self.base.ptr, self.base.dim, self.base.strides) },
+            inner_dim: self.window,
+            inner_strides: self.window_strides,
+        }
+    }
+}
+
+impl<'a, A, D> IntoIterator for ExactChunks<'a, A, D>
+    where D: Dimension
+{
+    type Item = ArrayView<'a, A, D>;
+    type IntoIter = WindowsIter<'a, A, D>;
+    fn into_iter(self) -> Self::IntoIter {
+        WindowsIter {
+            iter: unsafe { Baseiter::new(self.base.ptr, self.base.dim, self.base.strides) },
+            inner_dim: self.chunk,
+            inner_strides: self.inner_strides,
+        }
+    }
+}
+
+impl<'a, A, D> IntoIterator for ExactChunksMut<'a, A, D>
+    where D: Dimension
+{
+    type Item = ArrayViewMut<'a, A, D>;
+    type IntoIter = ExactChunksIterMut<'a, A, D>;
+    fn into_iter(self) -> Self::IntoIter {
+        ExactChunksIterMut {
+            iter: unsafe { Baseiter::new(self.base.ptr, self.base.dim, self.base.strides) },
+            inner_dim: self.chunk,
+            inner_strides: self.inner_strides,
+        }
+    }
+}
+
+unsafe impl<'a, A, D> Send for WindowsIter<'a, A, D> where A: Sync, D: Send { }
+unsafe impl<'a, A, D> Sync for WindowsIter<'a, A, D> where A: Sync, D: Sync { }
+unsafe impl<'a, A, D> Send for ExactChunksIterMut<'a, A, D> where A: Send, D: Send { }
+unsafe impl<'a, A, D> Sync for ExactChunksIterMut<'a, A, D> where A: Sync, D: Sync { }
+
+macro_rules! windows_producer_impl {
+    ($name:ident, $view:ident, $inner_dim:ident, $inner_strides:ident) => {
+impl<'a, A, D> NdProducer for $name<'a, A, D>
+    where D: Dimension,
+{
+    type Item = $view<'a, A, D>;
+    type Dim = D;
+    type Ptr = *mut A;
+    type Stride = isize;
+
+    fn layout(&self) -> Layout {
+        self.base.layout()
+    }
+
+    fn raw_dim(&self) -> D {
+        self.base.dim.clone()
+    }
+
+    fn as_ptr(&self) -> *mut A {
+        self.base.ptr
+    }
+
+    #[inline(always)]
+    unsafe fn as_ref(&self, ptr: *mut A) -> Self::Item {
+        $view::new_(ptr, self.$inner_dim.clone(), self.$inner_strides.clone())
+    }
+
+    #[inline(always)]
+    unsafe fn uget_ptr(&self, index: &D) -> *mut A {
+        self.base.uget_ptr(index)
+    }
+
+    fn stride_of(&self, axis: Axis) -> isize {
+        self.base.stride_of(axis)
+    }
+
+    #[inline(always)]
+    fn contiguous_stride(&self) -> isize {
+        self.base.contiguous_stride()
+    }
+
+    fn split_at(self, axis: Axis, index: Ix) -> (Self, Self) {
+        self.split_at(axis, index)
+    }
+}
+    }
+}
+
+windows_producer_impl!(Windows, ArrayView, window, window_strides);
+windows_producer_impl!(ExactChunks, ArrayView, chunk, inner_strides);
+windows_producer_impl!(ExactChunksMut, ArrayViewMut, chunk, inner_strides);
@@ -0,0 +1,930 @@
+// Copyright 2016 bluss and ndarray developers.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Lock step traversal of several arrays (the `Zip` producer).
+
+use imp_prelude::*;
+
+/// Memory layout flags of a producer (or of several producers together).
+///
+/// ***Internal type, see `NdProducer`.***
+#[doc(hidden)]
+#[derive(Copy, Clone, Debug)]
+pub struct Layout(u32);
+
+const CORDER: u32 = 0b01;
+const FORDER: u32 = 0b10;
+const CPREFER: u32 = 0b0100;
+const FPREFER: u32 = 0b1000;
+
+impl Layout {
+    #[inline(always)]
+    fn is(self, flag: u32) -> bool {
+        self.0 & flag != 0
+    }
+
+    /// Layout flags that hold for both `self` and `other`
+    #[inline(always)]
+    fn and(self, other: Layout) -> Layout {
+        Layout(self.0 & other.0)
+    }
+
+    /// A positive value if the producer prefers C order traversal,
+    /// negative if it prefers F order.
+    #[inline(always)]
+    fn tendency(self) -> i32 {
+        (self.is(CORDER) as i32 - self.is(FORDER) as i32) +
+        (self.is(CPREFER) as i32 - self.is(FPREFER) as i32)
+    }
+}
+
+/// Return `true` if the (length, stride) pairs, from the fastest varying
+/// axis and outwards, describe a contiguous block of memory.
+fn is_contiguous<'a, I>(axes: I) -> bool
+    where I: Iterator<Item=(&'a Ix, &'a Ix)>
+{
+    let mut expected = 1;
+    for (&d, &s) in axes {
+        // an axis of length 1 can have any stride
+        if d != 1 && s as Ixs != expected {
+            return false;
+        }
+        expected *= d as Ixs;
+    }
+    true
+}
+
+/// Compute the layout flags of an array with dimension `dim` and `strides`.
+fn array_layout<D: Dimension>(dim: &D, strides: &D) -> Layout {
+    let dim = dim.slice();
+    let strides = strides.slice();
+    let n = dim.len();
+    let is_c = is_contiguous(dim.iter().zip(strides).rev());
+    let is_f = is_contiguous(dim.iter().zip(strides));
+    if is_c && is_f {
+        Layout(CORDER | FORDER | CPREFER | FPREFER)
+    } else if is_c {
+        Layout(CORDER | CPREFER)
+    } else if is_f {
+        Layout(FORDER | FPREFER)
+    } else if n > 1 && dim[0] > 1 && dim[n - 1] > 1 {
+        let first = (strides[0] as Ixs).abs();
+        let last = (strides[n - 1] as Ixs).abs();
+        if first < last {
+            Layout(FPREFER)
+        } else if last < first {
+            Layout(CPREFER)
+        } else {
+            Layout(0)
+        }
+    } else {
+        Layout(0)
+    }
+}
+
+/// A pointer-like value that can be moved along an axis.
+///
+/// ***Internal trait, see `NdProducer`.***
+#[doc(hidden)]
+pub trait Offset : Clone {
+    type Stride: Copy;
+    unsafe fn stride_offset(self, stride: Self::Stride, index: usize) -> Self;
+}
+
+impl<T> Offset for *const T {
+    type Stride = isize;
+    #[inline(always)]
+    unsafe fn stride_offset(self, stride: isize, index: usize) -> Self {
+        self.offset(stride * index as isize)
+    }
+}
+
+impl<T> Offset for *mut T {
+    type Stride = isize;
+    #[inline(always)]
+    unsafe fn stride_offset(self, stride: isize, index: usize) -> Self {
+        self.offset(stride * index as isize)
+    }
+}
+
+/// The “pointer” of the index producer: the current index itself.
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct IndexPtr<D> {
+    index: D,
+}
+
+impl<D: Dimension> Offset for IndexPtr<D> {
+    // the stride is the axis that the index moves along
+    type Stride = usize;
+    #[inline(always)]
+    unsafe fn stride_offset(mut self, axis: usize, index: usize) -> Self {
+        self.index.slice_mut()[axis] += index;
+        self
+    }
+}
+
+/// A producer of an *n*-dimensional set of elements, for example
+/// an array view, a mutable array view or the index producer.
+///
+/// Producers are used as the arguments of [`Zip`](struct.Zip.html).
+///
+/// ***Don't implement or call the hidden methods in this trait, its
+/// interface is internal to the crate and will evolve at will.***
+pub trait NdProducer {
+    /// The element produced per iteration.
+    type Item;
+    /// Dimension type of the producer
+    type Dim: Dimension;
+    #[doc(hidden)]
+    type Ptr: Offset<Stride=Self::Stride>;
+    #[doc(hidden)]
+    type Stride: Copy;
+
+    #[doc(hidden)]
+    fn layout(&self) -> Layout;
+    #[doc(hidden)]
+    fn raw_dim(&self) -> Self::Dim;
+    #[doc(hidden)]
+    fn as_ptr(&self) -> Self::Ptr;
+    #[doc(hidden)]
+    unsafe fn as_ref(&self, ptr: Self::Ptr) -> Self::Item;
+    #[doc(hidden)]
+    unsafe fn uget_ptr(&self, index: &Self::Dim) -> Self::Ptr;
+    #[doc(hidden)]
+    fn stride_of(&self, axis: Axis) -> Self::Stride;
+    #[doc(hidden)]
+    fn contiguous_stride(&self) -> Self::Stride;
+    #[doc(hidden)]
+    fn split_at(self, axis: Axis, index: Ix) -> (Self, Self) where Self: Sized;
+}
+
+/// Argument conversion into a producer.
+///
+/// Slices and vectors can be used (equivalent to 1-dimensional array views),
+/// as well as `&ArrayBase` (read-only view) and `&mut ArrayBase`
+/// (read-write view) and the producers themselves.
+pub trait IntoNdProducer {
+    /// The element produced per iteration.
+    type Item;
+    /// Dimension type of the producer
+    type Dim: Dimension;
+    type Output: NdProducer<Dim=Self::Dim, Item=Self::Item>;
+    /// Convert the value into an `NdProducer`.
+    fn into_producer(self) -> Self::Output;
+}
+
+impl<P> IntoNdProducer for P where P: NdProducer {
+    type Item = P::Item;
+    type Dim = P::Dim;
+    type Output = Self;
+    fn into_producer(self) -> Self::Output { self }
+}
+
+impl<'a, A: 'a, S, D> IntoNdProducer for &'a ArrayBase<S, D>
+    where S: Data<Elem=A>,
+          D: Dimension,
+{
+    type Item = &'a A;
+    type Dim = D;
+    type Output = ArrayView<'a, A, D>;
+    fn into_producer(self) -> Self::Output {
+        self.view()
+    }
+}
+
+impl<'a, A: 'a, S, D> IntoNdProducer for &'a mut ArrayBase<S, D>
+    where S: DataMut<Elem=A>,
+          D: Dimension,
+{
+    type Item = &'a mut A;
+    type Dim = D;
+    type Output = ArrayViewMut<'a, A, D>;
+    fn into_producer(self) -> Self::Output {
+        self.view_mut()
+    }
+}
+
+impl<'a, A: 'a> IntoNdProducer for &'a [A] {
+    type Item = &'a A;
+    type Dim = Ix;
+    type Output = ArrayView<'a, A, Ix>;
+    fn into_producer(self) -> Self::Output {
+        ArrayView::from(self)
+    }
+}
+
+impl<'a, A: 'a> IntoNdProducer for &'a mut [A] {
+    type Item = &'a mut A;
+    type Dim = Ix;
+    type Output = ArrayViewMut<'a, A, Ix>;
+    fn into_producer(self) -> Self::Output {
+        ArrayViewMut::from(self)
+    }
+}
+
+impl<'a, A: 'a> IntoNdProducer for &'a Vec<A> {
+    type Item = &'a A;
+    type Dim = Ix;
+    type Output = ArrayView<'a, A, Ix>;
+    fn into_producer(self) -> Self::Output {
+        ArrayView::from(self)
+    }
+}
+
+impl<'a, A: 'a> IntoNdProducer for &'a mut Vec<A> {
+    type Item = &'a mut A;
+    type Dim = Ix;
+    type Output = ArrayViewMut<'a, A, Ix>;
+    fn into_producer(self) -> Self::Output {
+        ArrayViewMut::from(self)
+    }
+}
+
+macro_rules! array_producer_impl {
+    ($array:ident, $item:ty, $as_ref:ident) => {
+impl<'a, A, D> NdProducer for $array<'a, A, D>
+    where D: Dimension,
+{
+    type Item = $item;
+    type Dim = D;
+    type Ptr = *mut A;
+    type Stride = isize;
+
+    fn layout(&self) -> Layout {
+        array_layout(&self.dim, &self.strides)
+    }
+
+    fn raw_dim(&self) -> D {
+        self.dim.clone()
+    }
+
+    fn as_ptr(&self) -> *mut A {
+        self.ptr
+    }
+
+    #[inline(always)]
+    unsafe fn as_ref(&self, ptr: *mut A) -> Self::Item {
+        $as_ref(ptr)
+    }
+
+    #[inline(always)]
+    unsafe fn uget_ptr(&self, index: &D) -> *mut A {
+        self.ptr.offset(D::stride_offset(index, &self.strides))
+    }
+
+    fn stride_of(&self, axis: Axis) -> isize {
+        self.strides()[axis.axis()]
+    }
+
+    #[inline(always)]
+    fn contiguous_stride(&self) -> isize {
+        1
+    }
+
+    fn split_at(self, axis: Axis, index: Ix) -> (Self, Self) {
+        self.split_at(axis, index)
+    }
+}
+    }
+}
+
+#[inline(always)]
+unsafe fn ptr_as_ref<'a, A>(ptr: *mut A) -> &'a A { &*ptr }
+#[inline(always)]
+unsafe fn ptr_as_mut<'a, A>(ptr: *mut A) -> &'a mut A { &mut *ptr }
+
+array_producer_impl!(ArrayView, &'a A, ptr_as_ref);
+array_producer_impl!(ArrayViewMut, &'a mut A, ptr_as_mut);
+
+/// A producer of the indices of an array shape.
+///
+/// Iterator element type is `D`.
+///
+/// See [`indices()`](fn.indices.html) and
+/// [`Zip::indexed()`](struct.Zip.html#method.indexed).
+#[derive(Clone)]
+pub struct Indices<D> {
+    start: D,
+    dim: D,
+}
+
+/// Create a producer of all the indices of an array with shape `shape`.
+///
+/// ```
+/// use ndarray::{indices, Zip, OwnedArray};
+///
+/// let mut a = OwnedArray::zeros((2, 3));
+/// Zip::from(&mut a).and(indices((2, 3))).apply(|x, (i, j)| {
+///     *x = 10 * i + j;
+/// });
+/// assert_eq!(a[(1, 2)], 12);
+/// ```
+pub fn indices<D>(shape: D) -> Indices<D>
+    where D: Dimension,
+{
+    Indices {
+        start: D::zero_index_with_ndim(shape.ndim()),
+        dim: shape,
+    }
+}
+
+impl<D> NdProducer for Indices<D>
+    where D: Dimension,
+{
+    type Item = D;
+    type Dim = D;
+    type Ptr = IndexPtr<D>;
+    type Stride = usize;
+
+    fn layout(&self) -> Layout {
+        Layout(0)
+    }
+
+    fn raw_dim(&self) -> D {
+        self.dim.clone()
+    }
+
+    fn as_ptr(&self) -> IndexPtr<D> {
+        IndexPtr { index: self.start.clone() }
+    }
+
+    #[inline(always)]
+    unsafe fn as_ref(&self, ptr: IndexPtr<D>) -> D {
+        ptr.index
+    }
+
+    #[inline(always)]
+    unsafe fn uget_ptr(&self, index: &D) -> IndexPtr<D> {
+        let mut start = self.start.clone();
+        for (s, &i) in start.slice_mut().iter_mut().zip(index.slice()) {
+            *s += i;
+        }
+        IndexPtr { index: start }
+    }
+
+    fn stride_of(&self, axis: Axis) -> usize {
+        axis.axis()
+    }
+
+    #[inline(always)]
+    fn contiguous_stride(&self) -> usize {
+        // the index producer is never contiguous
+        0
+    }
+
+    fn split_at(self, axis: Axis, index: Ix) -> (Self, Self) {
+        let mut left_dim = self.dim.clone();
+        let mut right_start = self.start.clone();
+        let mut right_dim = self.dim;
+        assert!(index <= left_dim.axis(axis));
+        left_dim.set_axis(axis, index);
+        let start_ix = right_start.axis(axis);
+        right_start.set_axis(axis, start_ix + index);
+        let right_len = right_dim.axis(axis) - index;
+        right_dim.set_axis(axis, right_len);
+        (Indices { start: self.start, dim: left_dim },
+         Indices { start: right_start, dim: right_dim })
+    }
+}
+
+/// Value controlling the execution of `.fold_while` on `Zip`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FoldWhile<T> {
+    /// Continue folding with this value
+    Continue(T),
+    /// Fold is complete and will return this value
+    Done(T),
+}
+
+impl<T> FoldWhile<T> {
+    /// Return the inner value
+    pub fn into_inner(self) -> T {
+        match self {
+            FoldWhile::Continue(x) | FoldWhile::Done(x) => x
+        }
+    }
+
+    /// Return true if it is `Done`, false if `Continue`
+    pub fn is_done(&self) -> bool {
+        match *self {
+            FoldWhile::Continue(_) => false,
+            FoldWhile::Done(_) => true,
+        }
+    }
+}
+
+macro_rules! fold_while {
+    ($e:expr) => {
+        match $e {
+            FoldWhile::Continue(x) => x,
+            x => return x,
+        }
+    }
+}
+
+/// Lock step function application across several arrays or other producers.
+///
+/// `Zip` allows matching several producers to each other elementwise and
+/// applying a function over all tuples of elements (one item from each input
+/// at a time).
+///
+/// In general, the zip uses a tuple of producers
+/// ([`NdProducer`](trait.NdProducer.html) trait) that all have to be of the
+/// same shape. The NdProducer implementation defines what its item type is
+/// (for example if it's a shared reference, mutable reference or an array
+/// view etc).
+///
+/// Up to six arrays or array views can be zipped, as well as the index
+/// producer (use `Zip::indexed` or [`indices()`](fn.indices.html)).
+///
+/// The order elements are visited is not specified. The producers don’t have
+/// to have the same item type. `Zip` picks the traversal order from the
+/// memory layout of its inputs: if all of them are contiguous in the same
+/// order, it loops over them as flat slices; otherwise it iterates along the
+/// axis that is most likely to be contiguous in the innermost loop.
+///
+/// ```
+/// use ndarray::{Zip, OwnedArray};
+///
+/// type M = OwnedArray<f64, (usize, usize)>;
+///
+/// let mut a = M::zeros((16, 16));
+/// let mut b = M::zeros(a.dim());
+/// let mut c = M::zeros(a.dim());
+///
+/// // some code to populate b, c
+/// b.assign_scalar(&1.);
+/// c.assign_scalar(&2.);
+///
+/// // Compute a simple ternary operation:
+/// // elementwise addition of b and c, stored in a
+/// Zip::from(&mut a).and(&b).and(&c).apply(|a, &b, &c| {
+///     *a = b + c;
+/// });
+///
+/// assert_eq!(a, &b + &c);
+///
+/// // Collect the results into a new array
+/// let d = Zip::from(&b).and(&c).map_collect(|&b, &c| b * c);
+/// assert_eq!(d, &b * &c);
+/// ```
+#[derive(Clone)]
+pub struct Zip<Parts, D> {
+    parts: Parts,
+    dimension: D,
+    layout: Layout,
+    /// The sum of the layout tendencies of the parts;
+    /// positive for C preference, negative for F preference.
+    layout_tendency: i32,
+}
+
+impl<P, D> Zip<(P,), D>
+    where D: Dimension,
+          P: NdProducer<Dim=D>,
+{
+    /// Create a new `Zip` from the input array or other producer `p`.
+    ///
+    /// The Zip will take the exact dimension of `p` and all inputs
+    /// must have the same dimensions; inputs are not broadcast
+    /// automatically, use [`.broadcast()`](struct.ArrayBase.html#method.broadcast)
+    /// to adapt an array’s shape first.
+    pub fn from<IP>(p: IP) -> Self
+        where IP: IntoNdProducer<Dim=D, Output=P, Item=P::Item>
+    {
+        let array = p.into_producer();
+        let dim = array.raw_dim();
+        let layout = array.layout();
+        Zip {
+            dimension: dim,
+            layout: layout,
+            layout_tendency: layout.tendency(),
+            parts: (array,),
+        }
+    }
+}
+
+impl<P, D> Zip<(Indices<D>, P), D>
+    where D: Dimension,
+          P: NdProducer<Dim=D>,
+{
+    /// Create a new `Zip` with an index producer and the producer `p`.
+    ///
+    /// The Zip will take the exact dimension of `p` and all inputs
+    /// must have the same dimensions; inputs are not broadcast
+    /// automatically, use [`.broadcast()`](struct.ArrayBase.html#method.broadcast)
+    /// to adapt an array’s shape first.
+    ///
+    /// *Note:* Indexed zip has overhead.
+    ///
+    /// ```
+    /// use ndarray::{Zip, OwnedArray};
+    ///
+    /// let mut a = OwnedArray::zeros((3, 3));
+    /// Zip::indexed(&mut a).apply(|(i, j), x| *x = i * j);
+    /// assert_eq!(a[(2, 2)], 4);
+    /// ```
+    pub fn indexed<IP>(p: IP) -> Self
+        where IP: IntoNdProducer<Dim=D, Output=P, Item=P::Item>
+    {
+        let array = p.into_producer();
+        let dim = array.raw_dim();
+        let layout = array.layout();
+        Zip {
+            parts: (indices(dim.clone()), array),
+            dimension: dim,
+            layout: Layout(0),
+            layout_tendency: layout.tendency(),
+        }
+    }
+}
+
+impl<Parts, D> Zip<Parts, D>
+    where D: Dimension,
+{
+    fn check<P>(&self, part: &P)
+        where P: NdProducer<Dim=D>
+    {
+        let part_dim = part.raw_dim();
+        if self.dimension.slice() != part_dim.slice() {
+            zip_shape_panic(&self.dimension, &part_dim);
+        }
+    }
+
+    /// Return the number of elements in the zip.
+    pub fn size(&self) -> usize {
+        self.dimension.size()
+    }
+
+    /// Return `true` if the elements are visited in “f” (column major)
+    /// order. Otherwise, they are visited in “c” (row major) order.
+    fn prefer_f(&self) -> bool {
+        !self.layout.is(CORDER) &&
+            (self.layout.is(FORDER) || self.layout_tendency < 0)
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn zip_shape_panic<D: Dimension>(expected: &D, found: &D) -> ! {
+    panic!("ndarray: Zip: producer shape {:?} does not match the shape {:?}",
+           found.slice(), expected.slice())
+}
+
+/// Compute the next index in “f” (column major) order.
+#[inline]
+fn next_for_f<D: Dimension>(dim: &D, mut index: D) -> Option<D> {
+    for (&d, ix) in dim.slice().iter().zip(index.slice_mut()) {
+        *ix += 1;
+        if *ix == d {
+            *ix = 0;
+        } else {
+            return Some(index);
+        }
+    }
+    None
+}
+
+impl<P, D> Zip<P, D>
+    where D: Dimension,
+          P: ZippableTuple<Dim=D>,
+{
+    fn apply_core<F, Acc>(&mut self, acc: Acc, function: F) -> FoldWhile<Acc>
+        where F: FnMut(Acc, P::Item) -> FoldWhile<Acc>
+    {
+        if self.dimension.ndim() == 0 {
+            let mut function = function;
+            function(acc, unsafe { self.parts.as_ref(self.parts.as_ptr()) })
+        } else if self.layout.is(CORDER | FORDER) {
+            self.apply_core_contiguous(acc, function)
+        } else {
+            self.apply_core_strided(acc, function)
+        }
+    }
+
+    fn apply_core_contiguous<F, Acc>(&mut self, mut acc: Acc, mut function: F)
+        -> FoldWhile<Acc>
+        where F: FnMut(Acc, P::Item) -> FoldWhile<Acc>
+    {
+        debug_assert!(self.layout.is(CORDER | FORDER));
+        let size = self.dimension.size();
+        let ptrs = self.parts.as_ptr();
+        let inner_strides = self.parts.contiguous_stride();
+        for i in 0..size {
+            unsafe {
+                let ptr_i = ptrs.clone().stride_offset(inner_strides, i);
+                acc = fold_while!(function(acc, self.parts.as_ref(ptr_i)));
+            }
+        }
+        FoldWhile::Continue(acc)
+    }
+
+    fn apply_core_strided<F, Acc>(&mut self, mut acc: Acc, mut function: F)
+        -> FoldWhile<Acc>
+        where F: FnMut(Acc, P::Item) -> FoldWhile<Acc>
+    {
+        let n = self.dimension.ndim();
+        let prefer_f = self.prefer_f();
+        // the axis of the innermost loop
+        let unroll_axis = if prefer_f { 0 } else { n - 1 };
+        let inner_len = self.dimension.slice()[unroll_axis];
+        let mut dim = self.dimension.clone();
+        dim.slice_mut()[unroll_axis] = 1;
+        let inner_strides = self.parts.stride_of(unroll_axis);
+        let mut index_ = dim.first_index();
+        while let Some(index) = index_ {
+            unsafe {
+                let ptr = self.parts.uget_ptr(&index);
+                for i in 0..inner_len {
+                    let p = ptr.clone().stride_offset(inner_strides, i);
+                    acc = fold_while!(function(acc, self.parts.as_ref(p)));
+                }
+            }
+            index_ = if prefer_f {
+                next_for_f(&dim, index)
+            } else {
+                dim.next_for(index)
+            };
+        }
+        FoldWhile::Continue(acc)
+    }
+}
+
+/// ***Internal trait, see `Zip`.***
+#[doc(hidden)]
+pub trait OffsetTuple {
+    type Args: Copy;
+    unsafe fn stride_offset(self, stride: Self::Args, index: usize) -> Self;
+}
+
+/// ***Internal trait, see `Zip`.***
+#[doc(hidden)]
+pub trait ZippableTuple : Sized {
+    type Item;
+    type Ptr: OffsetTuple<Args=Self::Stride> + Clone;
+    type Dim: Dimension;
+    type Stride: Copy;
+    fn as_ptr(&self) -> Self::Ptr;
+    unsafe fn as_ref(&self, ptr: Self::Ptr) -> Self::Item;
+    unsafe fn uget_ptr(&self, index: &Self::Dim) -> Self::Ptr;
+    fn stride_of(&self, axis: usize) -> Self::Stride;
+    fn contiguous_stride(&self) -> Self::Stride;
+    fn split_at(self, axis: Axis, index: usize) -> (Self, Self);
+}
+
+macro_rules! offset_impl {
+    ($([$($param:ident)*][ $($q:ident)*],)+) => {
+        $(
+        #[allow(non_snake_case)]
+        impl<$($param: Offset),*> OffsetTuple for ($($param, )*) {
+            type Args = ($($param::Stride,)*);
+            #[inline(always)]
+            unsafe fn stride_offset(self, stride: Self::Args, index: usize) -> Self {
+                let ($($param, )*) = self;
+                let ($($q, )*) = stride;
+                ($(Offset::stride_offset($param, $q, index),)*)
+            }
+        }
+        )+
+    }
+}
+
+offset_impl!{
+    [A ][ a],
+    [A B][ a b],
+    [A B C][ a b c],
+    [A B C D][ a b c d],
+    [A B C D E][ a b c d e],
+    [A B C D E F][ a b c d e f],
+    [A B C D E F G][ a b c d e f g],
+}
+
+macro_rules! zipt_impl {
+    ($([$($p:ident)*][ $($q:ident)*],)+) => {
+        $(
+        #[allow(non_snake_case)]
+        impl<Dim: Dimension, $($p: NdProducer<Dim=Dim>),*> ZippableTuple for ($($p, )*) {
+            type Item = ($($p::Item, )*);
+            type Ptr = ($($p::Ptr, )*);
+            type Dim = Dim;
+            type Stride = ($($p::Stride,)* );
+
+            fn stride_of(&self, axis: usize) -> Self::Stride {
+                let ($(ref $p,)*) = *self;
+                ($($p.stride_of(Axis(axis)), )*)
+            }
+
+            #[inline(always)]
+            fn contiguous_stride(&self) -> Self::Stride {
+                let ($(ref $p,)*) = *self;
+                ($($p.contiguous_stride(), )*)
+            }
+
+            #[inline(always)]
+            fn as_ptr(&self) -> Self::Ptr {
+                let ($(ref $p,)*) = *self;
+                ($($p.as_ptr(), )*)
+            }
+
+            #[inline(always)]
+            unsafe fn as_ref(&self, ptr: Self::Ptr) -> Self::Item {
+                let ($(ref $q ,)*) = *self;
+                let ($($p,)*) = ptr;
+                ($($q.as_ref($p),)*)
+            }
+
+            #[inline(always)]
+            unsafe fn uget_ptr(&self, index: &Self::Dim) -> Self::Ptr {
+                let ($(ref $p,)*) = *self;
+                ($($p.uget_ptr(index), )*)
+            }
+
+            fn split_at(self, axis: Axis, index: Ix) -> (Self, Self) {
+                let ($($p,)*) = self;
+                let ($($p,)*) = (
+                    $($p.split_at(axis, index), )*
+                );
+                (
+                    ($($p.0,)*),
+                    ($($p.1,)*)
+                )
+            }
+        }
+        )+
+    }
+}
+
+zipt_impl!{
+    [A ][ a],
+    [A B][ a b],
+    [A B C][ a b c],
+    [A B C D][ a b c d],
+    [A B C D E][ a b c d e],
+    [A B C D E F][ a b c d e f],
+    [A B C D E F G][ a b c d e f g],
+}
+
+macro_rules! expand_if {
+    (@bool [true] $($body:tt)*) => { $($body)* };
+    (@bool [false] $($body:tt)*) => { };
+}
+
+macro_rules! map_impl {
+    ($([$notlast:ident $($p:ident)*],)+) => {
+        $(
+        #[allow(non_snake_case)]
+        impl<D, $($p),*> Zip<($($p,)*), D>
+            where D: Dimension,
+                  $($p: NdProducer<Dim=D> ,)*
+        {
+            /// Apply a function to all elements of the input arrays,
+            /// visiting elements in lock step.
+            pub fn apply<F>(mut self, mut function: F)
+                where F: FnMut($($p::Item),*)
+            {
+                self.apply_core((), move |(), args| {
+                    let ($($p,)*) = args;
+                    FoldWhile::Continue(function($($p),*))
+                });
+            }
+
+            /// Apply a fold function to all elements of the input arrays,
+            /// visiting elements in lock step.
+            ///
+            /// The fold continues while the return value is a
+            /// `FoldWhile::Continue`.
+            ///
+            /// ```
+            /// use ndarray::{Zip, FoldWhile, arr1};
+            ///
+            /// let a = arr1(&[1, 2, 3, 4]);
+            /// let b = arr1(&[1, 2, 5, 4]);
+            ///
+            /// // find the first position where the arrays differ
+            /// let first_difference = Zip::indexed(&a).and(&b).fold_while(None,
+            ///     |acc, i, x, y| if x != y {
+            ///         FoldWhile::Done(Some(i))
+            ///     } else {
+            ///         FoldWhile::Continue(acc)
+            ///     });
+            /// assert_eq!(first_difference.into_inner(), Some(2));
+            /// ```
+            pub fn fold_while<F, Acc>(mut self, acc: Acc, mut function: F)
+                -> FoldWhile<Acc>
+                where F: FnMut(Acc, $($p::Item),*) -> FoldWhile<Acc>
+            {
+                self.apply_core(acc, move |acc, args| {
+                    let ($($p,)*) = args;
+                    function(acc, $($p),*)
+                })
+            }
+
+            /// Apply a function to all elements of the input arrays,
+            /// visiting elements in lock step, and collect the return
+            /// values into a new array with the same shape as the inputs.
+            ///
+            /// The memory order of the result is the order the
+            /// elements were visited in.
+            pub fn map_collect<R, F>(mut self, mut function: F) -> OwnedArray<R, D>
+                where F: FnMut($($p::Item),*) -> R
+            {
+                let dim = self.dimension.clone();
+                let prefer_f = self.prefer_f();
+                let mut result = Vec::with_capacity(self.size());
+                self.apply_core((), |(), args| {
+                    let ($($p,)*) = args;
+                    result.push(function($($p),*));
+                    FoldWhile::Continue(())
+                });
+                unsafe {
+                    if prefer_f {
+                        ArrayBase::from_vec_dim_unchecked_f(dim, result)
+                    } else {
+                        ArrayBase::from_vec_dim_unchecked(dim, result)
+                    }
+                }
+            }
+
+            expand_if!(@bool [$notlast]
+
+            /// Include the producer `p` in the Zip.
+            ///
+            /// ***Panics*** if `p`’s shape doesn’t match the Zip’s exactly.
+            pub fn and<P>(self, p: P) -> Zip<($($p,)* P::Output, ), D>
+                where P: IntoNdProducer<Dim=D>,
+            {
+                let array = p.into_producer();
+                self.check(&array);
+                let part_layout = array.layout();
+                let ($($p,)*) = self.parts;
+                Zip {
+                    parts: ($($p,)* array, ),
+                    layout: self.layout.and(part_layout),
+                    layout_tendency: self.layout_tendency + part_layout.tendency(),
+                    dimension: self.dimension,
+                }
+            }
+            );
+
+            /// Split the `Zip` evenly in two.
+            ///
+            /// It will be split in the way that best preserves element locality.
+            ///
+            /// ***Panics*** if the `Zip` has fewer than two elements.
+            pub fn split(self) -> (Self, Self) {
+                assert!(self.size() > 1);
+                let axis = self.max_stride_axis();
+                let index = self.dimension.axis(axis) / 2;
+                let (p1, p2) = self.parts.split_at(axis, index);
+                let (mut d1, mut d2) = (self.dimension.clone(), self.dimension);
+                d1.set_axis(axis, index);
+                let right_len = d2.axis(axis) - index;
+                d2.set_axis(axis, right_len);
+                (Zip {
+                    dimension: d1,
+                    layout: self.layout,
+                    layout_tendency: self.layout_tendency,
+                    parts: p1,
+                 },
+                 Zip {
+                    dimension: d2,
+                    layout: self.layout,
+                    layout_tendency: self.layout_tendency,
+                    parts: p2,
+                 })
+            }
+        }
+        )+
+    }
+}
+
+map_impl!{
+    [true P1],
+    [true P1 P2],
+    [true P1 P2 P3],
+    [true P1 P2 P3 P4],
+    [true P1 P2 P3 P4 P5],
+    [true P1 P2 P3 P4 P5 P6],
+    [false P1 P2 P3 P4 P5 P6 P7],
+}
+
+impl<Parts, D> Zip<Parts, D>
+    where D: Dimension,
+{
+    /// The axis to split along: the outermost axis in the traversal
+    /// order that is longer than one element.
+    fn max_stride_axis(&self) -> Axis {
+        let n = self.dimension.ndim();
+        let dim = self.dimension.slice();
+        if self.prefer_f() {
+            Axis((0..n).rev().find(|&i| dim[i] > 1).unwrap_or(n - 1))
+        } else {
+            Axis((0..n).find(|&i| dim[i] > 1).unwrap_or(0))
+        }
+    }
+}
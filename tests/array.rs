@@ -4,8 +4,15 @@
 extern crate ndarray;
 extern crate itertools;
 
-use ndarray::{RcArray, S, Si,
+use std::thread;
+
+use ndarray::{RcArray, S, Si, rcarr1,
+    ArcArray,
     OwnedArray,
+    ArrayView,
+    ArrayViewMut,
+    RawArrayView,
+    RawArrayViewMut,
 };
 use ndarray::{
     rcarr2,
@@ -175,6 +182,65 @@ fn test_negative_stride_rcarray()
     }
 }
 
+#[test]
+fn test_invert_axis() {
+    let mut a = arr3(&[[[1, 2], [3, 4], [5, 6]],
+                       [[7, 8], [9, 10], [11, 12]]]);
+    let b = a.slice(s![.., ..;-1, ..]).to_owned();
+    a.invert_axis(Axis(1));
+    assert_eq!(a, b);
+    assert_eq!(a[[0, 0, 0]], 5);
+    assert_eq!(a.strides(), &[6, -2, 1]);
+    a.invert_axis(Axis(1));
+    assert_eq!(a[[0, 0, 0]], 1);
+
+    let mut e = OwnedArray::<f32, _>::zeros((0, 3));
+    e.invert_axis(Axis(0));
+    e.invert_axis(Axis(1));
+    assert_eq!(e.len(), 0);
+}
+
+#[test]
+fn test_negative_stride_empty_slice() {
+    let a = OwnedArray::<f32, _>::zeros((0, 3));
+    let b = a.slice(s![..;-1, ..;-1]);
+    assert_eq!(b.dim(), (0, 3));
+    assert_eq!(b.iter().count(), 0);
+
+    let c = arr1(&[1, 2, 3]);
+    let d = c.slice(s![1..1;-1]);
+    assert_eq!(d.len(), 0);
+}
+
+#[test]
+fn test_negative_stride_memory_order() {
+    let mut a = arr2(&[[1, 2, 3],
+                       [4, 5, 6]]);
+    a.invert_axis(Axis(0));
+    a.invert_axis(Axis(1));
+    assert_eq!(a, arr2(&[[6, 5, 4],
+                         [3, 2, 1]]));
+    assert_eq!(a.as_slice(), None);
+    assert_eq!(a.as_slice_memory_order(), Some(&[1, 2, 3, 4, 5, 6][..]));
+    a.as_slice_memory_order_mut().unwrap()[0] = 0;
+    assert_eq!(a[(1, 2)], 0);
+
+    // elementwise operations keep the layout
+    let b = a.map(|&x| x * 2);
+    assert_eq!(b, arr2(&[[12, 10, 8],
+                         [6, 4, 0]]));
+    assert_eq!(b.strides(), a.strides());
+    let c = a.to_owned();
+    assert_eq!(c, a);
+    assert_eq!(a.fold(0, |acc, &x| acc + x), 20);
+
+    let rev = arr1(&[1, 2, 3]);
+    let rev = rev.slice(s![..;-1]);
+    assert_eq!(rev.as_slice_memory_order(), Some(&[1, 2, 3][..]));
+    let rev2 = rev.slice(s![..;2]);
+    assert_eq!(rev2.as_slice_memory_order(), None);
+}
+
 #[test]
 fn test_cow()
 {
@@ -208,6 +274,51 @@ fn test_cow()
     assert_eq!(before[3], 1);
 }
 
+#[test]
+fn test_cow_arc()
+{
+    let mut mat = ArcArray::zeros((2, 2));
+    mat[[0, 0]] = 1;
+    let n = mat.clone();
+    mat[[0, 1]] = 2;
+    assert_eq!(mat, arr2(&[[1, 2], [0, 0]]));
+    assert_eq!(n, arr2(&[[1, 0], [0, 0]]));
+
+    // a shared, sliced array keeps its elements when converted
+    let mut rev = mat.reshape(4);
+    rev.islice(&[Si(1, None, -1)]);
+    let before = rev.clone();
+    let rc = rev.clone().into_shared();
+    assert_eq!(rc, aview1(&[0, 0, 2]));
+    let owned = before.to_owned();
+    rev[0] = 5;
+    assert_eq!(rev, aview1(&[5, 0, 2]));
+    assert_eq!(before, owned);
+    assert_eq!(rc, owned);
+}
+
+#[test]
+fn into_shared_sync()
+{
+    let a = arr2(&[[1., 2.], [3., 4.]]);
+    let ptr = a.as_ptr();
+    let shared = a.into_shared_sync();
+    // no copy when the data is uniquely owned
+    assert_eq!(shared.as_ptr(), ptr);
+
+    let rc = rcarr2(&[[1, 2], [3, 4]]).slice(&[S, Si(1, None, 1)]).to_owned().into_shared();
+    let copy = rc.clone();
+    assert_eq!(rc.into_shared_sync(), copy);
+    assert_eq!(copy.to_shared_sync(), copy);
+
+    let handles: Vec<_> = (0..2).map(|i| {
+        let a = shared.clone();
+        thread::spawn(move || a.row(i).scalar_sum())
+    }).collect();
+    let sums: Vec<f64> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(sums, vec![3., 7.]);
+}
+
 #[test]
 fn test_sub()
 {
@@ -304,6 +415,88 @@ fn sum_mean()
     assert_eq!(a.scalar_sum(), 10.);
 }
 
+#[test]
+fn product_min_max()
+{
+    let a = arr2(&[[1., 5.], [3., 2.]]);
+    assert_eq!(a.product(Axis(0)), arr1(&[3., 10.]));
+    assert_eq!(a.product(Axis(1)), arr1(&[5., 6.]));
+    assert_eq!(a.scalar_product(), 30.);
+    assert_eq!(a.t().scalar_product(), 30.);
+    assert_eq!(a.min(Axis(0)), arr1(&[1., 2.]));
+    assert_eq!(a.max(Axis(1)), arr1(&[5., 3.]));
+    assert_eq!(a.scalar_min(), Some(1.));
+    assert_eq!(a.scalar_max(), Some(5.));
+    assert_eq!(a.scalar_argmin(), Some((0, 0)));
+    assert_eq!(a.scalar_argmax(), Some((0, 1)));
+    assert_eq!(a.argmin(Axis(1)), arr1(&[0, 1]));
+    assert_eq!(a.argmax(Axis(0)), arr1(&[1, 0]));
+
+    // long, strided input to exercise the unrolled kernels
+    let mut b = OwnedArray::<f64, _>::zeros((5, 37));
+    for (i, elt) in b.iter_mut().enumerate() {
+        *elt = ((i * 13) % 29) as f64 - 7.;
+    }
+    let bs = b.slice(s![..;-1, ..;2]);
+    let min = bs.iter().cloned().fold(::std::f64::INFINITY, f64::min);
+    let max = bs.iter().cloned().fold(::std::f64::NEG_INFINITY, f64::max);
+    assert_eq!(bs.scalar_min(), Some(min));
+    assert_eq!(b.scalar_min(), Some(-7.));
+    assert_eq!(bs.scalar_max(), Some(max));
+    assert_eq!(bs[bs.scalar_argmax().unwrap()], max);
+
+    let empty = OwnedArray::<f64, _>::zeros((0, 3));
+    assert_eq!(empty.scalar_min(), None);
+    assert_eq!(empty.scalar_argmax(), None);
+    assert_eq!(empty.product(Axis(0)), arr1(&[1., 1., 1.]));
+    assert_eq!(empty.scalar_product(), 1.);
+}
+
+#[test]
+fn min_max_nan()
+{
+    use std::f64::NAN;
+    let a = arr2(&[[1., NAN, 0.], [2., 3., 4.]]);
+    assert!(a.scalar_min().unwrap().is_nan());
+    assert!(a.scalar_max().unwrap().is_nan());
+    assert_eq!(a.scalar_argmin(), Some((0, 1)));
+    assert_eq!(a.scalar_argmax(), Some((0, 1)));
+    let m = a.min(Axis(1));
+    assert!(m[0].is_nan());
+    assert_eq!(m[1], 2.);
+    let m = a.max(Axis(0));
+    assert_eq!(m[0], 2.);
+    assert!(m[1].is_nan());
+    assert_eq!(a.argmax(Axis(0)), arr1(&[1, 0, 1]));
+}
+
+#[test]
+fn var_std()
+{
+    let a = arr2(&[[1., 2., 3.], [5., 8., 11.]]);
+    assert_eq!(a.var(Axis(1), 0.), arr1(&[2. / 3., 6.]));
+    assert_eq!(a.var(Axis(1), 1.), arr1(&[1., 9.]));
+    assert_eq!(a.std(Axis(1), 1.), arr1(&[1., 3.]));
+    assert_eq!(a.var(Axis(0), 0.), arr1(&[4., 9., 16.]));
+    assert_eq!(arr1(&[2., 4., 4., 4., 5., 5., 7., 9.]).scalar_std(0.), 2.);
+    assert!(a.var(Axis(0), 2.).iter().all(|x: &f64| x.is_nan()));
+    let empty = OwnedArray::<f64, _>::zeros((0, 2));
+    assert!(empty.var(Axis(0), 0.).iter().all(|x| x.is_nan()));
+    assert!(empty.scalar_var(0.).is_nan());
+}
+
+#[test]
+fn cumsum_cumprod()
+{
+    let a = arr2(&[[1, 2, 3], [4, 5, 6]]);
+    assert_eq!(a.cumsum(Axis(0)), arr2(&[[1, 2, 3], [5, 7, 9]]));
+    assert_eq!(a.cumsum(Axis(1)), arr2(&[[1, 3, 6], [4, 9, 15]]));
+    assert_eq!(a.cumprod(Axis(1)), arr2(&[[1, 2, 6], [4, 20, 120]]));
+    assert_eq!(a.t().cumsum(Axis(0)), arr2(&[[1, 4], [3, 9], [6, 15]]));
+    let empty = OwnedArray::<i32, _>::zeros((0, 2));
+    assert_eq!(empty.cumsum(Axis(0)).shape(), &[0, 2]);
+}
+
 #[test]
 fn iter_size_hint()
 {
@@ -431,6 +624,43 @@ fn owned_array_with_stride() {
     assert_eq!(a.strides(), &[1, 4, 2]);
 }
 
+#[test]
+fn owned_array_with_negative_stride() {
+    let v: Vec<_> = (0..6).collect();
+    let dim = (2, 3);
+    let strides = (3, -1isize as usize);
+
+    let a = OwnedArray::from_vec_dim_stride(dim, strides, v).unwrap();
+    assert_eq!(a.strides(), &[3, -1]);
+    assert_eq!(a, arr2(&[[2, 1, 0],
+                         [5, 4, 3]]));
+
+    // out of bounds when counting the reversed axis
+    let strides = (3, -2isize as usize);
+    assert!(OwnedArray::from_vec_dim_stride(dim, strides, (0..6).collect()).is_err());
+}
+
+#[test]
+fn view_from_slice_with_negative_stride() {
+    let s = [0, 1, 2, 3, 4, 5];
+    let a = ArrayView::from_slice_dim_stride((3, 2), (-2isize as usize, 1), &s).unwrap();
+    assert_eq!(a, arr2(&[[4, 5],
+                         [2, 3],
+                         [0, 1]]));
+
+    let mut s = [0, 1, 2, 3, 4, 5];
+    {
+        let mut a = ArrayViewMut::from_slice_dim_stride(
+            (2, 3), (-1isize as usize, -2isize as usize), &mut s).unwrap();
+        a[(0, 0)] = 10;
+        assert_eq!(a[(1, 2)], 0);
+    }
+    assert_eq!(s[5], 10);
+
+    // overlapping strides are still rejected
+    assert!(ArrayView::from_slice_dim_stride((2, 2), (-1isize as usize, 1), &s).is_err());
+}
+
 macro_rules! assert_matches {
     ($value:expr, $pat:pat) => {
         match $value {
@@ -676,6 +906,96 @@ fn transpose_view_mut() {
     assert_eq!(at, arr2(&[[1, 4], [2, 5], [3, 7]]));
 }
 
+#[test]
+fn insert_remove_axis() {
+    let a = arr2(&[[1, 2, 3],
+                   [4, 5, 6]]);
+    let b = a.view().insert_axis(Axis(1));
+    assert_eq!(b.shape(), &[2, 1, 3]);
+    assert!(b.is_standard_layout());
+    assert_eq!(b.subview(Axis(1), 0), a);
+    let c = a.view().insert_axis(Axis(2));
+    assert_eq!(c.shape(), &[2, 3, 1]);
+    assert!(c.is_standard_layout());
+    assert_eq!(c.remove_axis(Axis(2)), a);
+    assert!(a.t().insert_axis(Axis(0)).t().into_shape(6).is_ok());
+
+    let d = OwnedArray::from_elem((), 7).insert_axis(Axis(0));
+    assert_eq!(d, arr1(&[7]));
+    assert!(d.is_standard_layout());
+    let e = a.clone().into_shape(vec![2, 3]).unwrap().insert_axis(Axis(0));
+    assert_eq!(e.shape(), &[1, 2, 3]);
+    assert_eq!(e.remove_axis(Axis(0)).shape(), a.shape());
+}
+
+#[test]
+#[should_panic]
+fn remove_axis_not_length_one() {
+    let a = arr2(&[[1, 2, 3]]);
+    a.remove_axis(Axis(1));
+}
+
+#[test]
+fn squeeze() {
+    let a = OwnedArray::from_shape_fn((1, 3, 1, 2), |(_, j, _, l)| 10 * j + l);
+    let b = a.view().squeeze();
+    assert_eq!(b.shape(), &[3, 2]);
+    assert_eq!(b.iter().collect::<Vec<_>>(), a.iter().collect::<Vec<_>>());
+    assert_eq!(OwnedArray::from_elem((1, 1), 0).squeeze().ndim(), 0);
+}
+
+#[test]
+fn permuted_axes() {
+    let a = OwnedArray::from_shape_fn((2, 3, 4), |(i, j, k)| 100 * i + 10 * j + k);
+    let p = a.view().permuted_axes((1, 2, 0));
+    assert_eq!(p.shape(), &[3, 4, 2]);
+    for ((i, j, k), &x) in a.indexed_iter() {
+        assert_eq!(p[(j, k, i)], x);
+    }
+    assert_eq!(a.view().permuted_axes((2, 1, 0)), a.view().reversed_axes());
+    assert_eq!(a.view().permuted_axes((0, 1, 2)), a);
+
+    let d = a.clone().into_shape(vec![2, 3, 4]).unwrap();
+    assert_eq!(d.permuted_axes(vec![1, 2, 0]).shape(), &[3, 4, 2]);
+}
+
+#[test]
+#[should_panic]
+fn permuted_axes_repeated_axis() {
+    let a = OwnedArray::<f32, _>::zeros((2, 3, 4));
+    a.permuted_axes((1, 1, 0));
+}
+
+#[test]
+#[should_panic]
+fn permuted_axes_out_of_bounds() {
+    let a = OwnedArray::<f32, _>::zeros((2, 3));
+    a.permuted_axes((0, 2));
+}
+
+#[test]
+fn merge_axes() {
+    use ndarray::ErrorKind;
+    let a = OwnedArray::from_shape_fn((2, 3, 4), |(i, j, k)| 100 * i + 10 * j + k);
+    let elements = a.iter().cloned().collect::<Vec<_>>();
+    let m = a.view().merge_axes(Axis(0), Axis(1)).unwrap();
+    assert_eq!(m.shape(), &[6, 4]);
+    assert_eq!(m.iter().cloned().collect::<Vec<_>>(), elements);
+    let m = a.view().merge_axes(Axis(1), Axis(2)).unwrap();
+    assert_eq!(m.shape(), &[2, 12]);
+    assert_eq!(m.iter().cloned().collect::<Vec<_>>(), elements);
+
+    let err = a.view().merge_axes(Axis(0), Axis(2)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::IncompatibleLayout);
+    let err = a.view().merge_axes(Axis(2), Axis(1)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::IncompatibleLayout);
+
+    // length one axes can always be merged
+    let b = a.slice(&[Si(0, Some(1), 1), S, Si(0, None, 3)]);
+    let m = b.merge_axes(Axis(0), Axis(2)).unwrap();
+    assert_eq!(m, b.subview(Axis(0), 0));
+}
+
 #[test]
 fn reshape() {
     let data = [1, 2, 3, 4, 5, 6, 7, 8];
@@ -694,6 +1014,126 @@ fn reshape() {
                            [7, 8]]));
 }
 
+#[test]
+fn reshape_error_info() {
+    let data = [1, 2, 3, 4, 5, 6];
+    let err = aview1(&data).into_shape((4, 2)).unwrap_err();
+    assert_eq!(err.operation(), Some("into_shape"));
+    assert_eq!(err.source_shape(), Some(&[6][..]));
+    assert_eq!(err.target_shape(), Some(&[4, 2][..]));
+    assert_eq!(err.axis(), None);
+
+    let a = aview2(&[[1, 2, 3], [4, 5, 6]]);
+    let err = a.slice(&[S, Si(0, None, 2)]).into_shape(4).unwrap_err();
+    assert_eq!(err.kind(), ndarray::ErrorKind::IncompatibleLayout);
+    assert_eq!(err.to_string(),
+               "ShapeError/IncompatibleLayout: incompatible memory layout in into_shape \
+                (from shape [2, 2] to [4])");
+
+    let err = OwnedArray::from_vec_dim((2, 2), vec![1, 2, 3]).unwrap_err();
+    assert_eq!(err.to_string(),
+               "ShapeError/IncompatibleShape: incompatible shapes in from_vec_dim \
+                (from shape [3] to [2, 2])");
+}
+
+#[test]
+fn try_methods() {
+    let a = rcarr1(&[1, 2, 3, 4, 5, 6]);
+    assert_eq!(a.try_reshape((2, 3)).unwrap(), arr2(&[[1, 2, 3], [4, 5, 6]]));
+    let err = a.try_reshape((4, 2)).unwrap_err();
+    assert_eq!(err.kind(), ndarray::ErrorKind::IncompatibleShape);
+    assert_eq!(err.operation(), Some("reshape"));
+
+    let mut b = arr2(&[[1, 2, 3], [4, 5, 6]]);
+    assert_eq!(b.try_subview(Axis(0), 1).unwrap(), aview1(&[4, 5, 6]));
+    let err = b.try_subview(Axis(1), 3).unwrap_err();
+    assert_eq!(err.kind(), ndarray::ErrorKind::OutOfBounds);
+    assert_eq!(err.axis(), Some(Axis(1)));
+    assert!(b.try_subview(Axis(2), 0).is_err());
+    b.try_subview_mut(Axis(1), 0).unwrap().assign_scalar(&0);
+    assert_eq!(b, arr2(&[[0, 2, 3], [0, 5, 6]]));
+
+    assert!(b.try_assign(&arr1(&[7, 8, 9])).is_ok());
+    assert!(b.try_assign(&arr0(1)).is_ok());
+    let err = b.try_assign(&arr1(&[7, 8])).unwrap_err();
+    assert_eq!(err.operation(), Some("assign"));
+    assert_eq!(err.source_shape(), Some(&[2][..]));
+    assert_eq!(err.target_shape(), Some(&[2, 3][..]));
+    assert_eq!(b, arr2(&[[1, 1, 1], [1, 1, 1]]));
+}
+
+#[test]
+#[should_panic(expected = "IncompatibleShape")]
+fn reshape_panic() {
+    rcarr1(&[1, 2, 3]).reshape((2, 2));
+}
+
+#[test]
+fn into_shape_with_order() {
+    use ndarray::{Order, ErrorKind, INFER};
+    let a = OwnedArray::from_shape_fn((2, 3, 4), |(i, j, k)| 100 * i + 10 * j + k);
+
+    // C order: compare with the elements in logical order
+    let elements = a.iter().cloned().collect::<Vec<_>>();
+    for &shape in &[(4, 6), (24, 1), (1, 24), (2, 12), (6, 4)] {
+        let b = a.view().into_shape_with_order(shape, Order::C).unwrap();
+        assert_eq!(b.iter().cloned().collect::<Vec<_>>(), elements);
+    }
+    let b = a.view().into_shape_with_order((INFER, 4), Order::C).unwrap();
+    assert_eq!(b.shape(), &[6, 4]);
+
+    // F order of the transpose
+    let t = a.view().reversed_axes();
+    let b = t.into_shape_with_order((6, 4), Order::F).unwrap();
+    assert_eq!(b.t().iter().cloned().collect::<Vec<_>>(), elements);
+
+    // not contiguous, but reshaping within the contiguous axes works
+    let s = a.slice(&[S, Si(0, None, 2), S]);
+    let b = s.into_shape_with_order((2, 2, 2, 2), Order::C).unwrap();
+    assert_eq!(b.iter().cloned().collect::<Vec<_>>(),
+               s.iter().cloned().collect::<Vec<_>>());
+    let err = s.into_shape_with_order(16, Order::C).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::IncompatibleLayout);
+
+    // negative strides
+    let r = a.slice(&[S, S, Si(0, None, -1)]);
+    let b = r.into_shape_with_order((6, 2, 2), Order::C).unwrap();
+    assert_eq!(b.iter().cloned().collect::<Vec<_>>(),
+               r.iter().cloned().collect::<Vec<_>>());
+
+    let err = a.view().into_shape_with_order((5, 5), Order::C).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::IncompatibleShape);
+    let err = a.view().into_shape_with_order((5, INFER), Order::C).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::IncompatibleShape);
+    let err = a.view().into_shape_with_order((INFER, INFER), Order::C);
+    assert_eq!(err.unwrap_err().kind(), ErrorKind::IncompatibleShape);
+}
+
+#[test]
+fn to_shape() {
+    use ndarray::{ShapeBuilder, CowArray, INFER};
+    let a = arr2(&[[1, 2, 3], [4, 5, 6]]);
+    let c = a.to_shape((3, 2)).unwrap();
+    assert!(c.is_view());
+    assert_eq!(c, arr2(&[[1, 2], [3, 4], [5, 6]]));
+
+    let f = a.to_shape((3, 2).f()).unwrap();
+    assert!(f.is_owned());
+    assert_eq!(f, arr2(&[[1, 5], [4, 3], [2, 6]]));
+    assert_eq!(a.t().to_shape(6.f()).unwrap(), aview1(&[1, 2, 3, 4, 5, 6]));
+    assert!(a.t().to_shape(6.f()).unwrap().is_view());
+
+    let at = a.t();
+    let t = at.to_shape((2, INFER)).unwrap();
+    assert!(t.is_owned());
+    assert_eq!(t, arr2(&[[1, 4, 2], [5, 3, 6]]));
+    assert_eq!(t.clone().into_owned(), t);
+    assert!(a.to_shape(4).is_err());
+
+    let v: CowArray<_, _> = a.view().into();
+    assert_eq!(v.into_owned(), a);
+}
+
 #[test]
 #[should_panic(expected = "IncompatibleShape")]
 fn reshape_error1() {
@@ -1001,3 +1441,183 @@ fn test_swap() {
     }
     assert_eq!(a, b.t());
 }
+
+#[test]
+fn test_select() {
+    let x = arr3(&[[[0, 1], [2, 3], [4, 5]],
+                   [[6, 7], [8, 9], [10, 11]]]);
+    let r = x.select(Axis(1), &[2, 0, 2]);
+    assert_eq!(r, arr3(&[[[4, 5], [0, 1], [4, 5]],
+                         [[10, 11], [6, 7], [10, 11]]]));
+    let r = x.select(Axis(2), &[1]);
+    assert_eq!(r, x.slice(s![.., .., 1..2]));
+    let r = x.slice(s![..;-1, .., ..]).select(Axis(0), &[1]);
+    assert_eq!(r, x.slice(s![..1, .., ..]));
+
+    let e = x.select(Axis(0), &[]);
+    assert_eq!(e.dim(), (0, 3, 2));
+
+    // non-Copy elements
+    let s = arr1(&[String::from("a"), String::from("b")]);
+    assert_eq!(s.select(Axis(0), &[1, 1, 0]),
+               arr1(&[String::from("b"), String::from("b"), String::from("a")]));
+}
+
+#[test]
+#[should_panic]
+fn test_select_out_of_bounds() {
+    let x = arr2(&[[1, 2], [3, 4]]);
+    x.select(Axis(0), &[2]);
+}
+
+#[test]
+fn test_take_put() {
+    let x = arr2(&[[1, 2, 3],
+                   [4, 5, 6]]);
+    assert_eq!(x.take(Axis(1), &[2, 1]).unwrap(), arr2(&[[3, 2],
+                                                         [6, 5]]));
+    assert!(x.take(Axis(1), &[3]).is_err());
+    assert!(x.take(Axis(2), &[0]).is_err());
+
+    let mut y = OwnedArray::zeros((2, 3));
+    y.put(Axis(0), &[1], &arr2(&[[7, 8, 9]])).unwrap();
+    assert_eq!(y, arr2(&[[0, 0, 0],
+                         [7, 8, 9]]));
+    let err = y.put(Axis(0), &[0], &arr2(&[[7, 8]])).unwrap_err();
+    assert_eq!(err.kind(), ndarray::ErrorKind::IncompatibleShape);
+    let err = y.put(Axis(0), &[2], &arr2(&[[7, 8, 9]])).unwrap_err();
+    assert_eq!(err.kind(), ndarray::ErrorKind::OutOfBounds);
+    assert_eq!(y[(0, 0)], 0);
+}
+
+#[test]
+fn test_mask() {
+    let mut a = arr2(&[[1, 2, 3],
+                       [4, 5, 6]]);
+    let mask = a.map(|&x| x % 2 == 0);
+    assert_eq!(a.mask_select(&mask).unwrap(), arr1(&[2, 4, 6]));
+    assert_eq!(a.t().mask_select(&mask.t()).unwrap(), arr1(&[4, 2, 6]));
+
+    a.assign_where(&mask, &0).unwrap();
+    assert_eq!(a, arr2(&[[1, 0, 3],
+                         [0, 5, 0]]));
+
+    let bad_mask = OwnedArray::from_elem((3, 2), true);
+    assert_eq!(a.mask_select(&bad_mask).unwrap_err().kind(),
+               ndarray::ErrorKind::IncompatibleShape);
+    assert!(a.assign_where(&bad_mask, &1).is_err());
+    assert_eq!(a[(0, 0)], 1);
+}
+
+#[test]
+fn raw_views() {
+    let data = [0, 1, 2, 3, 4, 5];
+    let raw = unsafe { RawArrayView::from_shape_ptr((2, 3), data.as_ptr()) };
+    assert_eq!(raw.shape(), &[2, 3]);
+    assert_eq!(raw.strides(), &[3, 1]);
+    let copy = raw;
+    assert_eq!(unsafe { copy.deref_into_view() }, arr2(&[[0, 1, 2], [3, 4, 5]]));
+
+    // negative and zero strides
+    let raw = unsafe {
+        RawArrayView::from_shape_stride_ptr((3, 2), (-2isize as usize, 0), data.as_ptr().offset(4))
+    };
+    assert_eq!(raw.strides(), &[-2, 0]);
+    assert_eq!(unsafe { raw.deref_into_view() }, arr2(&[[4, 4], [2, 2], [0, 0]]));
+
+    let mut a = arr2(&[[1., 2.], [3., 4.]]);
+    let raw = a.raw_view();
+    assert_eq!(raw.as_ptr(), a.as_ptr());
+    assert_eq!(unsafe { raw.deref_into_view() }, a);
+
+    let raw = a.raw_view_mut();
+    unsafe {
+        *raw.as_mut_ptr().offset(1) = 0.;
+        raw.deref_into_view_mut().column_mut(0).assign_scalar(&5.);
+    }
+    assert_eq!(a, arr2(&[[5., 0.], [5., 4.]]));
+
+    let mut v = vec![0u8; 4];
+    let raw = unsafe { RawArrayViewMut::from_shape_stride_ptr(2, 2, v.as_mut_ptr()) };
+    unsafe {
+        raw.deref_into_view_mut().assign_scalar(&7);
+    }
+    assert_eq!(unsafe { raw.into_raw_view().deref_into_view() }, aview1(&[7, 7]));
+    assert_eq!(v, vec![7, 0, 7, 0]);
+}
+
+#[test]
+fn uninit() {
+    use std::mem::MaybeUninit;
+
+    let mut a = OwnedArray::<i32, _>::uninit((2, 3));
+    assert_eq!(a.strides(), &[3, 1]);
+    for ((i, j), elt) in a.indexed_iter_mut() {
+        *elt = MaybeUninit::new((10 * i + j) as i32);
+    }
+    let a = unsafe { a.assume_init() };
+    assert_eq!(a, arr2(&[[0, 1, 2], [10, 11, 12]]));
+    assert_eq!(a.strides(), &[3, 1]);
+
+    let mut b = OwnedArray::<String, _>::uninit_f((2, 2));
+    assert_eq!(b.strides(), &[1, 2]);
+    b.view_mut().zip_mut_with(&arr2(&[[1, 2], [3, 4]]), |elt, x| {
+        *elt = MaybeUninit::new(x.to_string());
+    });
+    let b = unsafe { b.assume_init() };
+    assert_eq!(b.strides(), &[1, 2]);
+    assert_eq!(b[(1, 0)], "3");
+    assert_eq!(b.iter().cloned().collect::<Vec<_>>(), vec!["1", "2", "3", "4"]);
+
+    let e = OwnedArray::<f32, _>::uninit((0, 4));
+    assert_eq!(unsafe { e.assume_init() }.shape(), &[0, 4]);
+}
+
+#[test]
+fn shape_builder() {
+    use ndarray::{ShapeBuilder, ErrorKind};
+
+    let a = OwnedArray::from_elem((2, 3).f(), 1);
+    assert_eq!(a.strides(), &[1, 2]);
+    let a = OwnedArray::from_elem((2, 3).set_f(false), 1);
+    assert_eq!(a.strides(), &[3, 1]);
+    let a = RcArray::<f32, _>::zeros((2, 3, 4).f());
+    assert_eq!(a.strides(), &[1, 2, 6]);
+    let a = OwnedArray::<String, _>::default(vec![2, 2].f());
+    assert_eq!(a.strides(), &[1, 2]);
+
+    let c = OwnedArray::from_shape_fn((2, 3, 4), |(i, j, k)| 100 * i + 10 * j + k);
+    let f = OwnedArray::from_shape_fn((2, 3, 4).f(), |(i, j, k)| 100 * i + 10 * j + k);
+    assert_eq!(c, f);
+    assert_eq!(f.strides(), &[1, 2, 6]);
+    assert_eq!(c[(1, 2, 3)], 123);
+    let mut calls = Vec::new();
+    let d = OwnedArray::from_shape_fn(vec![2, 2].f(), |ix| {
+        calls.push(ix.clone());
+        ix[0] + 2 * ix[1]
+    });
+    assert_eq!(calls, vec![vec![0, 0], vec![1, 0], vec![0, 1], vec![1, 1]]);
+    assert_eq!(d.as_slice_memory_order(), Some(&[0, 1, 2, 3][..]));
+    let z = OwnedArray::from_shape_fn((), |()| 7);
+    assert_eq!(z, arr0(7));
+
+    let v: Vec<_> = (0..6).collect();
+    let a = OwnedArray::from_vec_dim((2, 3).f(), v.clone()).unwrap();
+    assert_eq!(a, arr2(&[[0, 2, 4], [1, 3, 5]]));
+    let a = OwnedArray::from_vec_dim((3, 2).strides((2, 1)), v.clone()).unwrap();
+    assert_eq!(a, arr2(&[[0, 1], [2, 3], [4, 5]]));
+    let e = OwnedArray::from_vec_dim((4, 2).f(), v.clone()).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::IncompatibleShape);
+    let e = OwnedArray::from_vec_dim((3, 2).strides((2, 2)), v.clone()).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::OutOfBounds);
+
+    let view = ArrayView::from_shape((3, 2).f(), &v).unwrap();
+    assert_eq!(view, arr2(&[[0, 3], [1, 4], [2, 5]]));
+    assert!(ArrayView::from_shape((2, 2).f(), &v).is_err());
+    let mut w = v.clone();
+    {
+        let mut view = ArrayViewMut::from_shape((2, 3).strides((1, 2)), &mut w).unwrap();
+        view[(1, 2)] = 0;
+    }
+    assert_eq!(w, vec![0, 1, 2, 3, 4, 0]);
+}
@@ -1,7 +1,10 @@
 
 extern crate ndarray;
 
-use ndarray::{RcArray, Dimension};
+use std::error::Error;
+
+use ndarray::{RcArray, OwnedArray, Dimension, ErrorKind, Ix};
+use ndarray::{arr1, arr2};
 
 #[test]
 fn broadcast_1()
@@ -49,3 +52,53 @@ fn test_add_incompat()
     let incompat = RcArray::from_elem(3, 1.0f32);
     a += &incompat;
 }
+
+#[test]
+fn test_co_broadcast()
+{
+    let col = arr2(&[[1.], [2.], [3.]]);
+    let row = arr2(&[[10., 20.]]);
+    let sum = &col + &row;
+    assert_eq!(sum, arr2(&[[11., 21.],
+                           [12., 22.],
+                           [13., 23.]]));
+    assert_eq!(&row + &col, sum);
+
+    // arrays with different number of axes
+    let v = arr1(&[1, 2, 3]);
+    let m = OwnedArray::from_elem((2, 1), 10);
+    let prod: OwnedArray<_, (Ix, Ix)> = &v * &m;
+    assert_eq!(prod, arr2(&[[10, 20, 30],
+                            [10, 20, 30]]));
+    let dyn_sum = &v.view().into_shape(vec![3]).unwrap() + &m;
+    assert_eq!(dyn_sum.shape(), &[2, 3]);
+}
+
+#[test]
+fn test_try_add()
+{
+    let a = arr2(&[[1., 2.], [3., 4.]]);
+    let b = arr1(&[1., 1.]);
+    assert_eq!(a.try_add(&b).unwrap(), arr2(&[[2., 3.], [4., 5.]]));
+    assert_eq!(b.try_sub(&a).unwrap(), arr2(&[[0., -1.], [-2., -3.]]));
+
+    let c = arr1(&[1., 1., 1.]);
+    let err = a.try_add(&c).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::IncompatibleShape);
+    assert_eq!(err.to_string(),
+               "ShapeError/IncompatibleShape: incompatible shapes in try_add \
+                (from shape [2, 2] to [3])");
+    let source = err.source().unwrap();
+    assert_eq!(source.to_string(),
+               "ShapeError/IncompatibleShape: incompatible shapes in broadcast \
+                (from shape [2, 2] to [3])");
+    assert!(source.source().is_none());
+}
+
+#[test] #[should_panic]
+fn test_co_broadcast_incompat()
+{
+    let a = arr2(&[[1., 2.], [3., 4.]]);
+    let c = arr1(&[1., 1., 1.]);
+    let _ = &a + &c;
+}
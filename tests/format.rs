@@ -1,7 +1,8 @@
 
+#[macro_use(s)]
 extern crate ndarray;
 
-use ndarray::{arr0, rcarr1, aview1};
+use ndarray::{arr0, arr2, rcarr1, aview1, Axis, OwnedArray, FormatOptions};
 
 #[test]
 fn formatting()
@@ -34,4 +35,79 @@ fn formatting()
 
     let s = format!("{:02x}", aview1::<u8>(&[1, 0xff, 0xfe]));
     assert_eq!(s, "[01, ff, fe]");
+
+    let a = rcarr1::<f32>(&[1., 2., 3., 4.]).reshape((2, 2));
+    let s = format!("{}", a.slice(s![..;-1, ..;-1]));
+    assert_eq!(s, "[[4, 3],\n [2, 1]]");
+}
+
+#[test]
+fn formatting_empty() {
+    assert_eq!(format!("{}", OwnedArray::<f32, _>::zeros(0)), "[]");
+    assert_eq!(format!("{}", OwnedArray::<f32, _>::zeros((0, 3))), "[[]]");
+    assert_eq!(format!("{:?}", OwnedArray::<f32, _>::zeros((0, 3))),
+               "[[]] shape=[0, 3], strides=[3, 1]");
+    assert_eq!(format!("{:#}", OwnedArray::<f32, _>::zeros((2, 0, 3))), "[[[]], [[]]]");
+}
+
+#[test]
+fn formatting_inverted_axes() {
+    let mut a = arr2(&[[1, -20],
+                       [300, 4]]);
+    a.invert_axis(Axis(1));
+    assert_eq!(format!("{}", a), "[[-20,   1],\n [  4, 300]]");
+    assert_eq!(format!("{}", a.t()), format!("{}", arr2(&[[-20, 4], [1, 300]])));
+
+    // summarized output keeps the logical order
+    let mut b = OwnedArray::from_iter(0..2000);
+    b.invert_axis(Axis(0));
+    assert_eq!(format!("{}", b), "[1999, 1998, 1997, ...,    2,    1,    0]");
+}
+
+#[test]
+fn formatting_aligned() {
+    let a = arr2(&[[1, -20],
+                   [300, 4]]);
+    assert_eq!(format!("{}", a), "[[  1, -20],\n [300,   4]]");
+    assert_eq!(format!("{:#}", a), "[[  1, -20], [300,   4]]");
+    assert_eq!(format!("{:x}", arr2(&[[1u8, 0xff]])), "[[ 1, ff]]");
+    assert_eq!(format!("{:+.1}", aview1(&[1., -10.])), "[ +1.0, -10.0]");
+    assert_eq!(format!("{:?}", aview1(&[1, 10])), "[ 1, 10] shape=[2], strides=[1]");
+
+    let opts = FormatOptions::new().align(false);
+    assert_eq!(format!("{}", a.format_with(opts)), "[[1, -20],\n [300, 4]]");
+}
+
+#[test]
+fn formatting_fill_align() {
+    let a = aview1(&[1, 20, 300]);
+    assert_eq!(format!("{:*>5}", a), "[****1, ***20, **300]");
+    assert_eq!(format!("{:<5}", a), "[1    , 20   , 300  ]");
+    assert_eq!(format!("{:-^5}", a), "[--1--, -20--, -300-]");
+    assert_eq!(format!("{:<2}", a), "[1  , 20 , 300]");
+    assert_eq!(format!("{:*>2}", a), "[**1, *20, 300]");
+    assert_eq!(format!("{:<05}", a), "[00001, 00020, 00300]");
+    let a = aview1(&["a", "bc"]);
+    assert_eq!(format!("{:>3}", a), "[  a,  bc]");
+}
+
+#[test]
+fn formatting_summarized() {
+    let a = OwnedArray::from_shape_fn(2000, |i| i);
+    assert_eq!(format!("{}", a), "[   0,    1,    2, ..., 1997, 1998, 1999]");
+
+    let a = OwnedArray::from_shape_fn((5, 6), |(i, j)| 10 * i + j);
+    let opts = FormatOptions::new().threshold(20).edge_items(1);
+    assert_eq!(format!("{}", a.format_with(opts)),
+               "[[ 0, ...,  5],\n ...,\n [40, ..., 45]]");
+    let b = aview1(&[1., 20., 300., 4000.5]);
+    assert_eq!(format!("{:e}", b.format_with(opts.threshold(3))),
+               "[     1e0, ..., 4.0005e3]");
+    assert_eq!(format!("{:e}", b.format_with(opts.threshold(3).edge_items(2))),
+               "[     1e0,      2e1,      3e2, 4.0005e3]");
+    assert_eq!(format!("{}", a.format_with(opts.threshold(30))),
+               format!("{}", a));
+
+    let a = OwnedArray::<i32, _>::zeros((3, 0));
+    assert_eq!(format!("{}", a.format_with(opts)), "[[],\n [],\n []]");
 }
@@ -13,6 +13,7 @@ use ndarray::{
     arr3,
     Axis,
     Indexes,
+    RemoveAxis,
 };
 
 use itertools::assert_equal;
@@ -479,4 +480,184 @@ fn iterators_are_send_sync() {
     _send_sync(&a.axis_chunks_iter(Axis(1), 1));
     _send_sync(&a.axis_chunks_iter_mut(Axis(1), 1));
     _send_sync(&Indexes::new(a.dim()));
+    _send_sync(&a.windows((2, 2, 2)));
+    _send_sync(&a.windows((2, 2, 2)).into_iter());
+    _send_sync(&a.exact_chunks((2, 2, 2)));
+    _send_sync(&a.exact_chunks_mut((2, 2, 2)));
+    _send_sync(&a.exact_chunks_mut((2, 2, 2)).into_iter());
+    _send_sync(&a.lanes(Axis(1)));
+    _send_sync(&a.lanes(Axis(1)).into_iter());
+    _send_sync(&a.lanes_mut(Axis(1)).into_iter());
+}
+
+#[test]
+fn inverted_axes() {
+    let a = RcArray::from_iter(0..24).into_shape((2, 3, 4)).unwrap();
+    for &(inv0, inv1, inv2) in &[(true, false, false), (false, true, true),
+                                 (true, true, true)] {
+        let mut b = a.clone();
+        let mut inverted = vec![];
+        for (i, &inv) in [inv0, inv1, inv2].iter().enumerate() {
+            if inv {
+                b.invert_axis(Axis(i));
+                inverted.push(i);
+            }
+        }
+        // reference copy in standard layout
+        let r = RcArray::from_iter(b.indexed_iter().map(|(_, &x)| x))
+            .into_shape((2, 3, 4)).unwrap();
+        let expected = |(i, j, k): (Ix, Ix, Ix)| {
+            let i = if inv0 { 1 - i } else { i };
+            let j = if inv1 { 2 - j } else { j };
+            let k = if inv2 { 3 - k } else { k };
+            a[(i, j, k)]
+        };
+        for ((index, &x), &y) in b.indexed_iter().zip(&r) {
+            assert_eq!(x, expected(index));
+            assert_eq!(x, y);
+        }
+        assert_equal(b.outer_iter().rev(), r.outer_iter().rev());
+        assert_equal(b.outer_iter(), r.outer_iter());
+        assert_equal(b.axis_iter(Axis(1)), r.axis_iter(Axis(1)));
+        assert_equal(b.inner_iter(), r.inner_iter());
+        assert_equal(b.lanes(Axis(0)), r.lanes(Axis(0)));
+        assert_equal(b.windows((2, 2, 3)), r.windows((2, 2, 3)));
+        assert_equal(b.exact_chunks((1, 2, 2)), r.exact_chunks((1, 2, 2)));
+        assert_eq!(b.fold(0, |acc, &x| acc + x), 276);
+
+        let mut c = b.to_owned();
+        for (x, &y) in c.iter_mut().zip(&r) {
+            *x -= y;
+        }
+        assert!(c.iter().all(|&x| x == 0), "inverted axes {:?}", inverted);
+    }
+}
+
+#[test]
+fn windows() {
+    let a = RcArray::from_iter(0..20).into_shape((4, 5)).unwrap();
+    let w = a.windows((2, 3));
+    assert_eq!(w.raw_dim(), (3, 3));
+    let windows: Vec<_> = w.into_iter().collect();
+    assert_eq!(windows.len(), 9);
+    assert_eq!(windows[0], arr2(&[[0, 1, 2], [5, 6, 7]]));
+    assert_eq!(windows[5], arr2(&[[7, 8, 9], [12, 13, 14]]));
+
+    // the windows of a view with negative strides
+    let b = a.slice(s![..;-1, ..;-2]);
+    let first = b.windows((2, 2)).into_iter().next().unwrap();
+    assert_eq!(first, arr2(&[[19, 17], [14, 12]]));
+    assert_equal(b.windows((4, 3)).into_iter(), vec![b.view()]);
+
+    // stride and dilation
+    let mut it = a.windows((2, 2)).stride((2, 3)).into_iter();
+    assert_eq!(it.len(), 4);
+    assert_eq!(it.next().unwrap(), arr2(&[[0, 1], [5, 6]]));
+    assert_eq!(it.next().unwrap(), arr2(&[[3, 4], [8, 9]]));
+    assert_eq!(it.next().unwrap(), arr2(&[[10, 11], [15, 16]]));
+    let w = a.windows((2, 2)).dilation((3, 2));
+    assert_eq!(w.raw_dim(), (1, 3));
+    assert_eq!(w.into_iter().last().unwrap(), arr2(&[[2, 4], [17, 19]]));
+
+    // no windows when the window does not fit
+    assert_eq!(a.windows((5, 1)).into_iter().count(), 0);
+    assert_eq!(a.windows((2, 3)).dilation((4, 1)).into_iter().count(), 0);
+}
+
+#[test]
+fn windows_split_at() {
+    let c = RcArray::from_iter(0..10);
+    let (left, right) = c.windows(2).stride(2).split_at(Axis(0), 3);
+    assert_equal(left.into_iter().map(|w| w[0]), vec![0, 2, 4]);
+    assert_equal(right.into_iter().map(|w| w[0]), vec![6, 8]);
+}
+
+#[test]
+#[should_panic(expected = "before splitting")]
+fn windows_stride_after_split() {
+    let c = RcArray::from_iter(0..10);
+    c.windows(2).split_at(Axis(0), 3).0.stride(1);
+}
+
+#[test]
+#[should_panic(expected = "before splitting")]
+fn windows_dilation_after_split() {
+    let c = RcArray::from_iter(0..10);
+    c.windows(2).split_at(Axis(0), 3).1.dilation(2);
+}
+
+#[test]
+#[should_panic]
+fn windows_zero_size() {
+    let a = RcArray::from_iter(0..20).into_shape((4, 5)).unwrap();
+    a.windows((2, 0));
+}
+
+#[test]
+fn exact_chunks() {
+    let mut a = RcArray::from_iter(0..35).into_shape((5, 7)).unwrap();
+    let chunks: Vec<_> = a.exact_chunks((2, 3)).into_iter().collect();
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(chunks[0], arr2(&[[0, 1, 2], [7, 8, 9]]));
+    assert_eq!(chunks[3], arr2(&[[17, 18, 19], [24, 25, 26]]));
+    assert_eq!(a.exact_chunks((6, 1)).into_iter().count(), 0);
+
+    for (i, mut chunk) in a.exact_chunks_mut((2, 3)).into_iter().enumerate() {
+        chunk.assign_scalar(&(100 + i));
+    }
+    assert_eq!(a.row(1), aview1(&[100, 100, 100, 101, 101, 101, 13]));
+    assert_eq!(a.row(3), aview1(&[102, 102, 102, 103, 103, 103, 27]));
+    assert_eq!(a.row(4), aview1(&[28, 29, 30, 31, 32, 33, 34]));
+
+    let (left, right) = a.exact_chunks((2, 2)).split_at(Axis(1), 1);
+    assert_eq!(left.raw_dim(), (2, 1));
+    assert_eq!(right.into_iter().next().unwrap(), arr2(&[[100, 101], [100, 101]]));
+}
+
+#[test]
+fn lanes() {
+    let a = RcArray::from_iter(0..24).into_shape((2, 3, 4)).unwrap();
+    for axis in 0..3 {
+        let axis = Axis(axis);
+        let lanes = a.lanes(axis);
+        assert_eq!(lanes.raw_dim().slice(), a.dim().remove_axis(axis).slice());
+        let it = lanes.into_iter();
+        assert_eq!(it.len(), 24 / a.shape()[axis.axis()]);
+        for lane in it {
+            assert_eq!(lane.len(), a.shape()[axis.axis()]);
+        }
+    }
+    assert_equal(a.lanes(Axis(2)), a.inner_iter());
+    let mut it = a.lanes(Axis(0)).into_iter();
+    assert_eq!(it.next().unwrap(), aview1(&[0, 12]));
+    assert_eq!(it.next_back().unwrap(), aview1(&[11, 23]));
+    assert_eq!(it.len(), 10);
+    let (left, right) = it.split_at(4);
+    assert_equal(left.map(|l| l[0]), 1..5);
+    assert_equal(right.rev().map(|l| l[1]), (17..23).rev());
+
+    // negative strides and empty lanes
+    let b = a.slice(s![.., ..;-1, ..]);
+    assert_eq!(b.lanes(Axis(1)).into_iter().next().unwrap(), aview1(&[8, 4, 0]));
+    let c = a.slice(s![.., 0..0, ..]);
+    assert_eq!(c.lanes(Axis(1)).into_iter().count(), 8);
+    assert_eq!(c.lanes(Axis(0)).into_iter().count(), 0);
+
+    let mut d = RcArray::<i32, _>::zeros((3, 2));
+    for (i, mut lane) in d.lanes_mut(Axis(0)).into_iter().enumerate() {
+        lane[2] = i as i32 + 1;
+    }
+    assert_eq!(d, arr2(&[[0, 0], [0, 0], [1, 2]]));
+}
+
+#[test]
+fn map_axis() {
+    let a = RcArray::from_iter(0..24).into_shape((2, 3, 4)).unwrap();
+    assert_eq!(a.map_axis(Axis(2), |lane| lane.scalar_sum()), a.sum(Axis(2)));
+    let b = a.map_axis(Axis(1), |lane| lane[2] - lane[0]);
+    assert_eq!(b, arr2(&[[8, 8, 8, 8], [8, 8, 8, 8]]));
+    let c = a.t().map_axis(Axis(0), |lane| lane.len());
+    assert_eq!(c.dim(), (3, 2));
+    let e = a.slice(s![.., .., 0..0]).map_axis(Axis(2), |lane| lane.len());
+    assert_eq!(e, arr2(&[[0, 0, 0], [0, 0, 0]]));
 }
@@ -0,0 +1,189 @@
+extern crate ndarray;
+extern crate num;
+
+use ndarray::{OwnedArray, arr1, arr2};
+use ndarray::linalg::LinalgError;
+use num::Complex;
+
+fn c(re: f64, im: f64) -> Complex<f64> {
+    Complex::new(re, im)
+}
+
+fn test_matrix(n: usize) -> OwnedArray<f64, (usize, usize)> {
+    let mut a = OwnedArray::zeros((n, n));
+    for ((i, j), elt) in a.indexed_iter_mut() {
+        *elt = ((i * 7 + j * 3) % 11) as f64 - 5. + if i == j { 0.5 } else { 0. };
+    }
+    a
+}
+
+#[test]
+fn lu_reconstruct() {
+    for n in 1..8 {
+        let a = test_matrix(n);
+        let lu = a.lu().unwrap();
+        assert!(!lu.is_singular());
+        let l = lu.l();
+        let u = lu.u();
+        for i in 0..n {
+            assert_eq!(l[(i, i)], 1.);
+            for j in i + 1..n {
+                assert_eq!(l[(i, j)], 0.);
+                assert_eq!(u[(j, i)], 0.);
+            }
+        }
+        assert!(lu.p().dot(&a).all_close(&l.dot(&u), 1e-10));
+    }
+}
+
+#[test]
+fn det() {
+    let a = arr2(&[[2f64, -3., 1.],
+                   [2., 0., -1.],
+                   [1., 4., 5.]]);
+    assert!((a.det().unwrap() - 49.).abs() < 1e-12);
+    // a row exchange changes the sign
+    let b = arr2(&[[0., 1.], [1., 0.]]);
+    assert_eq!(b.det().unwrap(), -1.);
+    let singular = arr2(&[[1., 2.], [2., 4.]]);
+    assert_eq!(singular.det().unwrap(), 0.);
+    assert!(singular.lu().unwrap().is_singular());
+}
+
+#[test]
+fn solve_inv() {
+    for n in 1..8 {
+        let a = test_matrix(n);
+        let b = OwnedArray::linspace(-1., 1., n);
+        let x = a.solve(&b).unwrap();
+        assert!(a.dot(&x).all_close(&b, 1e-10));
+        let inv = a.inv().unwrap();
+        assert!(a.dot(&inv).all_close(&OwnedArray::eye(n), 1e-10));
+    }
+    let a = arr2(&[[1f32, 2.], [3., 4.]]);
+    assert!(a.solve(&arr1(&[5., 11.])).unwrap().all_close(&arr1(&[1., 2.]), 1e-5));
+}
+
+#[test]
+fn errors() {
+    let a = arr2(&[[1., 2., 3.], [4., 5., 6.]]);
+    assert_eq!(a.lu().unwrap_err(), LinalgError::NotSquare { rows: 2, cols: 3 });
+    assert_eq!(a.inv().unwrap_err(), LinalgError::NotSquare { rows: 2, cols: 3 });
+    let singular = arr2(&[[1., 2.], [2., 4.]]);
+    assert_eq!(singular.inv().unwrap_err(), LinalgError::Singular);
+    assert_eq!(singular.solve(&arr1(&[1., 1.])).unwrap_err(), LinalgError::Singular);
+    match test_matrix(3).solve(&arr1(&[1., 1.])) {
+        Err(LinalgError::Shape(_)) => {}
+        other => panic!("unexpected result {:?}", other),
+    }
+    let indefinite = arr2(&[[1., 2.], [2., 1.]]);
+    assert_eq!(indefinite.cholesky().unwrap_err(), LinalgError::NotPositiveDefinite);
+}
+
+#[test]
+fn qr() {
+    let shapes = [(1, 1), (3, 3), (5, 3), (3, 5), (6, 6)];
+    for &(m, n) in &shapes {
+        let mut a = OwnedArray::zeros((m, n));
+        for ((i, j), elt) in a.indexed_iter_mut() {
+            *elt = ((i * 5 + j * 2) % 7) as f64 - 3.;
+        }
+        let (q, r) = a.qr();
+        let k = if m < n { m } else { n };
+        assert_eq!(q.shape(), &[m, k]);
+        assert_eq!(r.shape(), &[k, n]);
+        assert!(q.t().dot(&q).all_close(&OwnedArray::eye(k), 1e-10));
+        assert!(q.dot(&r).all_close(&a, 1e-10));
+        for i in 0..k {
+            for j in 0..i {
+                assert_eq!(r[(i, j)], 0.);
+            }
+        }
+    }
+}
+
+#[test]
+fn cholesky() {
+    let b = test_matrix(5);
+    // bᵀ b + I is positive definite
+    let a = b.t().dot(&b) + &OwnedArray::eye(5);
+    let l = a.cholesky().unwrap();
+    for i in 0..5 {
+        assert!(l[(i, i)] > 0.);
+        for j in i + 1..5 {
+            assert_eq!(l[(i, j)], 0.);
+        }
+    }
+    assert!(l.dot(&l.t()).all_close(&a, 1e-10));
+}
+
+#[test]
+fn eigh_real() {
+    let b = test_matrix(6);
+    let a = &b + &b.t();
+    let (w, v) = a.eigh().unwrap();
+    for i in 1..6 {
+        assert!(w[i - 1] <= w[i]);
+    }
+    assert!(v.t().dot(&v).all_close(&OwnedArray::eye(6), 1e-10));
+    assert!((&v * &w).dot(&v.t()).all_close(&a, 1e-10));
+    // only the lower triangle is read
+    let mut lower = a.clone();
+    lower[(0, 5)] = 100.;
+    assert_eq!(lower.eigh().unwrap().0, w);
+    assert!(arr2(&[[1., 2.]]).eigh().is_err());
+}
+
+#[test]
+fn eigh_complex() {
+    let a = arr2(&[[c(2., 0.), c(0., -1.)],
+                   [c(0., 1.), c(2., 0.)]]);
+    let (w, v) = a.eigh().unwrap();
+    assert!(w.all_close(&arr1(&[1., 3.]), 1e-12));
+    for (j, &wj) in w.iter().enumerate() {
+        let av = a.dot(&v.column(j));
+        let wv = v.column(j).mapv(|x| x * wj);
+        for (x, y) in av.iter().zip(&wv) {
+            assert!((x - y).norm() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn svd_shapes() {
+    let shapes = [(1, 1), (4, 3), (3, 4), (5, 5)];
+    for &(m, n) in &shapes {
+        let mut a = OwnedArray::zeros((m, n));
+        for ((i, j), elt) in a.indexed_iter_mut() {
+            *elt = ((i * 5 + j * 2) % 7) as f64 - 3.;
+        }
+        let k = if m < n { m } else { n };
+        let (u, s, vt) = a.svd();
+        assert_eq!(u.shape(), &[m, k]);
+        assert_eq!(s.shape(), &[k]);
+        assert_eq!(vt.shape(), &[k, n]);
+        for i in 1..k {
+            assert!(s[i - 1] >= s[i]);
+        }
+        assert!(u.t().dot(&u).all_close(&OwnedArray::eye(k), 1e-10));
+        assert!(vt.dot(&vt.t()).all_close(&OwnedArray::eye(k), 1e-10));
+        assert!((&u * &s).dot(&vt).all_close(&a, 1e-10));
+
+        let (u, s2, vt) = a.svd_full();
+        assert_eq!(s2, s);
+        assert!(u.t().dot(&u).all_close(&OwnedArray::eye(m), 1e-10));
+        assert!(vt.t().dot(&vt).all_close(&OwnedArray::eye(n), 1e-10));
+    }
+}
+
+#[test]
+fn svd_rank_deficient() {
+    let a = arr2(&[[1f64, 2., 3.],
+                   [2., 4., 6.],
+                   [0., 0., 0.]]);
+    let (u, s, vt) = a.svd();
+    assert!((s[0] - 70f64.sqrt()).abs() < 1e-12);
+    assert!(s[1].abs() < 1e-12 && s[2].abs() < 1e-12);
+    assert!(u.t().dot(&u).all_close(&OwnedArray::eye(3), 1e-10));
+    assert!((&u * &s).dot(&vt).all_close(&a, 1e-10));
+}
@@ -0,0 +1,161 @@
+#![cfg(feature = "memmap")]
+extern crate ndarray;
+
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use ndarray::{OwnedArray, Axis, Ix, arr1, arr2, S, Si};
+use ndarray::mmap::{MmapArray, MmapArrayMut};
+use ndarray::npy::{read_npy, write_npy, ReadNpyError};
+
+/// A file in the temporary directory that is removed when dropped.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(name: &str, contents: &[u8]) -> TempFile {
+        let path = env::temp_dir().join(format!("ndarray-mmap-{}-{}", name, std::process::id()));
+        File::create(&path).unwrap().write_all(contents).unwrap();
+        TempFile(path)
+    }
+
+    fn open(&self) -> File {
+        File::open(&self.0).unwrap()
+    }
+
+    fn open_rw(&self) -> File {
+        OpenOptions::new().read(true).write(true).open(&self.0).unwrap()
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+fn sample() -> OwnedArray<f64, (Ix, Ix)> {
+    OwnedArray::linspace(0., 11., 12).into_shape((3, 4)).unwrap()
+}
+
+#[test]
+fn mmap_npy() {
+    let a = sample();
+    let mut npy = Vec::new();
+    write_npy(&mut npy, &a).unwrap();
+    let file = TempFile::new("npy", &npy);
+
+    let m = unsafe { MmapArray::<f64, (Ix, Ix)>::open_npy(&file.open()).unwrap() };
+    assert_eq!(m, a);
+    assert_eq!(m.slice(&[Si(0, None, 2), S]), a.slice(&[Si(0, None, 2), S]));
+    let chunks: Vec<_> = m.axis_chunks_iter(Axis(1), 3).map(|c| c.shape()[1]).collect();
+    assert_eq!(chunks, vec![3, 1]);
+    assert_eq!(m.fold_axis(Axis(0), 0., |acc, x| acc + x), arr1(&[12., 15., 18., 21.]));
+
+    let m = unsafe { MmapArray::<f64, Vec<Ix>>::open_npy(&file.open()).unwrap() };
+    assert_eq!(m.shape(), &[3, 4]);
+
+    // fortran order
+    let mut npy = Vec::new();
+    write_npy(&mut npy, &a.t()).unwrap();
+    let file = TempFile::new("npy-f", &npy);
+    let m = unsafe { MmapArray::<f64, (Ix, Ix)>::open_npy(&file.open()).unwrap() };
+    assert_eq!(m, a.t());
+}
+
+#[test]
+fn mmap_npy_mut() {
+    let a = arr2(&[[1i32, 2], [3, 4]]);
+    let mut npy = Vec::new();
+    write_npy(&mut npy, &a).unwrap();
+    let file = TempFile::new("npy-mut", &npy);
+    {
+        let mut m = unsafe {
+            MmapArrayMut::<i32, (Ix, Ix)>::open_npy_mut(&file.open_rw()).unwrap()
+        };
+        m.row_mut(1).assign_scalar(&0);
+        m[(0, 0)] = 7;
+        m.flush().unwrap();
+    }
+    let b: OwnedArray<i32, (Ix, Ix)> = read_npy(file.open()).unwrap();
+    assert_eq!(b, arr2(&[[7, 2], [0, 0]]));
+}
+
+#[test]
+fn mmap_shape_strides() {
+    let mut bytes = vec![0u8; 8];
+    for x in 0..6u32 {
+        bytes.extend_from_slice(&x.to_ne_bytes());
+    }
+    let file = TempFile::new("raw", &bytes);
+    let m = unsafe { MmapArray::<u32, _>::map_file(&file.open(), 8, (2, 3)).unwrap() };
+    assert_eq!(m, arr2(&[[0, 1, 2], [3, 4, 5]]));
+
+    let strides = (1, -2isize as Ix);
+    let m = unsafe {
+        MmapArray::<u32, _>::map_file_with_strides(&file.open(), 8, (2, 3), strides).unwrap()
+    };
+    assert_eq!(m, arr2(&[[4, 2, 0], [5, 3, 1]]));
+
+    let err = unsafe { MmapArray::<u32, _>::map_file(&file.open(), 12, (2, 3)).unwrap_err() };
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    let err = unsafe { MmapArray::<u32, _>::map_file(&file.open(), 6, 2).unwrap_err() };
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn mmap_offset_not_multiple_of_size() {
+    // an element type whose size is larger than its alignment
+    let mut bytes = Vec::new();
+    for x in 0..7u32 {
+        bytes.extend_from_slice(&x.to_ne_bytes());
+    }
+    let file = TempFile::new("unaligned-size", &bytes);
+    let m = unsafe { MmapArray::<[u32; 3], Ix>::map_file(&file.open(), 4, 2).unwrap() };
+    assert_eq!(m, arr1(&[[1, 2, 3], [4, 5, 6]]));
+    let m = unsafe {
+        MmapArray::<[u32; 3], Ix>::map_file_with_strides(&file.open(), 4, 2, -1isize as Ix)
+            .unwrap()
+    };
+    assert_eq!(m, arr1(&[[4, 5, 6], [1, 2, 3]]));
+
+    let mut m = unsafe {
+        MmapArrayMut::<[u32; 3], Ix>::map_file_mut(&file.open_rw(), 4, 2).unwrap()
+    };
+    m[1] = [40, 50, 60];
+    m.flush().unwrap();
+    let m = unsafe { MmapArray::<u32, Ix>::map_file(&file.open(), 0, 7).unwrap() };
+    assert_eq!(m, arr1(&[0, 1, 2, 3, 40, 50, 60]));
+}
+
+#[test]
+fn mmap_npy_errors() {
+    let mut npy = Vec::new();
+    write_npy(&mut npy, &sample()).unwrap();
+    let file = TempFile::new("npy-short", &npy[..npy.len() - 8]);
+    match unsafe { MmapArray::<f64, (Ix, Ix)>::open_npy(&file.open()) } {
+        Err(ReadNpyError::MissingData) => {}
+        Err(e) => panic!("{:?}", e),
+        Ok(_) => panic!(),
+    }
+    match unsafe { MmapArray::<f64, Ix>::open_npy(&file.open()) } {
+        Err(ReadNpyError::WrongNdim { expected: 1, found: 2 }) => {}
+        Err(e) => panic!("{:?}", e),
+        Ok(_) => panic!(),
+    }
+
+    // elements that are not in native byte order can not be mapped
+    let order = if cfg!(target_endian = "little") { '>' } else { '<' };
+    let dict = format!("{{'descr': '{}u2', 'fortran_order': False, 'shape': (1,), }}\n", order);
+    let mut npy = b"\x93NUMPY\x01\x00".to_vec();
+    npy.extend_from_slice(&[dict.len() as u8, 0]);
+    npy.extend_from_slice(dict.as_bytes());
+    npy.extend_from_slice(&[0, 1]);
+    let file = TempFile::new("npy-be", &npy);
+    match unsafe { MmapArray::<u16, Ix>::open_npy(&file.open()) } {
+        Err(ReadNpyError::WrongEndianness(_)) => {}
+        Err(e) => panic!("{:?}", e),
+        Ok(_) => panic!(),
+    }
+}
@@ -0,0 +1,213 @@
+extern crate ndarray;
+extern crate num;
+
+use std::io::Cursor;
+
+use ndarray::{OwnedArray, Ix, arr1, arr2, arr3, aview1};
+use ndarray::npy::{read_npy, write_npy, NpzReader, ReadNpyError};
+use num::Complex;
+
+fn to_npy<A, D>(a: &OwnedArray<A, D>) -> Vec<u8>
+    where A: ndarray::npy::NpyElement,
+          D: ndarray::Dimension,
+{
+    let mut file = Vec::new();
+    write_npy(&mut file, a).unwrap();
+    file
+}
+
+/// Build an `.npy` file by hand, padding the header like numpy does.
+fn npy_file(dict: &str, data: &[u8]) -> Vec<u8> {
+    let mut dict = dict.to_string();
+    while (10 + dict.len() + 1) % 64 != 0 {
+        dict.push(' ');
+    }
+    dict.push('\n');
+    let mut file = b"\x93NUMPY\x01\x00".to_vec();
+    file.push(dict.len() as u8);
+    file.push((dict.len() >> 8) as u8);
+    file.extend_from_slice(dict.as_bytes());
+    file.extend_from_slice(data);
+    file
+}
+
+#[test]
+fn npy_header() {
+    let a = arr2(&[[1., 2., 3.], [4., 5., 6.]]);
+    let file = to_npy(&a);
+    assert_eq!(&file[..8], b"\x93NUMPY\x01\x00");
+    let len = file[8] as usize | (file[9] as usize) << 8;
+    assert_eq!((10 + len) % 64, 0);
+    let header = String::from_utf8(file[10..10 + len].to_vec()).unwrap();
+    assert!(header.starts_with("{'descr': '<f8', 'fortran_order': False, 'shape': (2, 3), }"));
+    assert!(header.ends_with("\n"));
+    assert_eq!(file.len(), 10 + len + 6 * 8);
+
+    let file = to_npy(&arr1(&[true, false]));
+    let header = String::from_utf8(file[10..].to_vec()).unwrap();
+    assert!(header.starts_with("{'descr': '|b1', 'fortran_order': False, 'shape': (2,), }"));
+}
+
+#[test]
+fn npy_round_trip() {
+    let a = arr3(&[[[1., 2.], [3., 4.]], [[5., 6.], [7., 8.]]]);
+    let b: OwnedArray<f64, (Ix, Ix, Ix)> = read_npy(&to_npy(&a)[..]).unwrap();
+    assert_eq!(a, b);
+    let b: OwnedArray<f64, Vec<Ix>> = read_npy(&to_npy(&a)[..]).unwrap();
+    assert_eq!(b.shape(), a.shape());
+    assert_eq!(b.iter().cloned().collect::<Vec<_>>(), a.iter().cloned().collect::<Vec<_>>());
+
+    let a = arr2(&[[1i32, -2], [3, -4]]);
+    assert_eq!(a, read_npy::<_, i32, (Ix, Ix)>(&to_npy(&a)[..]).unwrap());
+    let a = arr1(&[true, false, true]);
+    assert_eq!(a, read_npy::<_, bool, Ix>(&to_npy(&a)[..]).unwrap());
+    let a = arr1(&[Complex::new(1f32, 2.), Complex::new(-3., 0.5)]);
+    assert_eq!(a, read_npy::<_, Complex<f32>, Ix>(&to_npy(&a)[..]).unwrap());
+    let a = OwnedArray::<u8, _>::zeros((0, 3));
+    assert_eq!(a, read_npy::<_, u8, (Ix, Ix)>(&to_npy(&a)[..]).unwrap());
+    let a = OwnedArray::from_elem((), 7u64);
+    assert_eq!(a, read_npy::<_, u64, ()>(&to_npy(&a)[..]).unwrap());
+}
+
+#[test]
+fn npy_layouts() {
+    let a = arr2(&[[1., 2., 3.], [4., 5., 6.]]);
+
+    // fortran layout is written in memory order
+    let f = OwnedArray::from_vec_dim_f((2, 3), vec![1., 4., 2., 5., 3., 6.]).unwrap();
+    assert_eq!(f, a);
+    let mut file = Vec::new();
+    write_npy(&mut file, &f).unwrap();
+    assert!(String::from_utf8_lossy(&file).contains("'fortran_order': True"));
+    assert_eq!(&file[file.len() - 8..], &6f64.to_bits().to_le_bytes());
+    let b: OwnedArray<f64, (Ix, Ix)> = read_npy(&file[..]).unwrap();
+    assert_eq!(a, b);
+
+    // a view with no particular layout is written in logical order
+    let mut file = Vec::new();
+    let s = a.slice(&[ndarray::S, ndarray::Si(0, None, 2)]);
+    write_npy(&mut file, &s).unwrap();
+    assert!(String::from_utf8_lossy(&file).contains("'fortran_order': False"));
+    let b: OwnedArray<f64, (Ix, Ix)> = read_npy(&file[..]).unwrap();
+    assert_eq!(b, s);
+}
+
+#[test]
+fn npy_big_endian() {
+    let mut data = Vec::new();
+    for x in &[1i16, -2, 300] {
+        data.extend_from_slice(&x.to_be_bytes());
+    }
+    let file = npy_file("{'descr': '>i2', 'fortran_order': False, 'shape': (3,), }", &data);
+    let a: OwnedArray<i16, Ix> = read_npy(&file[..]).unwrap();
+    assert_eq!(a, aview1(&[1, -2, 300]));
+}
+
+#[test]
+fn npy_errors() {
+    let file = to_npy(&arr2(&[[1., 2.], [3., 4.]]));
+    match read_npy::<_, f32, (Ix, Ix)>(&file[..]) {
+        Err(ReadNpyError::WrongDescriptor(ref d)) if d == "<f8" => {}
+        other => panic!("{:?}", other),
+    }
+    match read_npy::<_, f64, Ix>(&file[..]) {
+        Err(ReadNpyError::WrongNdim { expected: 1, found: 2 }) => {}
+        other => panic!("{:?}", other),
+    }
+    match read_npy::<_, f64, (Ix, Ix)>(&file[..file.len() - 1]) {
+        Err(ReadNpyError::MissingData) => {}
+        other => panic!("{:?}", other),
+    }
+    match read_npy::<_, f64, (Ix, Ix)>(&file[..20]) {
+        Err(ReadNpyError::Io(_)) => {}
+        other => panic!("{:?}", other),
+    }
+    let file = npy_file("{'descr': '|i4', 'fortran_order': False, 'shape': (0,), }", &[]);
+    match read_npy::<_, i32, Ix>(&file[..]) {
+        Err(ReadNpyError::WrongEndianness(_)) => {}
+        other => panic!("{:?}", other),
+    }
+    let file = npy_file("{'descr': '<i4', 'shape': (0,), }", &[]);
+    match read_npy::<_, i32, Ix>(&file[..]) {
+        Err(ReadNpyError::ParseHeader(_)) => {}
+        other => panic!("{:?}", other),
+    }
+    // deeply nested values are rejected before recursing further
+    let dict = format!("{{'descr': '<i4', 'fortran_order': False, 'shape': {}{}, }}",
+                       "(".repeat(4000), ")".repeat(4000));
+    let file = npy_file(&dict, &[]);
+    match read_npy::<_, i32, Ix>(&file[..]) {
+        Err(ReadNpyError::ParseHeader(ref msg)) if msg.contains("nested") => {}
+        other => panic!("{:?}", other),
+    }
+    // a huge header length is rejected before reading the header
+    let mut file = b"\x93NUMPY\x02\x00".to_vec();
+    file.extend_from_slice(&0xffff_ffffu32.to_le_bytes());
+    match read_npy::<_, i32, Ix>(&file[..]) {
+        Err(ReadNpyError::ParseHeader(_)) => {}
+        other => panic!("{:?}", other),
+    }
+}
+
+/// Build a zip archive of uncompressed entries; the checksums are left
+/// out since they are not verified.
+fn stored_zip(entries: &[(&str, Vec<u8>)]) -> Vec<u8> {
+    fn push16(v: &mut Vec<u8>, x: usize) { v.extend_from_slice(&(x as u16).to_le_bytes()); }
+    fn push32(v: &mut Vec<u8>, x: usize) { v.extend_from_slice(&(x as u32).to_le_bytes()); }
+    let mut zip = Vec::new();
+    let mut central = Vec::new();
+    for &(name, ref data) in entries {
+        let offset = zip.len();
+        push32(&mut zip, 0x04034b50);
+        for &x in &[20, 0, 0, 0, 0] { push16(&mut zip, x); }
+        for &x in &[0, data.len(), data.len()] { push32(&mut zip, x); }
+        push16(&mut zip, name.len());
+        push16(&mut zip, 0);
+        zip.extend_from_slice(name.as_bytes());
+        zip.extend_from_slice(data);
+
+        push32(&mut central, 0x02014b50);
+        for &x in &[20, 20, 0, 0, 0, 0] { push16(&mut central, x); }
+        for &x in &[0, data.len(), data.len()] { push32(&mut central, x); }
+        for &x in &[name.len(), 0, 0, 0, 0] { push16(&mut central, x); }
+        push32(&mut central, 0);
+        push32(&mut central, offset);
+        central.extend_from_slice(name.as_bytes());
+    }
+    let central_offset = zip.len();
+    zip.extend_from_slice(&central);
+    push32(&mut zip, 0x06054b50);
+    for &x in &[0, 0, entries.len(), entries.len()] { push16(&mut zip, x); }
+    push32(&mut zip, central.len());
+    push32(&mut zip, central_offset);
+    push16(&mut zip, 0);
+    zip
+}
+
+#[test]
+fn npz_read() {
+    let a = arr2(&[[1., 2.], [3., 4.]]);
+    let b = arr1(&[1i64, 2, 3]);
+    let zip = stored_zip(&[("a.npy", to_npy(&a)), ("b.npy", to_npy(&b))]);
+    let mut npz = NpzReader::new(Cursor::new(zip)).unwrap();
+    assert_eq!(npz.len(), 2);
+    assert_eq!(npz.names(), vec!["a", "b"]);
+    let b2: OwnedArray<i64, Vec<Ix>> = npz.by_name("b.npy").unwrap();
+    assert_eq!(b2.shape(), &[3]);
+    let a2: OwnedArray<f64, (Ix, Ix)> = npz.by_name("a").unwrap();
+    assert_eq!(a2, a);
+    match npz.by_name::<f64, Ix>("c") {
+        Err(ReadNpyError::MissingArray(ref name)) if name == "c" => {}
+        other => panic!("{:?}", other),
+    }
+    match npz.by_name::<f64, (Ix, Ix)>("b") {
+        Err(ReadNpyError::WrongDescriptor(_)) => {}
+        other => panic!("{:?}", other),
+    }
+
+    match NpzReader::new(Cursor::new(to_npy(&a))) {
+        Err(ReadNpyError::ParseArchive(_)) => {}
+        Err(e) => panic!("{:?}", e),
+        Ok(_) => panic!(),
+    }
+}
@@ -3,7 +3,7 @@ extern crate num as libnum;
 
 use ndarray::prelude::*;
 use ndarray::{arr0, rcarr1, rcarr2};
-use ndarray::{LinalgScalar, Data};
+use ndarray::{LinalgScalar, Data, ErrorKind};
 use ndarray::linalg::general_mat_mul;
 
 use std::fmt;
@@ -109,6 +109,58 @@ fn scalar_operations()
     }
 }
 
+#[test]
+fn owned_operations_co_broadcast()
+{
+    let col = arr2(&[[1.], [2.], [3.]]);
+    let row = arr2(&[[10., 20.]]);
+    let expected = arr2(&[[11., 21.],
+                          [12., 22.],
+                          [13., 23.]]);
+    assert_eq!(col.clone() + &row, expected);
+    assert_eq!(col.clone() + row.clone(), expected);
+    assert_eq!(row.clone() + col.clone(), expected);
+    assert_eq!(row.clone() - &col, -(col.clone() - &row));
+
+    // the elements of the left hand side are reused if it has the result shape
+    let e = expected.clone();
+    let ptr = e.as_ptr();
+    let sum = e + &row;
+    assert_eq!(sum.as_ptr(), ptr);
+    assert_eq!(sum, arr2(&[[21., 41.], [22., 42.], [23., 43.]]));
+
+    let rc = rcarr2(&[[1.], [2.]]);
+    let prod: RcArray<_, (Ix, Ix)> = rc * &aview1(&[1., 10.]);
+    assert_eq!(prod, arr2(&[[1., 10.], [2., 20.]]));
+}
+
+#[test]
+#[should_panic]
+fn owned_operations_incompatible()
+{
+    let a = OwnedArray::<f32, _>::zeros((3, 2));
+    let _ = a + &OwnedArray::zeros((2, 3));
+}
+
+#[test]
+fn view_mut_operations()
+{
+    let mut a = arr2(&[[1., 2.], [3., 4.]]);
+    {
+        let sum = a.view_mut() + &aview1(&[10., 20.]);
+        assert_eq!(sum, arr2(&[[11., 22.], [13., 24.]]));
+    }
+    assert_eq!(a, arr2(&[[11., 22.], [13., 24.]]));
+}
+
+#[test]
+#[should_panic]
+fn view_mut_operations_no_reallocation()
+{
+    let mut a = arr2(&[[1.], [2.]]);
+    let _ = a.view_mut() + &aview2(&[[10., 20.]]);
+}
+
 fn assert_approx_eq<F: fmt::Debug + Float>(f: F, g: F, tol: F) -> bool {
     assert!((f - g).abs() <= tol, "{:?} approx== {:?} (tol={:?})",
             f, g, tol);
@@ -356,6 +408,21 @@ fn mat_mul_shape_mismatch() {
     a.dot(&b);
 }
 
+#[test]
+fn try_dot_shape_mismatch() {
+    let a = range_mat(8, 8);
+    let b = range_mat(9, 8);
+    let err = a.try_dot(&b).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::IncompatibleShape);
+    assert_eq!(err.operation(), Some("dot"));
+    assert_eq!(err.target_shape(), Some(&[9, 8][..]));
+    assert!(a.try_dot(&b.row(0)).is_ok());
+    assert!(a.try_dot(&b.column(0)).is_err());
+    assert_eq!(a.row(0).try_dot(&b.row(0)), Ok(a.row(0).dot(&b.row(0))));
+    assert!(a.row(0).try_dot(&b.column(0)).is_err());
+    assert_eq!(a.try_dot(&a).unwrap(), a.dot(&a));
+}
+
 // test matrix multiplication shape mismatch
 #[test]
 #[should_panic]
@@ -403,6 +470,31 @@ fn mat_mul_rev() {
     assert_eq!(c1, c2);
 }
 
+// Check that matrix multiplication supports negative strides in any operand
+#[test]
+fn mat_mul_neg_stride() {
+    let (m, n, k) = (16, 13, 11);
+    let a = range_mat64(m, n);
+    let b = range_mat64(n, k);
+    let expected = a.dot(&b);
+
+    let mut a_inv = a.slice(s![..;-1, ..]).to_owned();
+    a_inv.invert_axis(Axis(0));
+    let mut b_inv = b.slice(s![.., ..;-1]).to_owned();
+    b_inv.invert_axis(Axis(1));
+    assert_eq!(a_inv.dot(&b), expected);
+    assert_eq!(a.dot(&b_inv), expected);
+    assert_eq!(a_inv.dot(&b_inv), expected);
+    assert_eq!(a_inv.t().dot(&a_inv), a.t().dot(&a));
+
+    let mut c = OwnedArray::zeros((m, k));
+    {
+        let mut c_inv = c.slice_mut(s![..;-1, ..;-1]);
+        general_mat_mul(1., &a_inv, &b_inv, 0., &mut c_inv);
+    }
+    assert_eq!(c, expected.slice(s![..;-1, ..;-1]));
+}
+
 #[test]
 fn scaled_add() {
     let a = range_mat(16, 15);
@@ -479,3 +571,188 @@ fn gen_mat_mul_i32() {
         assert_eq!(&c, &answer);
     }
 }
+
+#[test]
+fn batched_mat_mul() {
+    let a = OwnedArray::from_iter(0..2 * 3 * 4 * 5).mapv(|x| x as f64)
+                                                   .into_shape((2, 3, 4, 5)).unwrap();
+    let b = OwnedArray::from_iter(0..3 * 5 * 2).mapv(|x| x as f64)
+                                               .into_shape((3, 5, 2)).unwrap();
+    let c = a.matmul(&b);
+    assert_eq!(c.shape(), &[2, 3, 4, 2]);
+    for i in 0..2 {
+        for j in 0..3 {
+            let ab = a.subview(Axis(0), i).subview(Axis(0), j)
+                      .dot(&b.subview(Axis(0), j));
+            assert_eq!(c.subview(Axis(0), i).subview(Axis(0), j), ab);
+        }
+    }
+
+    // broadcast a batch axis of length one, and strided operands
+    let a1 = a.slice(s![..1, ..;-1, .., ..;2]);
+    let b1 = b.slice(s![.., ..;2, ..]);
+    let c1 = a1.matmul(&b1.t().to_owned().reversed_axes());
+    assert_eq!(c1.shape(), &[1, 3, 4, 2]);
+    for j in 0..3 {
+        let ab = a1.subview(Axis(0), 0).subview(Axis(0), j)
+                   .dot(&b1.subview(Axis(0), j));
+        assert_eq!(c1.subview(Axis(0), 0).subview(Axis(0), j), ab);
+    }
+
+    // two-dimensional operands are plain matrix multiplication
+    let m = range_mat64(4, 6);
+    let n = range_mat64(6, 3);
+    assert_eq!(m.matmul(&n), m.dot(&n));
+
+    let e = OwnedArray::<f64, _>::zeros((0, 4, 5)).matmul(&b.subview(Axis(0), 0));
+    assert_eq!(e.shape(), &[0, 4, 2]);
+}
+
+#[test]
+#[should_panic]
+fn batched_mat_mul_batch_mismatch() {
+    let a = OwnedArray::<f64, _>::zeros((2, 3, 3));
+    let b = OwnedArray::<f64, _>::zeros((3, 3, 3));
+    a.matmul(&b);
+}
+
+#[test]
+fn tensordot_axes() {
+    use ndarray::linalg::tensordot;
+    let a = OwnedArray::from_iter(0..3 * 4 * 5).mapv(|x| x as f64)
+                                               .into_shape((3, 4, 5)).unwrap();
+    let b = OwnedArray::from_iter(0..4 * 3 * 2).mapv(|x| x as f64)
+                                               .into_shape((4, 3, 2)).unwrap();
+    let c = tensordot(&a, &b, &[(Axis(0), Axis(1)), (Axis(1), Axis(0))]);
+    assert_eq!(c.shape(), &[5, 2]);
+    for k in 0..5 {
+        for l in 0..2 {
+            let mut sum = 0.;
+            for i in 0..3 {
+                for j in 0..4 {
+                    sum += a[(i, j, k)] * b[(j, i, l)];
+                }
+            }
+            assert_eq!(c[&[k, l][..]], sum);
+        }
+    }
+
+    // no contracted axes is the outer product
+    let x = arr1(&[1., 2.]);
+    let y = arr1(&[3., 4., 5.]);
+    let outer = tensordot(&x, &y, &[]);
+    assert_eq!(outer.into_shape((2, 3)).unwrap(),
+               arr2(&[[3., 4., 5.], [6., 8., 10.]]));
+
+    // matrix multiplication
+    let m = range_mat64(4, 6);
+    let n = range_mat64(6, 3);
+    let mn = tensordot(&m, &n, &[(Axis(1), Axis(0))]);
+    assert_eq!(mn.into_shape((4, 3)).unwrap(), m.dot(&n));
+    let mn = tensordot(&m.t(), &n, &[(Axis(0), Axis(0))]);
+    assert_eq!(mn.into_shape((4, 3)).unwrap(), m.dot(&n));
+}
+
+#[test]
+#[should_panic]
+fn tensordot_length_mismatch() {
+    let a = range_mat64(4, 6);
+    ndarray::linalg::tensordot(&a, &a, &[(Axis(0), Axis(1))]);
+}
+
+fn dyn_range(shape: Vec<Ix>) -> OwnedArray<f64, Vec<Ix>> {
+    let n = shape.iter().fold(1, |acc, &x| acc * x);
+    OwnedArray::linspace(0., (n - 1) as f64, n).into_shape(shape).unwrap()
+}
+
+#[test]
+fn einsum_products() {
+    use ndarray::linalg::einsum;
+    let a = range_mat64(4, 6);
+    let b = range_mat64(6, 3);
+    let ad = a.clone().into_shape(vec![4, 6]).unwrap();
+    let bd = b.clone().into_shape(vec![6, 3]).unwrap();
+
+    let ab = einsum("ij,jk->ik", &[ad.view(), bd.view()]).unwrap();
+    assert_eq!(ab.into_shape((4, 3)).unwrap(), a.dot(&b));
+    // implicit output and whitespace
+    let ab = einsum("ij, jk", &[ad.view(), bd.view()]).unwrap();
+    assert_eq!(ab.into_shape((4, 3)).unwrap(), a.dot(&b));
+    // transposed output
+    let ab_t = einsum("ij,jk->ki", &[ad.view(), bd.view()]).unwrap();
+    assert_eq!(ab_t.into_shape((3, 4)).unwrap(), a.dot(&b).reversed_axes());
+
+    // batched
+    let x = dyn_range(vec![2, 3, 4]);
+    let y = dyn_range(vec![2, 4, 5]);
+    let xy = einsum("bij,bjk->bik", &[x.view(), y.view()]).unwrap();
+    assert_eq!(xy.shape(), &[2, 3, 5]);
+    let x3 = x.clone().into_shape((2, 3, 4)).unwrap();
+    let y3 = y.clone().into_shape((2, 4, 5)).unwrap();
+    assert_eq!(xy.into_shape((2, 3, 5)).unwrap(), x3.matmul(&y3));
+
+    // three operands, with a label summed only in the first
+    let v = dyn_range(vec![3]);
+    let r = einsum("i,bij,bjk->k", &[v.view(), x.view(), y.view()]).unwrap();
+    let mut expected = OwnedArray::zeros(5);
+    for bb in 0..2 {
+        for i in 0..3 {
+            for j in 0..4 {
+                for k in 0..5 {
+                    expected[k] += v[&[i][..]] * x3[(bb, i, j)] * y3[(bb, j, k)];
+                }
+            }
+        }
+    }
+    assert_eq!(r.into_shape(5).unwrap(), expected);
+
+    // outer product
+    let w = dyn_range(vec![2]);
+    let outer = einsum("i,j->ij", &[v.view(), w.view()]).unwrap();
+    assert_eq!(outer.shape(), &[3, 2]);
+    assert_eq!(outer[&[2, 1][..]], 2.);
+}
+
+#[test]
+fn einsum_single_operand() {
+    use ndarray::linalg::einsum;
+    let a = dyn_range(vec![3, 3]);
+    let trace = einsum("ii->", &[a.view()]).unwrap();
+    assert_eq!(trace[&[][..]], 0. + 4. + 8.);
+    let diag = einsum("ii->i", &[a.view()]).unwrap();
+    assert_eq!(diag.into_shape(3).unwrap(), arr1(&[0., 4., 8.]));
+    let t = einsum("ij->ji", &[a.view()]).unwrap();
+    assert_eq!(t.into_shape((3, 3)).unwrap(),
+               a.clone().into_shape((3, 3)).unwrap().reversed_axes());
+    let sums = einsum("ij->i", &[a.view()]).unwrap();
+    assert_eq!(sums.into_shape(3).unwrap(), arr1(&[3., 12., 21.]));
+    let total = einsum("ij->", &[a.view()]).unwrap();
+    assert_eq!(total[&[][..]], 36.);
+}
+
+#[test]
+fn einsum_errors() {
+    use ndarray::linalg::einsum;
+    use ndarray::ErrorKind;
+    let a = dyn_range(vec![3, 4]);
+    let b = dyn_range(vec![3, 4]);
+    let kind = |s: &str, ops: &[ArrayView<f64, Vec<Ix>>]| einsum(s, ops).unwrap_err().kind();
+    assert_eq!(kind("ij,jk->ik", &[a.view(), b.view()]), ErrorKind::IncompatibleShape);
+    assert_eq!(kind("ij", &[a.view(), b.view()]), ErrorKind::IncompatibleShape);
+    assert_eq!(kind("ijk", &[a.view()]), ErrorKind::IncompatibleShape);
+    assert_eq!(kind("ij->iz", &[a.view()]), ErrorKind::IncompatibleShape);
+    assert_eq!(kind("ij->ii", &[a.view()]), ErrorKind::IncompatibleShape);
+    assert_eq!(kind("ii", &[a.view()]), ErrorKind::IncompatibleShape);
+    assert_eq!(kind("i1", &[a.view()]), ErrorKind::Unsupported);
+    assert_eq!(kind("ij>i", &[a.view()]), ErrorKind::Unsupported);
+
+    let err = einsum("ij,j1", &[a.view(), b.view()]).unwrap_err();
+    assert_eq!(err.operation(), Some("einsum"));
+    assert_eq!(err.detail(), Some("term 1 `j1` has labels that are not letters"));
+    let err = einsum("ij,jk->ik", &[a.view(), b.view()]).unwrap_err();
+    assert_eq!(err.to_string(),
+               "ShapeError/IncompatibleShape: incompatible shapes in einsum \
+                (label `j` in term 1 has length 3, but 4 elsewhere)");
+    let err = einsum("ij->iz", &[a.view()]).unwrap_err();
+    assert_eq!(err.detail().unwrap(), "output label `z` is repeated or missing from the inputs");
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "rayon")]
+
+#[macro_use(s)]
+extern crate ndarray;
+
+use ndarray::{OwnedArray, Axis};
+use ndarray::parallel::prelude::*;
+
+const M: usize = 1024 * 10;
+const N: usize = 100;
+
+#[test]
+fn test_par_iter() {
+    let mut a = OwnedArray::from_elem((M, N), 0.);
+    for (i, elt) in a.iter_mut().enumerate() {
+        *elt = i as f64;
+    }
+    let s1: f64 = a.par_iter().sum();
+    assert_eq!(s1, a.scalar_sum());
+
+    let at = a.t();
+    let s2: f64 = at.slice(s![..;2, ..;-3]).par_iter().map(|&x| x).sum();
+    let s3 = at.slice(s![..;2, ..;-3]).scalar_sum();
+    assert_eq!(s2, s3);
+}
+
+#[test]
+fn test_par_iter_mut() {
+    let mut a = OwnedArray::<f64, _>::zeros((M, N));
+    a.slice_mut(s![.., ..;2]).par_iter_mut().for_each(|x| *x = 1.);
+    assert_eq!(a.scalar_sum(), (M * N / 2) as f64);
+    for row in a.outer_iter() {
+        assert_eq!(row[0], 1.);
+        assert_eq!(row[1], 0.);
+    }
+}
+
+#[test]
+fn test_par_map_inplace() {
+    let mut a = OwnedArray::<f64, _>::from_elem((M, N), 2.);
+    a.par_map_inplace(|x| *x *= *x);
+    assert!(a.iter().all(|&x| x == 4.));
+
+    let mut b = OwnedArray::<f64, _>::from_elem((M, N), 2.);
+    b.slice_mut(s![..;-1, ..;3]).par_mapv_inplace(|x| x + 1.);
+    assert_eq!(b.column(0)[10], 3.);
+    assert_eq!(b.column(1)[10], 2.);
+}
+
+#[test]
+fn test_par_axis_iter() {
+    let mut a = OwnedArray::<f64, _>::zeros((M, N));
+    a.par_axis_iter_mut(Axis(0)).enumerate().for_each(|(i, mut row)| {
+        row.assign_scalar(&(i as f64));
+    });
+    assert_eq!(a[(10, 3)], 10.);
+
+    let sums: Vec<f64> = a.par_axis_iter(Axis(1))
+                          .map(|col| col.scalar_sum())
+                          .collect();
+    assert_eq!(sums.len(), N);
+    assert_eq!(sums[0], a.column(0).scalar_sum());
+    assert!(sums.iter().all(|&s| s == sums[0]));
+}
+
+#[test]
+fn test_par_fold_axis() {
+    let mut a = OwnedArray::<f64, _>::zeros((M / 16, 16, 8));
+    for (i, elt) in a.iter_mut().enumerate() {
+        *elt = (i % 17) as f64;
+    }
+    for axis in 0..3 {
+        let axis = Axis(axis);
+        let par = a.par_fold_axis(axis, 0., |acc, &x| acc + x);
+        let seq = a.fold_axis(axis, 0., |acc, &x| acc + x);
+        assert_eq!(par, seq);
+    }
+    let b = a.slice(s![..;-1, .., ..;3]);
+    assert_eq!(b.par_fold_axis(Axis(1), 1., |acc, &x| acc * (1. + x / 10.)),
+               b.fold_axis(Axis(1), 1., |acc, &x| acc * (1. + x / 10.)));
+}
+
+#[test]
+fn test_par_windows() {
+    let a = OwnedArray::from_iter(0..N * 64).into_shape((64, N)).unwrap();
+    let par: Vec<usize> = a.windows((3, 3)).into_par_iter().map(|w| w.scalar_sum()).collect();
+    let seq: Vec<usize> = a.windows((3, 3)).into_iter().map(|w| w.scalar_sum()).collect();
+    assert_eq!(par, seq);
+
+    let mut b = OwnedArray::<usize, _>::zeros((64, N));
+    b.exact_chunks_mut((4, 5)).into_par_iter().for_each(|mut c| c[(0, 0)] = 1);
+    assert_eq!(b.scalar_sum(), 16 * 20);
+    let sum: usize = b.exact_chunks((2, 5)).into_par_iter().map(|c| c.scalar_sum()).sum();
+    assert_eq!(sum, 16 * 20);
+}
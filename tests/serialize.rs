@@ -4,44 +4,93 @@ extern crate rustc_serialize as serialize;
 extern crate ndarray;
 
 #[cfg(feature = "serde")]
-extern crate serde;
+extern crate serde_json;
 
 #[cfg(feature = "rustc-serialize")]
 use serialize::json;
 
 use ndarray::{arr0, arr1, arr2, RcArray, Ix, S, Si};
+#[cfg(feature = "serde")]
+use ndarray::OwnedArray;
 
 #[cfg(feature = "serde")]
 #[test]
-fn test_serde()
+fn serial_many_dim_serde()
 {
-    let mut out = std::io::stdout();
     {
         let a = arr0::<f32>(2.72);
-        println!("{:?}", a);
-        let v = serde::json::to_value(&a);
-        serde::json::to_writer(&mut out, &a);
-        /*
-        let serial = serde::json::encode(&a).unwrap();
+        let serial = serde_json::to_string(&a).unwrap();
         println!("Encode {:?} => {:?}", a, serial);
-        */
-        //let res = json::decode::<RcArray<f32, _>>(&serial);
-        //println!("{:?}", res);
-        //assert_eq!(a, res.unwrap());
+        let res = serde_json::from_str::<RcArray<f32, _>>(&serial);
+        println!("{:?}", res);
+        assert_eq!(a, res.unwrap());
+    }
+
+    {
+        let a = arr1::<f32>(&[2.72, 1., 2.]);
+        let serial = serde_json::to_string(&a).unwrap();
+        println!("{:?}", serial);
+        let res = serde_json::from_str::<OwnedArray<f32, _>>(&serial);
+        println!("{:?}", res);
+        assert_eq!(a, res.unwrap());
     }
 
     {
-        let a = arr2(&[[2.7, 1., 2.],
-                       [ 1., 0., 1.]]);
+        let a = arr2(&[[3., 1., 2.2], [3.1, 4., 7.]]);
+        let serial = serde_json::to_string(&a.view()).unwrap();
+        println!("{:?}", serial);
+        assert_eq!(serial, r##"{"v":1,"dim":[2,3],"data":[3.0,1.0,2.2,3.1,4.0,7.0]}"##);
+        let res = serde_json::from_str::<RcArray<f32, _>>(&serial);
+        println!("{:?}", res);
+        assert_eq!(a, res.unwrap());
+        let text = r##"{"v":1,"dim":[2,3],"data":[3,1,2.2,3.1,4,7]}"##;
+        let b = serde_json::from_str::<RcArray<f32, Vec<Ix>>>(text).unwrap();
+        assert_eq!(b.shape(), &[2, 3]);
+        assert_eq!(a, b.into_shape((2, 3)).unwrap());
+    }
+
+    {
+        // Test a sliced array.
+        let mut a = RcArray::linspace(0., 31., 32).reshape((2, 2, 2, 4));
+        a.islice(&[Si(0, None, -1), S, S, Si(0, Some(2), 1)]);
         println!("{:?}", a);
-        serde::json::to_writer(&mut out, &a);
-        /*
-        let serial = serde::json::encode(&a).unwrap();
+        let serial = serde_json::to_string(&a).unwrap();
         println!("{:?}", serial);
-        let res = json::decode::<RcArray<f32, _>>(&serial);
+        let res = serde_json::from_str::<RcArray<f32, _>>(&serial);
         println!("{:?}", res);
         assert_eq!(a, res.unwrap());
-        */
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serial_wrong_count_serde()
+{
+    // one element too few
+    let text = r##"{"v":1,"dim":[2,3],"data":[3,1,2.2,3.1,4]}"##;
+    let arr = serde_json::from_str::<RcArray<f32, (Ix, Ix)>>(text);
+    println!("{:?}", arr);
+    assert!(arr.is_err());
+
+    // future version
+    let text = r##"{"v":200,"dim":[2,3],"data":[3,1,2.2,3.1,4,7]}"##;
+    let arr = serde_json::from_str::<RcArray<f32, (Ix, Ix)>>(text);
+    println!("{:?}", arr);
+    assert!(arr.is_err());
+
+    // missing field
+    let text = r##"{"v":1,"data":[3,1,2.2,3.1,4,7]}"##;
+    let arr = serde_json::from_str::<OwnedArray<f32, Vec<Ix>>>(text);
+    println!("{:?}", arr);
+    assert!(arr.is_err());
+
+    // repeated fields
+    for text in &[r##"{"v":1,"v":1,"dim":[2],"data":[3,1]}"##,
+                  r##"{"v":1,"dim":[2],"dim":[2],"data":[3,1]}"##,
+                  r##"{"v":1,"dim":[2],"data":[3,1],"data":[3,1]}"##] {
+        let err = serde_json::from_str::<OwnedArray<f32, Vec<Ix>>>(text).unwrap_err();
+        println!("{:?}", err);
+        assert!(err.to_string().contains("duplicate field"));
     }
 }
 
@@ -1,11 +1,14 @@
 
-#[macro_use(stack)]
+#[macro_use(stack, concatenate, stack_new_axis)]
 extern crate ndarray;
 
 
 use ndarray::{
     aview1,
+    aview2,
+    arr1,
     arr2,
+    arr3,
     Axis,
     Ix,
     OwnedArray,
@@ -34,7 +37,13 @@ fn stacking() {
     assert_eq!(d, aview1(&[2., 2., 9., 9.]));
 
     let res = ndarray::stack(Axis(1), &[a.view(), c.view()]);
-    assert_eq!(res.unwrap_err().kind(), ErrorKind::IncompatibleShape);
+    let err = res.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::IncompatibleShape);
+    assert_eq!(err.operation(), Some("stack"));
+    assert_eq!(err.axis(), Some(Axis(0)));
+    assert_eq!(err.to_string(),
+               "ShapeError/IncompatibleShape: incompatible shapes in stack \
+                (from shape [2, 2] to [6, 2], along axis 0)");
 
     let res = ndarray::stack(Axis(2), &[a.view(), c.view()]);
     assert_eq!(res.unwrap_err().kind(), ErrorKind::OutOfBounds);
@@ -42,3 +51,123 @@ fn stacking() {
     let res: Result<OwnedArray<f64, (Ix, Ix)>, _> = ndarray::stack(Axis(0), &[]);
     assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
 }
+
+#[test]
+fn stacking_clone() {
+    let a = OwnedArray::from_vec(vec![String::from("a"), String::from("b")]);
+    let b = ndarray::stack(Axis(0), &[a.view(), a.slice(&[ndarray::Si(0, Some(1), 1)])]).unwrap();
+    assert_eq!(b.iter().cloned().collect::<Vec<_>>(), vec!["a", "b", "a"]);
+}
+
+#[test]
+fn concatenating() {
+    let a = arr2(&[[2., 2.],
+                   [3., 3.]]);
+    let b = ndarray::concatenate(Axis(1), &[a.view(), a.slice(&[ndarray::S, ndarray::Si(0, Some(1), 1)])]);
+    assert_eq!(b.unwrap(), arr2(&[[2., 2., 2.],
+                                  [3., 3., 3.]]));
+
+    let c = concatenate![Axis(0), a, a.slice(&[ndarray::Si(0, Some(1), 1), ndarray::S])];
+    assert_eq!(c, arr2(&[[2., 2.],
+                         [3., 3.],
+                         [2., 2.]]));
+
+    let res = ndarray::concatenate(Axis(1), &[a.view(), c.view()]);
+    let err = res.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::IncompatibleShape);
+    assert_eq!(err.operation(), Some("concatenate"));
+    assert_eq!(err.axis(), Some(Axis(0)));
+
+    // different storage kinds, through views
+    let r = a.to_shared();
+    let d = ndarray::concatenate(Axis(0), &[a.view(), r.view(), aview2(&[[1., 1.]])]).unwrap();
+    assert_eq!(d.shape(), &[5, 2]);
+
+    // empty arrays whose lengths along the axis overflow
+    let empty = aview1::<f64>(&[]);
+    let big = empty.broadcast((!0 / 2 + 1, 0)).unwrap();
+    let err = ndarray::concatenate(Axis(0), &[big.view(), big.view()]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::RangeLimited);
+}
+
+#[test]
+fn stacking_new_axis() {
+    let a = arr1(&[1, 2, 3]);
+    let b = arr1(&[4, 5, 6]);
+    let c = stack_new_axis![Axis(0), a, b];
+    assert_eq!(c, arr2(&[[1, 2, 3],
+                         [4, 5, 6]]));
+    let d = ndarray::stack_new_axis(Axis(1), &[a.view(), b.view()]).unwrap();
+    assert_eq!(d, c.t());
+
+    let e = ndarray::stack_new_axis(Axis(2), &[c.view(), c.view()]).unwrap();
+    assert_eq!(e, arr3(&[[[1, 1], [2, 2], [3, 3]],
+                         [[4, 4], [5, 5], [6, 6]]]));
+
+    let res = ndarray::stack_new_axis(Axis(2), &[a.view(), b.view()]);
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::OutOfBounds);
+
+    let res = ndarray::stack_new_axis(Axis(0), &[a.view(), b.slice(&[ndarray::Si(1, None, 1)])]);
+    let err = res.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::IncompatibleShape);
+    assert_eq!(err.operation(), Some("stack_new_axis"));
+    assert_eq!(err.axis(), Some(Axis(0)));
+
+    let res = ndarray::stack_new_axis::<i32, Ix>(Axis(0), &[]);
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+}
+
+#[test]
+fn splitting() {
+    let a = arr2(&[[1, 2, 3, 4],
+                   [5, 6, 7, 8]]);
+    let parts = ndarray::split(&a, Axis(1), &[1, 1, 3]).unwrap();
+    assert_eq!(parts.len(), 4);
+    assert_eq!(parts[0], arr2(&[[1], [5]]));
+    assert_eq!(parts[1].shape(), &[2, 0]);
+    assert_eq!(parts[2], arr2(&[[2, 3], [6, 7]]));
+    assert_eq!(parts[3], arr2(&[[4], [8]]));
+    assert_eq!(ndarray::concatenate(Axis(1), &parts).unwrap(), a);
+
+    let parts = ndarray::split(a.row(1), Axis(0), &[]).unwrap();
+    assert_eq!(parts, vec![a.row(1)]);
+
+    let res = ndarray::split(&a, Axis(1), &[3, 1]);
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::OutOfBounds);
+    let res = ndarray::split(&a, Axis(1), &[5]);
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::OutOfBounds);
+    let res = ndarray::split(&a, Axis(2), &[1]);
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::OutOfBounds);
+
+    let parts = ndarray::array_split(&[1, 2, 3, 4, 5, 6, 7][..], Axis(0), 3).unwrap();
+    let lens = parts.iter().map(|p| p.len()).collect::<Vec<_>>();
+    assert_eq!(lens, vec![3, 2, 2]);
+    assert_eq!(parts[2], aview1(&[6, 7]));
+    let parts = ndarray::array_split(&a, Axis(0), 3).unwrap();
+    let lens = parts.iter().map(|p| p.shape()[0]).collect::<Vec<_>>();
+    assert_eq!(lens, vec![1, 1, 0]);
+    let res = ndarray::array_split(&a, Axis(0), 0);
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::Unsupported);
+}
+
+#[test]
+fn tile_repeat() {
+    let a = arr2(&[[1, 2],
+                   [3, 4]]);
+    assert_eq!(ndarray::tile(&a, (1, 2)).unwrap(),
+               arr2(&[[1, 2, 1, 2],
+                      [3, 4, 3, 4]]));
+    assert_eq!(ndarray::tile(a.t(), (2, 1)).unwrap(),
+               arr2(&[[1, 3], [2, 4], [1, 3], [2, 4]]));
+    assert_eq!(ndarray::tile(&a, (0, 3)).unwrap().shape(), &[0, 6]);
+
+    assert_eq!(ndarray::repeat(&a, Axis(0), 2).unwrap(),
+               arr2(&[[1, 2], [1, 2], [3, 4], [3, 4]]));
+    assert_eq!(ndarray::repeat(&a, Axis(1), 0).unwrap().shape(), &[2, 0]);
+    let res = ndarray::repeat(&a, Axis(2), 2);
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::OutOfBounds);
+
+    let d = a.clone().into_shape(vec![2, 2]).unwrap();
+    let res = ndarray::tile(&d, vec![2]);
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::IncompatibleShape);
+}
@@ -0,0 +1,138 @@
+#[macro_use(s)]
+extern crate ndarray;
+
+use ndarray::{
+    OwnedArray,
+    Zip,
+    FoldWhile,
+    indices,
+    arr1,
+    arr2,
+};
+
+#[test]
+fn zip_apply_c() {
+    let mut a = OwnedArray::zeros((4, 5));
+    let b = OwnedArray::from_vec_dim((4, 5), (0..20).collect()).unwrap();
+    let c = OwnedArray::from_elem((4, 5), 10);
+    Zip::from(&mut a).and(&b).and(&c).apply(|x, &y, &z| *x = y + z);
+    assert_eq!(a, &b + &c);
+}
+
+#[test]
+fn zip_apply_f_and_transposed() {
+    let b = OwnedArray::from_vec_dim((4, 5), (0..20).collect()).unwrap();
+    let bt = b.t();
+    let mut a = OwnedArray::zeros_f((5, 4));
+    Zip::from(&mut a).and(bt).apply(|x, &y| *x = y);
+    assert_eq!(a, bt);
+
+    // mixed c and f inputs
+    let mut c = OwnedArray::zeros((5, 4));
+    Zip::from(&mut c).and(&a).and(bt).apply(|x, &y, &z| *x = y * z);
+    assert_eq!(c, &bt * &bt);
+}
+
+#[test]
+fn zip_apply_strided() {
+    let b = OwnedArray::from_vec_dim((4, 6), (0..24).collect()).unwrap();
+    let bs = b.slice(s![.., ..;2]);
+    let mut a = OwnedArray::zeros((4, 3));
+    Zip::from(&mut a).and(bs).apply(|x, &y| *x = y);
+    assert_eq!(a, bs);
+
+    let mut d = vec![0; 4];
+    Zip::from(&mut d).and(b.column(1)).apply(|x, &y| *x = y);
+    assert_eq!(d, vec![1, 7, 13, 19]);
+}
+
+#[test]
+fn zip_many() {
+    let a = arr1(&[1, 2, 3]);
+    let mut out = OwnedArray::zeros(3);
+    Zip::from(&mut out).and(&a).and(&a).and(&a).and(&a).and(&a)
+        .apply(|x, &a, &b, &c, &d, &e| *x = a + b + c + d + e);
+    assert_eq!(out, arr1(&[5, 10, 15]));
+
+    let mut out2 = OwnedArray::zeros(3);
+    Zip::indexed(&mut out2).and(&a).and(&a).and(&a).and(&a).and(&a)
+        .apply(|i, x, &a, &b, &c, &d, &e| *x = i + a + b + c + d + e);
+    assert_eq!(out2, arr1(&[5, 11, 17]));
+}
+
+#[test]
+fn zip_indexed() {
+    let mut a = OwnedArray::zeros((3, 4, 2));
+    Zip::indexed(&mut a).apply(|(i, j, k), x| *x = 100 * i + 10 * j + k);
+    for ((i, j, k), &x) in a.indexed_iter() {
+        assert_eq!(x, 100 * i + 10 * j + k);
+    }
+
+    let b = a.t();
+    Zip::from(b).and(indices(b.dim())).apply(|&x, (k, j, i)| {
+        assert_eq!(x, 100 * i + 10 * j + k);
+    });
+}
+
+#[test]
+fn zip_fold_while() {
+    let a = OwnedArray::from_vec_dim((3, 3), (0..9).collect()).unwrap();
+    let sum = Zip::from(&a).fold_while(0, |acc, &x| FoldWhile::Continue(acc + x));
+    assert_eq!(sum, FoldWhile::Continue(36));
+
+    let first_big = Zip::indexed(&a).fold_while(None, |acc, ix, &x| {
+        if x > 4 { FoldWhile::Done(Some(ix)) } else { FoldWhile::Continue(acc) }
+    });
+    assert!(first_big.is_done());
+    assert_eq!(first_big.into_inner(), Some((1, 2)));
+}
+
+#[test]
+fn zip_map_collect() {
+    let a = arr2(&[[1., 2., 3.],
+                   [4., 5., 6.]]);
+    let b = Zip::from(&a).and(&a).map_collect(|&x, &y| x * y);
+    assert_eq!(b, &a * &a);
+    assert!(b.is_standard_layout());
+
+    let at = a.t();
+    let c = Zip::from(at).map_collect(|&x| x);
+    assert_eq!(c, at);
+
+    let d = Zip::from(a.slice(s![.., ..;-1])).map_collect(|&x| x + 1.);
+    assert_eq!(d, arr2(&[[4., 3., 2.],
+                         [7., 6., 5.]]));
+}
+
+#[test]
+fn zip_empty_and_zero_dim() {
+    let a = OwnedArray::<f32, _>::zeros((0, 3));
+    let mut count = 0;
+    Zip::from(&a).apply(|_| count += 1);
+    assert_eq!(count, 0);
+
+    let mut b = OwnedArray::from_elem((), 1);
+    Zip::from(&mut b).apply(|x| *x += 1);
+    assert_eq!(b[()], 2);
+}
+
+#[test]
+fn zip_split() {
+    let a = OwnedArray::from_vec_dim((4, 3), (0..12).collect()).unwrap();
+    let mut b = OwnedArray::zeros((4, 3));
+    {
+        let (z1, z2) = Zip::indexed(&mut b).and(&a).split();
+        assert_eq!(z1.size() + z2.size(), 12);
+        z1.apply(|_, x, &y| *x = y);
+        z2.apply(|_, x, &y| *x = y);
+    }
+    assert_eq!(a, b);
+}
+
+#[test]
+#[should_panic]
+fn zip_shape_mismatch() {
+    let a = OwnedArray::<f32, _>::zeros((3, 3));
+    let b = OwnedArray::<f32, _>::zeros((3, 4));
+    Zip::from(&a).and(&b).apply(|_, _| ());
+}